@@ -1,10 +1,101 @@
 use criterion::{criterion_group, criterion_main, Criterion};
 
+use std::alloc::{GlobalAlloc, Layout, System};
 use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use edn_rs::edn::{Atom, Interner};
+use edn_rs::{Edn, List, Map, Set, Tagged, Vector};
+
+// Tracks live heap bytes so the benchmark can report how much a parsed corpus retains.
+struct Counting;
+
+static LIVE_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+#[allow(unsafe_code)]
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        LIVE_BYTES.fetch_add(layout.size(), Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        LIVE_BYTES.fetch_sub(layout.size(), Ordering::Relaxed);
+        System.dealloc(ptr, layout);
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+const DOCUMENTS: usize = 1_000;
+
+fn retained_bytes<T>(f: impl FnOnce() -> T) -> (usize, T) {
+    let before = LIVE_BYTES.load(Ordering::Relaxed);
+    let kept = f();
+    (LIVE_BYTES.load(Ordering::Relaxed) - before, kept)
+}
 
 fn criterion_benchmark(c: &mut Criterion) {
     let edn = edn_str();
-    c.bench_function("parse", |b| b.iter(|| edn_rs::Edn::from_str(&edn)));
+    c.bench_function("parse", |b| b.iter(|| Edn::from_str(&edn)));
+    c.bench_function("parse_interned", |b| {
+        let mut interner = Interner::new();
+        b.iter(|| interner.parse(&edn));
+    });
+
+    report_retained("map", &edn);
+    report_retained("tx-data", &tx_data_str());
+    report_footprint(&corpus_str());
+}
+
+// How many bytes a parsed tree keeps alive for every byte of its source text.
+#[allow(clippy::cast_precision_loss)]
+fn report_footprint(edn: &str) {
+    let (bytes, doc) = retained_bytes(|| Edn::from_str(edn).unwrap());
+    drop(doc);
+    println!(
+        "corpus: {} input bytes retain {bytes} bytes ({:.1}x), Edn is {} bytes",
+        edn.len(),
+        bytes as f64 / edn.len() as f64,
+        std::mem::size_of::<Edn>()
+    );
+}
+
+// Rebuilds `edn` without sharing any keyword, symbol, map key or tag with another document.
+fn unshared(edn: &Edn) -> Edn {
+    match edn {
+        Edn::Key(k) => Edn::Key(Atom::from(k.as_str())),
+        Edn::Symbol(s) => Edn::Symbol(Atom::from(s.as_str())),
+        Edn::Vector(v) => Edn::Vector(Vector::new(
+            v.clone().to_vec().iter().map(unshared).collect(),
+        )),
+        Edn::List(l) => Edn::List(List::new(l.clone().to_vec().iter().map(unshared).collect())),
+        Edn::Set(s) => Edn::Set(Set::new(s.clone().to_set().iter().map(unshared).collect())),
+        Edn::Map(m) => Edn::Map(
+            m.iter()
+                .map(|(k, v)| (Atom::from(k.as_str()), unshared(v)))
+                .collect::<Map>(),
+        ),
+        Edn::Tagged(t) => Edn::Tagged(Tagged::new(t.tag(), unshared(t.value()))),
+        edn => edn.clone(),
+    }
+}
+
+fn report_retained(corpus: &str, edn: &str) {
+    let retained = |parse: &mut dyn FnMut() -> Edn| {
+        let (bytes, docs) = retained_bytes(|| (0..DOCUMENTS).map(|_| parse()).collect::<Vec<_>>());
+        drop(docs);
+        bytes
+    };
+    let owned = retained(&mut || unshared(&Edn::from_str(edn).unwrap()));
+    let per_document = retained(&mut || Edn::from_str(edn).unwrap());
+    let mut interner = Interner::new();
+    let shared = retained(&mut || interner.parse(edn).unwrap());
+    println!(
+        "{corpus}: {DOCUMENTS} documents retain {owned} bytes with owned keywords, \
+         {per_document} interned per document, {shared} with a shared interner"
+    );
 }
 
 criterion_group!(benches, criterion_benchmark);
@@ -34,3 +125,39 @@ fn edn_str() -> String {
     }"
     .to_string()
 }
+
+fn tx_data_str() -> String {
+    (0..20)
+        .map(|id| {
+            format!(
+                "[:db/add {id} :user/name \"user {id}\"]
+                 [:db/add {id} :user/role :role/contributor]
+                 [:db/add {id} :user/status :status/active]"
+            )
+        })
+        .fold(String::from("["), |tx, datom| tx + &datom)
+        + "]"
+}
+
+// Entities shaped like a typical application export: ids, tags, strings, numbers and nested
+// collections of keywords.
+fn corpus_str() -> String {
+    (0..500)
+        .map(|id| {
+            format!(
+                "{{:db/id {id}
+                  :user/uuid #uuid \"f81d4fae-7dec-11d0-a765-00a0c91e{id:04}\"
+                  :user/name \"User number {id}\"
+                  :user/email \"user{id}@example.com\"
+                  :user/created #inst \"2020-01-01T00:00:00.000-00:00\"
+                  :user/score {}.5
+                  :user/active? true
+                  :user/roles #{{:role/admin :role/editor}}
+                  :user/tags [:tag/new :tag/beta \"custom\"]
+                  :user/address {{:address/city \"Porto Alegre\" :address/zip {id}}}}}",
+                id % 100
+            )
+        })
+        .fold(String::from("["), |corpus, entity| corpus + &entity)
+        + "]"
+}
@@ -44,7 +44,6 @@ mod serde {
 
 mod edn {
     use criterion::Criterion;
-    use edn_derive::Serialize;
 
     use edn_rs::{map, set, Serialize};
     use std::collections::{BTreeMap, BTreeSet};
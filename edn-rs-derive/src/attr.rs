@@ -0,0 +1,264 @@
+use proc_macro2::Span;
+use syn::meta::ParseNestedMeta;
+use syn::{Attribute, Error, ExprPath, LitStr, Path, Result};
+
+/// How `rename_all` spells field and variant names.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Case {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+    ScreamingKebab,
+}
+
+impl Case {
+    fn parse(lit: &LitStr) -> Result<Self> {
+        Ok(match lit.value().as_str() {
+            "lowercase" => Self::Lower,
+            "UPPERCASE" => Self::Upper,
+            "PascalCase" => Self::Pascal,
+            "camelCase" => Self::Camel,
+            "snake_case" => Self::Snake,
+            "SCREAMING_SNAKE_CASE" => Self::ScreamingSnake,
+            "kebab-case" => Self::Kebab,
+            "SCREAMING-KEBAB-CASE" => Self::ScreamingKebab,
+            _ => {
+                return Err(Error::new(
+                    lit.span(),
+                    "unknown case, expected one of \"lowercase\", \"UPPERCASE\", \"PascalCase\", \
+                     \"camelCase\", \"snake_case\", \"SCREAMING_SNAKE_CASE\", \"kebab-case\" or \
+                     \"SCREAMING-KEBAB-CASE\"",
+                ))
+            }
+        })
+    }
+
+    pub fn apply(self, name: &str) -> String {
+        let words = words(name);
+        let lower = words.iter().map(|w| w.to_lowercase());
+        let upper = words.iter().map(|w| w.to_uppercase());
+        let capitalized = words.iter().map(|w| capitalize(w));
+        match self {
+            Self::Lower => lower.collect(),
+            Self::Upper => upper.collect(),
+            Self::Pascal => capitalized.collect(),
+            Self::Camel => lower
+                .take(1)
+                .chain(capitalized.skip(1))
+                .collect::<Vec<_>>()
+                .concat(),
+            Self::Snake => lower.collect::<Vec<_>>().join("_"),
+            Self::ScreamingSnake => upper.collect::<Vec<_>>().join("_"),
+            Self::Kebab => lower.collect::<Vec<_>>().join("-"),
+            Self::ScreamingKebab => upper.collect::<Vec<_>>().join("-"),
+        }
+    }
+}
+
+// Splits `snake_case`, `PascalCase` and `HTTPServer` style names into words.
+fn words(name: &str) -> Vec<String> {
+    let chars = name.chars().collect::<Vec<_>>();
+    let mut words = Vec::new();
+    let mut word = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if c == '_' || c == '-' {
+            if !word.is_empty() {
+                words.push(core::mem::take(&mut word));
+            }
+            continue;
+        }
+        if c.is_uppercase() && !word.is_empty() {
+            let prev = chars[i - 1];
+            let next_is_lower = chars.get(i + 1).is_some_and(|n| n.is_lowercase());
+            if prev.is_lowercase() || prev.is_numeric() || (prev.is_uppercase() && next_is_lower) {
+                words.push(core::mem::take(&mut word));
+            }
+        }
+        word.push(c);
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    chars.next().map_or_else(String::new, |first| {
+        first
+            .to_uppercase()
+            .chain(chars.flat_map(char::to_lowercase))
+            .collect()
+    })
+}
+
+/// How enum variants with data are written. Unit variants are always keywords.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Repr {
+    /// Only unit variants, as `:variant`.
+    Keyword,
+    /// `#variant value`, `#variant [a b]` or `#variant {:a 1}`.
+    Tagged,
+    /// `{:variant value}`, `{:variant [a b]}` or `{:variant {:a 1}}`.
+    Map,
+}
+
+/// `#[edn(...)]` on a struct or enum.
+#[derive(Default)]
+pub struct Container {
+    pub rename_all: Option<Case>,
+    pub namespace: Option<String>,
+    pub repr: Option<(Repr, Span)>,
+}
+
+impl Container {
+    pub fn from_attrs(attrs: &[Attribute]) -> Result<Self> {
+        let mut container = Self::default();
+        for_each_meta(attrs, |meta| {
+            if meta.path.is_ident("rename_all") {
+                container.rename_all = Some(Case::parse(&meta.value()?.parse()?)?);
+            } else if meta.path.is_ident("namespace") {
+                container.namespace = Some(name(&meta.value()?.parse()?)?);
+            } else if meta.path.is_ident("repr") {
+                let lit: LitStr = meta.value()?.parse()?;
+                let repr = match lit.value().as_str() {
+                    "keyword" => Repr::Keyword,
+                    "tagged" => Repr::Tagged,
+                    "map" => Repr::Map,
+                    _ => {
+                        return Err(Error::new(
+                            lit.span(),
+                            "unknown repr, expected \"keyword\", \"tagged\" or \"map\"",
+                        ))
+                    }
+                };
+                container.repr = Some((repr, lit.span()));
+            } else {
+                return Err(meta.error("unknown edn container attribute"));
+            }
+            Ok(())
+        })?;
+        Ok(container)
+    }
+}
+
+/// Where a missing or skipped field gets its value from.
+pub enum FieldDefault {
+    Trait,
+    Path(ExprPath),
+}
+
+/// `#[edn(...)]` on a field.
+#[derive(Default)]
+pub struct Field {
+    pub rename: Option<String>,
+    pub skip: bool,
+    pub default: Option<FieldDefault>,
+    pub flatten: bool,
+    pub with: Option<Path>,
+}
+
+impl Field {
+    pub fn from_attrs(attrs: &[Attribute]) -> Result<Self> {
+        let mut field = Self::default();
+        for_each_meta(attrs, |meta| {
+            if meta.path.is_ident("rename") {
+                field.rename = Some(name(&meta.value()?.parse()?)?);
+            } else if meta.path.is_ident("skip") {
+                field.skip = true;
+            } else if meta.path.is_ident("default") {
+                field.default = Some(if meta.input.peek(syn::Token![=]) {
+                    FieldDefault::Path(meta.value()?.parse::<LitStr>()?.parse()?)
+                } else {
+                    FieldDefault::Trait
+                });
+            } else if meta.path.is_ident("flatten") {
+                field.flatten = true;
+            } else if meta.path.is_ident("with") {
+                field.with = Some(meta.value()?.parse::<LitStr>()?.parse()?);
+            } else {
+                return Err(meta.error("unknown edn field attribute"));
+            }
+            Ok(())
+        })?;
+        Ok(field)
+    }
+
+    /// Whether any attribute besides `with` was given, as tuple fields take only `with`.
+    pub const fn has_key_attrs(&self) -> bool {
+        self.rename.is_some() || self.skip || self.default.is_some() || self.flatten
+    }
+}
+
+/// `#[edn(...)]` on an enum variant.
+#[derive(Default)]
+pub struct Variant {
+    pub rename: Option<String>,
+}
+
+impl Variant {
+    pub fn from_attrs(attrs: &[Attribute]) -> Result<Self> {
+        let mut variant = Self::default();
+        for_each_meta(attrs, |meta| {
+            if meta.path.is_ident("rename") {
+                variant.rename = Some(name(&meta.value()?.parse()?)?);
+            } else {
+                return Err(meta.error("unknown edn variant attribute"));
+            }
+            Ok(())
+        })?;
+        Ok(variant)
+    }
+}
+
+fn for_each_meta(
+    attrs: &[Attribute],
+    mut f: impl FnMut(ParseNestedMeta<'_>) -> Result<()>,
+) -> Result<()> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("edn"))
+        .try_for_each(|attr| attr.parse_nested_meta(&mut f))
+}
+
+// Names are written after the `:` of a keyword or the `#` of a tag, so they can't have
+// whitespace or EDN delimiters in them, or a leading `:`.
+fn name(lit: &LitStr) -> Result<String> {
+    let name = lit.value();
+    let invalid = |c: char| c.is_whitespace() || "()[]{}\",;#\\".contains(c);
+    if name.is_empty() || name.starts_with(':') || name.contains(invalid) {
+        return Err(Error::new(
+            lit.span(),
+            "expected a keyword name without the leading `:`, like \"name\" or \"person/name\"",
+        ));
+    }
+    Ok(name)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn cases() {
+        let cases = [
+            (Case::Lower, "maxretries", "httpserver"),
+            (Case::Upper, "MAXRETRIES", "HTTPSERVER"),
+            (Case::Pascal, "MaxRetries", "HttpServer"),
+            (Case::Camel, "maxRetries", "httpServer"),
+            (Case::Snake, "max_retries", "http_server"),
+            (Case::ScreamingSnake, "MAX_RETRIES", "HTTP_SERVER"),
+            (Case::Kebab, "max-retries", "http-server"),
+            (Case::ScreamingKebab, "MAX-RETRIES", "HTTP-SERVER"),
+        ];
+        for (case, field, variant) in cases {
+            assert_eq!(case.apply("max_retries"), field);
+            assert_eq!(case.apply("HTTPServer"), variant);
+        }
+        assert_eq!(Case::Kebab.apply("InProgress2Way"), "in-progress2-way");
+    }
+}
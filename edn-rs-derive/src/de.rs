@@ -0,0 +1,153 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{DeriveInput, Result};
+
+use crate::attr::{FieldDefault, Repr};
+use crate::model::{Body, Field, Input, Style, Variant};
+
+pub fn expand(input: &DeriveInput) -> Result<TokenStream> {
+    let input = Input::from_ast(input)?;
+    let ident = input.ident;
+    let name = ident.to_string();
+    let generics = input.generics_bound_by(&quote!(::edn_rs::Deserialize));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let body = match &input.body {
+        Body::Struct(style, fields) => {
+            let read = read_fields(*style, fields, &quote!(Self), &quote!(edn), &name);
+            quote!(::core::result::Result::Ok(#read))
+        }
+        Body::Enum(repr, variants) => read_enum(*repr, variants, &name),
+    };
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics ::edn_rs::Deserialize for #ident #ty_generics #where_clause {
+            fn deserialize(
+                edn: &::edn_rs::Edn,
+            ) -> ::core::result::Result<Self, ::edn_rs::EdnError> {
+                #body
+            }
+        }
+    })
+}
+
+// Reads a field's value out of `edn`, a `&Edn` expression.
+fn read_value(field: &Field, edn: &TokenStream) -> TokenStream {
+    field.attrs.with.as_ref().map_or_else(
+        || quote!(::edn_rs::Deserialize::deserialize(#edn)?),
+        |with| quote!(#with::deserialize(#edn)?),
+    )
+}
+
+fn default_value(default: &FieldDefault) -> TokenStream {
+    match default {
+        FieldDefault::Trait => quote!(::core::default::Default::default()),
+        FieldDefault::Path(path) => quote!(#path()),
+    }
+}
+
+// An expression building `constructor` out of `edn`, returning early on errors.
+fn read_fields(
+    style: Style,
+    fields: &[Field],
+    constructor: &TokenStream,
+    edn: &TokenStream,
+    name: &str,
+) -> TokenStream {
+    match style {
+        Style::Named => read_map(fields, constructor, edn),
+        Style::Tuple if fields.len() == 1 => {
+            let read = read_value(&fields[0], edn);
+            quote!(#constructor(#read))
+        }
+        Style::Tuple => {
+            let len = fields.len();
+            let reads = fields
+                .iter()
+                .enumerate()
+                .map(|(i, field)| read_value(field, &quote!(&items[#i])));
+            quote!({
+                let items = ::edn_rs::__private::expect_seq(#edn, #len, #name)?;
+                #constructor(#(#reads),*)
+            })
+        }
+        Style::Unit => quote!({
+            <() as ::edn_rs::Deserialize>::deserialize(#edn)?;
+            #constructor
+        }),
+    }
+}
+
+// A missing key reads as `nil`, so `Option` fields can be left out without a `default`.
+fn read_map(fields: &[Field], constructor: &TokenStream, edn: &TokenStream) -> TokenStream {
+    let inits = fields.iter().map(|field| {
+        let member = &field.member;
+        let value = if field.attrs.skip {
+            default_value(field.attrs.default.as_ref().unwrap_or(&FieldDefault::Trait))
+        } else if field.attrs.flatten {
+            read_value(field, edn)
+        } else {
+            let key = &field.key;
+            let read = read_value(field, &quote!(value));
+            let missing = field.attrs.default.as_ref().map_or_else(
+                || read_value(field, &quote!(&::edn_rs::Edn::Nil)),
+                default_value,
+            );
+            quote! {
+                match map.get(#key) {
+                    ::core::option::Option::Some(value) => #read,
+                    ::core::option::Option::None => #missing,
+                }
+            }
+        };
+        quote!(#member: #value)
+    });
+    let map = if fields
+        .iter()
+        .any(|field| !field.attrs.skip && !field.attrs.flatten)
+    {
+        quote!(let map = ::edn_rs::__private::expect_map(#edn)?;)
+    } else {
+        quote!(::edn_rs::__private::expect_map(#edn)?;)
+    };
+    quote!({
+        #map
+        #constructor { #(#inits),* }
+    })
+}
+
+fn read_enum(repr: Repr, variants: &[Variant<'_>], name: &str) -> TokenStream {
+    let repr = match repr {
+        Repr::Keyword => quote!(Keyword),
+        Repr::Tagged => quote!(Tagged),
+        Repr::Map => quote!(Map),
+    };
+    let arms = variants.iter().map(|variant| {
+        let ident = variant.ident;
+        let variant_name = &variant.name;
+        if variant.style == Style::Unit {
+            return quote! {
+                ::core::option::Option::Some((#variant_name, ::core::option::Option::None)) => {
+                    ::core::result::Result::Ok(Self::#ident)
+                }
+            };
+        }
+        let read = read_fields(
+            variant.style,
+            &variant.fields,
+            &quote!(Self::#ident),
+            &quote!(value),
+            name,
+        );
+        quote! {
+            ::core::option::Option::Some((#variant_name, ::core::option::Option::Some(value))) => {
+                ::core::result::Result::Ok(#read)
+            }
+        }
+    });
+    quote! {
+        match ::edn_rs::__private::variant(edn, ::edn_rs::__private::Repr::#repr) {
+            #(#arms)*
+            _ => ::core::result::Result::Err(::edn_rs::__private::unknown_variant(edn, #name)),
+        }
+    }
+}
@@ -0,0 +1,31 @@
+//! Derive macros for the `Serialize` and `Deserialize` traits of `edn-rs`. Deriving `Serialize`
+//! implements `ToEdn` too.
+//!
+//! Use them through the `derive` feature of `edn-rs`, which re-exports them next to the traits
+//! as `edn_rs::Serialize` and `edn_rs::Deserialize`. The attributes they take are documented
+//! there.
+
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput};
+
+mod attr;
+mod de;
+mod model;
+mod ser;
+mod to_edn;
+
+#[proc_macro_derive(Serialize, attributes(edn))]
+pub fn derive_serialize(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    ser::expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+#[proc_macro_derive(Deserialize, attributes(edn))]
+pub fn derive_deserialize(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    de::expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
@@ -0,0 +1,294 @@
+use proc_macro2::TokenStream;
+use syn::{
+    parse_quote, Data, DeriveInput, Error, Fields, Generics, Ident, Index, Member, Result, Type,
+};
+
+use crate::attr::{self, Case, Container, Repr};
+
+/// A struct or enum with its `#[edn(...)]` attributes resolved into keyword and tag names.
+pub struct Input<'a> {
+    pub ident: &'a Ident,
+    pub generics: &'a Generics,
+    pub body: Body<'a>,
+}
+
+pub enum Body<'a> {
+    Struct(Style, Vec<Field>),
+    Enum(Repr, Vec<Variant<'a>>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Style {
+    Named,
+    Tuple,
+    Unit,
+}
+
+pub struct Field {
+    pub member: Member,
+    /// The map key, like `:person/name`. Empty for tuple fields.
+    pub key: String,
+    pub ty: Type,
+    pub attrs: attr::Field,
+}
+
+pub struct Variant<'a> {
+    pub ident: &'a Ident,
+    /// The keyword or tag name without its `:` or `#`, like `shape/circle`.
+    pub name: String,
+    pub style: Style,
+    pub fields: Vec<Field>,
+}
+
+impl<'a> Input<'a> {
+    pub fn from_ast(input: &'a DeriveInput) -> Result<Self> {
+        let container = Container::from_attrs(&input.attrs)?;
+        let body = match &input.data {
+            Data::Struct(data) => {
+                if let Some((_, span)) = container.repr {
+                    return Err(Error::new(span, "`repr` is only supported on enums"));
+                }
+                let (style, fields) = fields(
+                    &data.fields,
+                    container.rename_all,
+                    container.namespace.as_deref(),
+                )?;
+                Body::Struct(style, fields)
+            }
+            Data::Enum(data) => {
+                let repr = container.repr.map_or(Repr::Tagged, |(repr, _)| repr);
+                let variants = data
+                    .variants
+                    .iter()
+                    .map(|variant| {
+                        let attrs = attr::Variant::from_attrs(&variant.attrs)?;
+                        let (style, fields) = fields(&variant.fields, container.rename_all, None)?;
+                        if repr == Repr::Keyword && style != Style::Unit {
+                            return Err(Error::new_spanned(
+                                variant,
+                                "`repr = \"keyword\"` only supports unit variants",
+                            ));
+                        }
+                        let name = attrs.rename.unwrap_or_else(|| {
+                            let ident = unraw(&variant.ident);
+                            container
+                                .rename_all
+                                .map_or_else(|| ident.clone(), |case| case.apply(&ident))
+                        });
+                        Ok(Variant {
+                            ident: &variant.ident,
+                            name: namespaced(container.namespace.as_deref(), name),
+                            style,
+                            fields,
+                        })
+                    })
+                    .collect::<Result<_>>()?;
+                Body::Enum(repr, variants)
+            }
+            Data::Union(_) => {
+                return Err(Error::new_spanned(
+                    input,
+                    "edn-rs can only derive for structs and enums",
+                ))
+            }
+        };
+        Ok(Self {
+            ident: &input.ident,
+            generics: &input.generics,
+            body,
+        })
+    }
+
+    /// The input's generics with `T: bound` added for every type parameter `T`.
+    pub fn generics_bound_by(&self, bound: &TokenStream) -> Generics {
+        let mut generics = self.generics.clone();
+        let params = generics
+            .type_params()
+            .map(|param| param.ident.clone())
+            .collect::<Vec<_>>();
+        let where_clause = generics.make_where_clause();
+        for param in params {
+            where_clause.predicates.push(parse_quote!(#param: #bound));
+        }
+        generics
+    }
+
+    /// Like `generics_bound_by`, plus `F: ToEdnMap` for the type `F` of every flattened field, so
+    /// a field that isn't always a map fails to compile instead of failing when written.
+    pub fn generics_bound_for_to_edn(&self, bound: &TokenStream) -> Generics {
+        let mut generics = self.generics_bound_by(bound);
+        let fields = match &self.body {
+            Body::Struct(_, fields) => fields.iter().collect::<Vec<_>>(),
+            Body::Enum(_, variants) => variants.iter().flat_map(|v| &v.fields).collect(),
+        };
+        let where_clause = generics.make_where_clause();
+        for field in fields {
+            if field.attrs.flatten {
+                let ty = &field.ty;
+                where_clause
+                    .predicates
+                    .push(parse_quote!(#ty: ::edn_rs::ToEdnMap));
+            }
+        }
+        generics
+    }
+}
+
+fn fields(
+    fields: &Fields,
+    rename_all: Option<Case>,
+    namespace: Option<&str>,
+) -> Result<(Style, Vec<Field>)> {
+    let style = match fields {
+        Fields::Named(_) => Style::Named,
+        Fields::Unnamed(_) => Style::Tuple,
+        Fields::Unit => Style::Unit,
+    };
+    let fields = fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| {
+            let attrs = attr::Field::from_attrs(&field.attrs)?;
+            let Some(ident) = &field.ident else {
+                if attrs.has_key_attrs() {
+                    return Err(Error::new_spanned(
+                        field,
+                        "tuple fields only support `with`",
+                    ));
+                }
+                return Ok(Field {
+                    member: Member::Unnamed(Index::from(i)),
+                    key: String::new(),
+                    ty: field.ty.clone(),
+                    attrs,
+                });
+            };
+            if attrs.flatten
+                && (attrs.rename.is_some()
+                    || attrs.skip
+                    || attrs.default.is_some()
+                    || attrs.with.is_some())
+            {
+                return Err(Error::new_spanned(
+                    field,
+                    "`flatten` can't be combined with `rename`, `skip`, `default` or `with`",
+                ));
+            }
+            if attrs.flatten && is_never_a_map(&field.ty) {
+                return Err(Error::new_spanned(
+                    &field.ty,
+                    "`flatten` needs a field that is written as a map",
+                ));
+            }
+            let name = attrs.rename.clone().unwrap_or_else(|| {
+                let ident = unraw(ident);
+                rename_all.map_or_else(|| ident.replace('_', "-"), |case| case.apply(&ident))
+            });
+            Ok(Field {
+                member: Member::Named(ident.clone()),
+                key: format!(":{}", namespaced(namespace, name)),
+                ty: field.ty.clone(),
+                attrs,
+            })
+        })
+        .collect::<Result<_>>()?;
+    Ok((style, fields))
+}
+
+// Catches the types that are plainly not maps with a clearer error than the `ToEdnMap` bound.
+fn is_never_a_map(ty: &Type) -> bool {
+    const NOT_MAPS: &[&str] = &[
+        "bool",
+        "char",
+        "str",
+        "String",
+        "i8",
+        "i16",
+        "i32",
+        "i64",
+        "i128",
+        "isize",
+        "u8",
+        "u16",
+        "u32",
+        "u64",
+        "u128",
+        "usize",
+        "f32",
+        "f64",
+        "Vec",
+        "VecDeque",
+        "LinkedList",
+        "BTreeSet",
+        "HashSet",
+        "BinaryHeap",
+    ];
+    match ty {
+        Type::Array(_) | Type::Slice(_) | Type::Tuple(_) => true,
+        Type::Group(group) => is_never_a_map(&group.elem),
+        Type::Paren(paren) => is_never_a_map(&paren.elem),
+        Type::Reference(reference) => is_never_a_map(&reference.elem),
+        Type::Path(path) if path.qself.is_none() => path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| NOT_MAPS.iter().any(|name| segment.ident == name)),
+        _ => false,
+    }
+}
+
+fn unraw(ident: &Ident) -> String {
+    let ident = ident.to_string();
+    ident
+        .strip_prefix("r#")
+        .map_or_else(|| ident.clone(), str::to_string)
+}
+
+// A name that already has a namespace, like a `rename = "person/name"`, keeps it.
+fn namespaced(namespace: Option<&str>, name: String) -> String {
+    match namespace {
+        Some(namespace) if !name.contains('/') => format!("{namespace}/{name}"),
+        _ => name,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn flatten_rejects_types_that_are_never_maps() {
+        let input: DeriveInput = parse_quote!(
+            struct A {
+                #[edn(flatten)]
+                b: Vec<u8>,
+            }
+        );
+        assert!(Input::from_ast(&input).is_err());
+        let input: DeriveInput = parse_quote!(
+            struct A {
+                #[edn(flatten)]
+                b: &'static str,
+            }
+        );
+        assert!(Input::from_ast(&input).is_err());
+        let input: DeriveInput = parse_quote!(
+            struct A {
+                #[edn(flatten)]
+                b: Option<B>,
+            }
+        );
+        assert!(Input::from_ast(&input).is_ok());
+    }
+
+    #[test]
+    fn flatten_rejects_with() {
+        let input: DeriveInput = parse_quote!(
+            struct A {
+                #[edn(flatten, with = "module")]
+                b: B,
+            }
+        );
+        assert!(Input::from_ast(&input).is_err());
+    }
+}
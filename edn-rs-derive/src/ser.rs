@@ -0,0 +1,215 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{DeriveInput, Result};
+
+use crate::attr::Repr;
+use crate::model::{Body, Field, Input, Style, Variant};
+
+pub fn expand(input: &DeriveInput) -> Result<TokenStream> {
+    let input = Input::from_ast(input)?;
+    let to_edn = crate::to_edn::expand(&input);
+    let ident = input.ident;
+    // Flattened fields are written through `ToEdnMap`.
+    let generics = input.generics_bound_for_to_edn(&quote!(::edn_rs::Serialize));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let body = match &input.body {
+        Body::Struct(style, fields) => {
+            let values = fields
+                .iter()
+                .map(|field| {
+                    let member = &field.member;
+                    (field, quote!(&self.#member))
+                })
+                .collect::<Vec<_>>();
+            let write = write_fields(*style, &values);
+            quote! {
+                #write
+                ::core::result::Result::Ok(())
+            }
+        }
+        Body::Enum(_, variants) if variants.is_empty() => quote!(match *self {}),
+        Body::Enum(repr, variants) => {
+            let write = write_enum(*repr, variants);
+            quote! {
+                #write
+                ::core::result::Result::Ok(())
+            }
+        }
+    };
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics ::edn_rs::Serialize for #ident #ty_generics #where_clause {
+            fn serialize_into(&self, w: &mut dyn ::core::fmt::Write) -> ::core::fmt::Result {
+                #body
+            }
+        }
+
+        #to_edn
+    })
+}
+
+// Writes `value`, a `&T` expression, into the `fmt::Write` called `w`.
+fn write_value(field: &Field, value: &TokenStream, w: &TokenStream) -> TokenStream {
+    field.attrs.with.as_ref().map_or_else(
+        || quote!(::edn_rs::Serialize::serialize_into(#value, #w)?;),
+        |with| quote!(#with::serialize_into(#value, #w)?;),
+    )
+}
+
+// The `write_*` functions generate statements, each ending in `?;`.
+fn write_fields(style: Style, values: &[(&Field, TokenStream)]) -> TokenStream {
+    match style {
+        Style::Named => write_map(values),
+        Style::Tuple if values.len() == 1 => {
+            let (field, value) = &values[0];
+            write_value(field, value, &quote!(w))
+        }
+        Style::Tuple => {
+            let write = write_seq(values);
+            quote! {
+                w.write_char('(')?;
+                #write
+                w.write_char(')')?;
+            }
+        }
+        Style::Unit => quote!(w.write_str("nil")?;),
+    }
+}
+
+fn write_seq(values: &[(&Field, TokenStream)]) -> TokenStream {
+    let writes = values.iter().enumerate().map(|(i, (field, value))| {
+        let write = write_value(field, value, &quote!(w));
+        if i == 0 {
+            write
+        } else {
+            quote! {
+                w.write_str(", ")?;
+                #write
+            }
+        }
+    });
+    quote!(#(#writes)*)
+}
+
+// Keeps the `{ :key value, }` layout that `Serialize` has always had for structs. Flattened
+// fields are turned into maps first, and their entries come last, without the keys the struct's
+// own fields have.
+fn write_map(values: &[(&Field, TokenStream)]) -> TokenStream {
+    let values = values
+        .iter()
+        .filter(|(field, _)| !field.attrs.skip)
+        .collect::<Vec<_>>();
+    let writes = values
+        .iter()
+        .filter(|(field, _)| !field.attrs.flatten)
+        .map(|(field, value)| {
+            let key = format!("{} ", field.key);
+            let write = write_value(field, value, &quote!(w));
+            quote! {
+                w.write_str(#key)?;
+                #write
+                w.write_str(", ")?;
+            }
+        });
+    let flattens = values
+        .iter()
+        .filter(|(field, _)| field.attrs.flatten)
+        .map(|(_, value)| {
+            quote!(::edn_rs::__private::flatten_into(&mut flattened, ::edn_rs::ToEdnMap::to_edn_map(#value));)
+        })
+        .collect::<Vec<_>>();
+    if flattens.is_empty() {
+        return quote! {
+            w.write_str("{ ")?;
+            #(#writes)*
+            w.write_char('}')?;
+        };
+    }
+    let keys = values
+        .iter()
+        .filter(|(field, _)| !field.attrs.flatten)
+        .map(|(field, _)| &field.key);
+    quote! {
+        let mut flattened = ::edn_rs::Map::empty();
+        #(#flattens)*
+        w.write_str("{ ")?;
+        #(#writes)*
+        ::edn_rs::__private::write_flattened(w, &flattened, &[#(#keys),*])?;
+        w.write_char('}')?;
+    }
+}
+
+fn write_enum(repr: Repr, variants: &[Variant<'_>]) -> TokenStream {
+    let arms = variants.iter().map(|variant| {
+        let ident = variant.ident;
+        let bindings = variant
+            .fields
+            .iter()
+            .enumerate()
+            .filter(|(_, field)| !field.attrs.skip)
+            .map(|(i, field)| (field, format_ident!("__field{}", i)))
+            .collect::<Vec<_>>();
+        let values = bindings
+            .iter()
+            .map(|(field, binding)| (*field, quote!(#binding)))
+            .collect::<Vec<_>>();
+        let pattern = match variant.style {
+            Style::Named => {
+                let members = bindings.iter().map(|(field, binding)| {
+                    let member = &field.member;
+                    quote!(#member: #binding)
+                });
+                quote!(Self::#ident { #(#members,)* .. })
+            }
+            Style::Tuple => {
+                let bindings = bindings.iter().map(|(_, binding)| binding);
+                quote!(Self::#ident(#(#bindings),*))
+            }
+            Style::Unit => quote!(Self::#ident),
+        };
+        let write = match (variant.style, repr) {
+            (Style::Unit, _) => {
+                let keyword = format!(":{}", variant.name);
+                quote!(w.write_str(#keyword)?;)
+            }
+            (style, Repr::Tagged | Repr::Keyword) => {
+                let tag = format!("#{} ", variant.name);
+                let write = write_variant_value(style, &values);
+                quote! {
+                    w.write_str(#tag)?;
+                    #write
+                }
+            }
+            (style, Repr::Map) => {
+                let key = format!("{{:{} ", variant.name);
+                let write = write_variant_value(style, &values);
+                quote! {
+                    w.write_str(#key)?;
+                    #write
+                    w.write_char('}')?;
+                }
+            }
+        };
+        quote!(#pattern => { #write })
+    });
+    quote! {
+        match self {
+            #(#arms)*
+        }
+    }
+}
+
+// Tuple variants are written as vectors, like the `serde` serializer does.
+fn write_variant_value(style: Style, values: &[(&Field, TokenStream)]) -> TokenStream {
+    match style {
+        Style::Tuple if values.len() > 1 => {
+            let write = write_seq(values);
+            quote! {
+                w.write_char('[')?;
+                #write
+                w.write_char(']')?;
+            }
+        }
+        _ => write_fields(style, values),
+    }
+}
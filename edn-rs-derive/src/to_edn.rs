@@ -0,0 +1,176 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+
+use crate::attr::Repr;
+use crate::model::{Body, Field, Input, Style, Variant};
+
+/// `ToEdn` for the input, building the `Edn` that its derived `Serialize` output parses into, and
+/// `ToEdnMap` for structs with named fields.
+pub fn expand(input: &Input<'_>) -> TokenStream {
+    let ident = input.ident;
+    let generics = input.generics_bound_for_to_edn(&quote!(::edn_rs::ToEdn));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let (body, to_edn_map) = match &input.body {
+        Body::Struct(style, fields) => {
+            let values = fields
+                .iter()
+                .map(|field| {
+                    let member = &field.member;
+                    (field, quote!(&self.#member))
+                })
+                .collect::<Vec<_>>();
+            if *style == Style::Named {
+                let map = map_to_edn(&values);
+                let to_edn_map = quote! {
+                    #[automatically_derived]
+                    impl #impl_generics ::edn_rs::ToEdnMap for #ident #ty_generics #where_clause {
+                        fn to_edn_map(&self) -> ::edn_rs::Map {
+                            #map
+                        }
+                    }
+                };
+                (
+                    quote!(::edn_rs::Edn::Map(::edn_rs::ToEdnMap::to_edn_map(self))),
+                    to_edn_map,
+                )
+            } else {
+                (fields_to_edn(*style, &values), quote!())
+            }
+        }
+        Body::Enum(_, variants) if variants.is_empty() => (quote!(match *self {}), quote!()),
+        Body::Enum(repr, variants) => (enum_to_edn(*repr, variants), quote!()),
+    };
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics ::edn_rs::ToEdn for #ident #ty_generics #where_clause {
+            fn to_edn(&self) -> ::edn_rs::Edn {
+                #body
+            }
+        }
+
+        #to_edn_map
+    }
+}
+
+// `value` is a `&T` expression. A `with` module only knows how to write the field, so its
+// output is read back.
+fn value_to_edn(field: &Field, value: &TokenStream) -> TokenStream {
+    field.attrs.with.as_ref().map_or_else(
+        || quote!(::edn_rs::ToEdn::to_edn(#value)),
+        |with| quote!(::edn_rs::__private::to_edn_with(|w| #with::serialize_into(#value, w))),
+    )
+}
+
+// The `*_to_edn` functions generate expressions of type `Edn`, except `map_to_edn`, which
+// generates a `Map`.
+fn fields_to_edn(style: Style, values: &[(&Field, TokenStream)]) -> TokenStream {
+    match style {
+        Style::Named => {
+            let map = map_to_edn(values);
+            quote!(::edn_rs::Edn::Map(#map))
+        }
+        Style::Tuple if values.len() == 1 => {
+            let (field, value) = &values[0];
+            value_to_edn(field, value)
+        }
+        Style::Tuple => {
+            let items = seq_to_edn(values);
+            quote!(::edn_rs::Edn::List(::edn_rs::List::new(#items)))
+        }
+        Style::Unit => quote!(::edn_rs::Edn::Nil),
+    }
+}
+
+fn seq_to_edn(values: &[(&Field, TokenStream)]) -> TokenStream {
+    let items = values
+        .iter()
+        .map(|(field, value)| value_to_edn(field, value));
+    quote!(::edn_rs::__private::Vec::from([#(#items),*]))
+}
+
+fn map_to_edn(values: &[(&Field, TokenStream)]) -> TokenStream {
+    let inserts = values
+        .iter()
+        .filter(|(field, _)| !field.attrs.skip)
+        .map(|(field, value)| {
+            if field.attrs.flatten {
+                quote!(::edn_rs::__private::flatten_into(&mut map, ::edn_rs::ToEdnMap::to_edn_map(#value));)
+            } else {
+                let key = &field.key;
+                let edn = value_to_edn(field, value);
+                quote!(map.insert(#key, #edn);)
+            }
+        });
+    quote! {{
+        let mut map = ::edn_rs::Map::empty();
+        #(#inserts)*
+        map
+    }}
+}
+
+fn enum_to_edn(repr: Repr, variants: &[Variant<'_>]) -> TokenStream {
+    let arms = variants.iter().map(|variant| {
+        let ident = variant.ident;
+        let bindings = variant
+            .fields
+            .iter()
+            .enumerate()
+            .filter(|(_, field)| !field.attrs.skip)
+            .map(|(i, field)| (field, format_ident!("__field{}", i)))
+            .collect::<Vec<_>>();
+        let values = bindings
+            .iter()
+            .map(|(field, binding)| (*field, quote!(#binding)))
+            .collect::<Vec<_>>();
+        let pattern = match variant.style {
+            Style::Named => {
+                let members = bindings.iter().map(|(field, binding)| {
+                    let member = &field.member;
+                    quote!(#member: #binding)
+                });
+                quote!(Self::#ident { #(#members,)* .. })
+            }
+            Style::Tuple => {
+                let bindings = bindings.iter().map(|(_, binding)| binding);
+                quote!(Self::#ident(#(#bindings),*))
+            }
+            Style::Unit => quote!(Self::#ident),
+        };
+        let edn = match (variant.style, repr) {
+            (Style::Unit, _) => {
+                let keyword = format!(":{}", variant.name);
+                quote!(::edn_rs::Edn::Key(#keyword.into()))
+            }
+            (style, Repr::Tagged | Repr::Keyword) => {
+                let tag = &variant.name;
+                let value = variant_value_to_edn(style, &values);
+                quote!(::edn_rs::Edn::Tagged(::edn_rs::Tagged::new(#tag, #value)))
+            }
+            (style, Repr::Map) => {
+                let key = format!(":{}", variant.name);
+                let value = variant_value_to_edn(style, &values);
+                quote! {{
+                    let mut map = ::edn_rs::Map::empty();
+                    map.insert(#key, #value);
+                    ::edn_rs::Edn::Map(map)
+                }}
+            }
+        };
+        quote!(#pattern => #edn,)
+    });
+    quote! {
+        match self {
+            #(#arms)*
+        }
+    }
+}
+
+fn variant_value_to_edn(style: Style, values: &[(&Field, TokenStream)]) -> TokenStream {
+    match style {
+        Style::Tuple if values.len() > 1 => {
+            let items = seq_to_edn(values);
+            quote!(::edn_rs::Edn::Vector(::edn_rs::Vector::new(#items)))
+        }
+        _ => fields_to_edn(style, values),
+    }
+}
@@ -21,12 +21,12 @@ fn test_edn_from_str() {
     assert_eq!(
         edn,
         Edn::Map(Map::new(map! {
-        ":a".to_string() => Edn::Str("2".to_string()),
+        ":a".to_string() => Edn::Str("2".into()),
         ":b".to_string() => Edn::Vector(Vector::new(vec![Edn::Bool(true), Edn::Bool(false)])),
         ":c".to_string() => Edn::Set(Set::new(
             set!{
-                Edn::Map(Map::new(map!{":a".to_string() => Edn::Key(":b".to_string())})),
-                Edn::Key(":A".to_string()),
+                Edn::Map(Map::new(map!{":a".to_string() => Edn::Key(":b".into())})),
+                Edn::Key(":A".into()),
                 Edn::Nil}))}))
     );
     assert_eq!(edn[":b"][0], Edn::Bool(true));
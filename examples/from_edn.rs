@@ -17,7 +17,7 @@ impl Deserialize for Person {
 
 fn person_ok() -> Result<(), EdnError> {
     let edn = Edn::Map(Map::new(map! {
-        ":name".to_string() => Edn::Str("rose".to_string()),
+        ":name".to_string() => Edn::Str("rose".into()),
         ":age".to_string() => Edn::UInt(66)
     }));
     let person: Person = edn_rs::from_edn(&edn)?;
@@ -37,8 +37,8 @@ fn person_ok() -> Result<(), EdnError> {
 
 fn person_mistyped() -> Result<(), EdnError> {
     let bad_edn = Edn::Map(Map::new(map! {
-        ":name".to_string() => Edn::Str("rose".to_string()),
-        ":age".to_string() => Edn::Str("some text".to_string())
+        ":name".to_string() => Edn::Str("rose".into()),
+        ":age".to_string() => Edn::Str("some text".into())
     }));
     let person: Result<Person, EdnError> = edn_rs::from_edn(&bad_edn);
 
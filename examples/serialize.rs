@@ -1,4 +1,4 @@
-use edn_derive::Serialize;
+use edn_rs::Serialize;
 use edn_rs::{hmap, hset, map, set};
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 
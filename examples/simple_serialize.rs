@@ -1,3 +1,5 @@
+use core::fmt::{self, Write};
+
 use edn_rs::Serialize;
 
 struct Foo<'a> {
@@ -6,8 +8,8 @@ struct Foo<'a> {
 }
 
 impl Serialize for Foo<'_> {
-    fn serialize(&self) -> String {
-        format!("{{:value {}, :say {:?}}}", self.value, self.say)
+    fn serialize_into(&self, w: &mut dyn Write) -> fmt::Result {
+        write!(w, "{{:value {}, :say {:?}}}", self.value, self.say)
     }
 }
 
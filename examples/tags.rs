@@ -11,11 +11,12 @@ fn inst_printer(inst: &Edn) {
 }
 
 fn print_tagged_or_panic(tagged_data: &Edn) {
-    if let Edn::Tagged(t, d) = tagged_data {
-        match t.as_str() {
+    if let Edn::Tagged(t) = tagged_data {
+        let d = t.value();
+        match t.tag() {
             "uuid" => uuid_printer(d),
             "inst" => inst_printer(d),
-            _ => println!("\"{t}\" tag with {d}"),
+            tag => println!("\"{tag}\" tag with {d}"),
         }
     } else {
         panic!();
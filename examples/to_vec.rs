@@ -3,9 +3,9 @@ use edn_rs::{Edn, List};
 fn to_vec() {
     let expected = vec![":my-key", "6", "7/4"];
     let v = Edn::List(List::new(vec![
-        Edn::Key(":my-key".to_string()),
+        Edn::Key(":my-key".into()),
         Edn::Int(6),
-        Edn::Rational("7/4".to_string()),
+        Edn::Rational("7/4".into()),
     ]));
 
     println!("{:?}", v.to_vec().unwrap());
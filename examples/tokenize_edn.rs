@@ -6,13 +6,13 @@ use edn_rs::{
 fn tokenize() {
     let edn = edn!((sym 1.2 3 false :f nil 3/4));
     let expected = Edn::List(List::new(vec![
-        Edn::Symbol("sym".to_string()),
+        Edn::Symbol("sym".into()),
         Edn::Double(1.2.into()),
         Edn::Int(3),
         Edn::Bool(false),
-        Edn::Key(":f".to_string()),
+        Edn::Key(":f".into()),
         Edn::Nil,
-        Edn::Rational("3/4".to_string()),
+        Edn::Rational("3/4".into()),
     ]));
 
     println!("{edn:?}");
@@ -0,0 +1,128 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{self, Write};
+use core::ops::{Deref, DerefMut};
+
+use crate::edn::{Edn, Error, Tagged};
+
+/// The tag bytes are written with, as in `#base64 "AQID"`.
+pub const TAG: &str = "base64";
+
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// `Bytes` is a `Vec<u8>` that is written as `#base64 "..."`, with the standard alphabet and
+/// padding, instead of a vector of numbers.
+///
+/// `Vec<u8>` can't be written differently than any other `Vec<T>`, so wrap it in `Bytes`, or
+/// use `#[edn(with = "edn_rs::base64")]` on a derived field.
+/// ```rust
+/// use edn_rs::Bytes;
+///
+/// assert_eq!(edn_rs::to_string(&Bytes(vec![1, 2, 3])), "#base64 \"AQID\"");
+/// assert_eq!(edn_rs::from_str("#base64 \"AQID\""), Ok(Bytes(vec![1, 2, 3])));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Bytes(pub Vec<u8>);
+
+impl From<Vec<u8>> for Bytes {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self(bytes)
+    }
+}
+
+impl From<Bytes> for Vec<u8> {
+    fn from(bytes: Bytes) -> Self {
+        bytes.0
+    }
+}
+
+impl Deref for Bytes {
+    type Target = Vec<u8>;
+
+    fn deref(&self) -> &Vec<u8> {
+        &self.0
+    }
+}
+
+impl DerefMut for Bytes {
+    fn deref_mut(&mut self) -> &mut Vec<u8> {
+        &mut self.0
+    }
+}
+
+/// Writes `bytes` as `#base64 "..."`. For `#[edn(with = "edn_rs::base64")]`.
+///
+/// # Errors
+///
+/// Any error from `w`.
+pub fn serialize_into(bytes: &[u8], w: &mut dyn Write) -> fmt::Result {
+    write!(w, "#{TAG} \"")?;
+    encode_into(bytes, w)?;
+    w.write_char('"')
+}
+
+/// Reads the bytes of a `#base64 "..."`. For `#[edn(with = "edn_rs::base64")]`.
+///
+/// # Errors
+///
+/// `EdnError::Deserialize` if `edn` isn't a `#base64` tagged string of valid base64.
+pub fn deserialize(edn: &Edn) -> Result<Vec<u8>, Error> {
+    match edn {
+        Edn::Tagged(t) if t.tag() == TAG => match t.value() {
+            Edn::Str(s) => decode(s),
+            _ => None,
+        },
+        _ => None,
+    }
+    .ok_or_else(|| Error::Deserialize(alloc::format!("couldn't convert `{edn}` into `base64`")))
+}
+
+pub(crate) fn to_edn(bytes: &[u8]) -> Edn {
+    let mut s = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    // Writing into a `String` can't fail.
+    let _ = encode_into(bytes, &mut s);
+    Edn::Tagged(Tagged::new(TAG, Edn::Str(s.into())))
+}
+
+fn encode_into(bytes: &[u8], w: &mut dyn Write) -> fmt::Result {
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = u32::from(b[0]) << 16 | u32::from(b[1]) << 8 | u32::from(b[2]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                w.write_char(char::from(ALPHABET[(n >> (18 - 6 * i)) as usize & 63]))?;
+            } else {
+                w.write_char('=')?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn decode(s: &str) -> Option<Vec<u8>> {
+    let s = s.as_bytes();
+    if !s.len().is_multiple_of(4) {
+        return None;
+    }
+    let mut bytes = Vec::with_capacity(s.len() / 4 * 3);
+    for (i, chunk) in s.chunks(4).enumerate() {
+        let last = i == s.len() / 4 - 1;
+        let padding = chunk.iter().rev().take_while(|&&c| c == b'=').count();
+        if padding > 2 || (padding > 0 && !last) {
+            return None;
+        }
+        let mut n = 0u32;
+        for &c in &chunk[..4 - padding] {
+            let digit = ALPHABET.iter().position(|&a| a == c)?;
+            n = n << 6 | u32::try_from(digit).ok()?;
+        }
+        n <<= 6 * padding;
+        let [_, b0, b1, b2] = n.to_be_bytes();
+        bytes.extend_from_slice(&[b0, b1, b2][..3 - padding]);
+    }
+    Some(bytes)
+}
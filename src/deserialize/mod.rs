@@ -1,22 +1,35 @@
-use alloc::collections::BTreeMap;
-#[cfg(feature = "sets")]
-use alloc::collections::BTreeSet;
+use alloc::borrow::{Cow, ToOwned};
+use alloc::boxed::Box;
+use alloc::collections::{BTreeMap, BTreeSet, BinaryHeap, LinkedList, VecDeque};
 use alloc::format;
+use alloc::rc::Rc;
 use alloc::string::{String, ToString};
+use alloc::sync::Arc;
 use alloc::vec::Vec;
 use core::any;
+use core::cmp::{Ordering, Reverse};
 use core::convert::{Into, TryFrom};
+use core::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+use core::num::{
+    NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+    NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize, Wrapping,
+};
 use core::str::FromStr;
+use core::time::Duration;
 #[cfg(feature = "std")]
 use std::collections::HashMap;
-#[cfg(all(feature = "sets", feature = "std"))]
+#[cfg(feature = "std")]
 use std::collections::HashSet;
 
+use crate::base64::{self, Bytes};
 use crate::edn::{Edn, Error};
+#[cfg(feature = "std")]
+use crate::inst;
+use crate::serialize::{Keyword, DURATION_TAG, IP_TAG, SOCKET_TAG};
 
 pub mod parse;
 
-#[cfg(feature = "sets")]
+#[cfg(feature = "ordered-float")]
 use ordered_float::OrderedFloat;
 
 /// public trait to be used to `Deserialize` structs.
@@ -70,12 +83,24 @@ use ordered_float::OrderedFloat;
 #[allow(clippy::missing_errors_doc)]
 pub trait Deserialize: Sized {
     fn deserialize(edn: &Edn) -> Result<Self, Error>;
+
+    /// Reads a map key, the reverse of `Serialize::serialize_key_into`. `String` reads a keyword
+    /// key without its `:`, so `{:a 1}` reads back into `{"a" => 1}`.
+    fn deserialize_key(edn: &Edn) -> Result<Self, Error> {
+        Self::deserialize(edn)
+    }
 }
 
 fn build_deserialize_error(edn: &Edn, type_: &str) -> Error {
     Error::Deserialize(format!("couldn't convert `{edn}` into `{type_}`"))
 }
 
+impl Deserialize for Edn {
+    fn deserialize(edn: &Edn) -> Result<Self, Error> {
+        Ok(edn.clone())
+    }
+}
+
 impl Deserialize for () {
     fn deserialize(edn: &Edn) -> Result<Self, Error> {
         match edn {
@@ -85,7 +110,7 @@ impl Deserialize for () {
     }
 }
 
-#[cfg(feature = "sets")]
+#[cfg(feature = "ordered-float")]
 impl Deserialize for OrderedFloat<f64> {
     fn deserialize(edn: &Edn) -> Result<Self, Error> {
         edn.to_float()
@@ -102,6 +127,15 @@ impl Deserialize for f64 {
     }
 }
 
+impl Deserialize for f32 {
+    #[allow(clippy::cast_possible_truncation)]
+    fn deserialize(edn: &Edn) -> Result<Self, Error> {
+        edn.to_float()
+            .ok_or_else(|| build_deserialize_error(edn, "edn_rs::Double"))
+            .map(|f| f as Self)
+    }
+}
+
 macro_rules! impl_deserialize_int {
     ( $( $name:ty ),+ ) => {
         $(
@@ -150,12 +184,19 @@ impl Deserialize for String {
                 if s.starts_with('\"') {
                     Ok(s.replace('\"', ""))
                 } else {
-                    Ok(s.clone())
+                    Ok(s.to_string())
                 }
             }
             e => Ok(e.to_string()),
         }
     }
+
+    fn deserialize_key(edn: &Edn) -> Result<Self, Error> {
+        match edn {
+            Edn::Key(k) => Ok(k.strip_prefix(':').unwrap_or(k).to_string()),
+            edn => Self::deserialize(edn),
+        }
+    }
 }
 
 impl Deserialize for char {
@@ -165,88 +206,126 @@ impl Deserialize for char {
     }
 }
 
-impl<T> Deserialize for Vec<T>
+// Vectors, lists and sets can all be read into a sequence.
+fn deserialize_seq<T, C>(edn: &Edn) -> Result<C, Error>
+where
+    T: Deserialize,
+    C: FromIterator<T>,
+{
+    match edn {
+        Edn::Vector(v) => v.iter().map(from_edn).collect(),
+        Edn::List(l) => l.iter().map(from_edn).collect(),
+        Edn::Set(s) => s.iter().map(from_edn).collect(),
+        _ => Err(build_deserialize_error(edn, any::type_name::<C>())),
+    }
+}
+
+// Tuples and arrays need an order, so they can't be read from sets.
+fn ordered_items(edn: &Edn) -> Option<&[Edn]> {
+    match edn {
+        Edn::Vector(v) => Some(v),
+        Edn::List(l) => Some(l),
+        _ => None,
+    }
+}
+
+macro_rules! impl_deserialize_seq {
+    ( $( $name:ident $( : $bound:path )? ),+ ) => {
+        $(
+            impl<T> Deserialize for $name<T>
+            where
+                T: Deserialize $( + $bound )?,
+            {
+                fn deserialize(edn: &Edn) -> Result<Self, Error> {
+                    deserialize_seq(edn)
+                }
+            }
+        )+
+    };
+}
+
+impl_deserialize_seq!(Vec, VecDeque, LinkedList, BinaryHeap: Ord);
+
+impl<T> Deserialize for Box<[T]>
 where
     T: Deserialize,
 {
     fn deserialize(edn: &Edn) -> Result<Self, Error> {
-        match edn {
-            Edn::Vector(_) => Ok(edn
-                .iter_some()
-                .ok_or_else(|| Error::Iter(format!("Could not create iter from {edn:?}")))?
-                .map(|e| Deserialize::deserialize(e))
-                .collect::<Result<Self, Error>>()?),
-            Edn::List(_) => Ok(edn
-                .iter_some()
-                .ok_or_else(|| Error::Iter(format!("Could not create iter from {edn:?}")))?
-                .map(|e| Deserialize::deserialize(e))
-                .collect::<Result<Self, Error>>()?),
-            #[cfg(feature = "sets")]
-            Edn::Set(_) => Ok(edn
-                .iter_some()
-                .ok_or_else(|| Error::Iter(format!("Could not create iter from {edn:?}")))?
-                .map(|e| Deserialize::deserialize(e))
-                .collect::<Result<Self, Error>>()?),
-            _ => Err(build_deserialize_error(edn, any::type_name::<Self>())),
-        }
+        deserialize_seq(edn)
+    }
+}
+
+impl<T, const N: usize> Deserialize for [T; N]
+where
+    T: Deserialize,
+{
+    fn deserialize(edn: &Edn) -> Result<Self, Error> {
+        ordered_items(edn)
+            .filter(|items| items.len() == N)
+            .ok_or_else(|| build_deserialize_error(edn, any::type_name::<Self>()))?
+            .iter()
+            .map(from_edn)
+            .collect::<Result<Vec<T>, Error>>()?
+            .try_into()
+            .map_err(|_| build_deserialize_error(edn, any::type_name::<Self>()))
+    }
+}
+
+// Keys are stored as the text they were parsed from, so they are read back into `Edn` first.
+fn deserialize_map<K, T, M>(edn: &Edn, name: &str) -> Result<M, Error>
+where
+    K: Deserialize,
+    T: Deserialize,
+    M: FromIterator<(K, T)>,
+{
+    match edn {
+        Edn::Map(map) => map
+            .iter()
+            .map(|(key, e)| {
+                let cannot =
+                    |_| Error::Deserialize(format!("Cannot safely deserialize {edn:?} to {name}"));
+                Ok((
+                    K::deserialize_key(&Edn::read_key(key)).map_err(cannot)?,
+                    Deserialize::deserialize(e).map_err(cannot)?,
+                ))
+            })
+            .collect(),
+        _ => Err(build_deserialize_error(edn, any::type_name::<M>())),
     }
 }
 
 #[cfg(feature = "std")]
-impl<T, H> Deserialize for HashMap<String, T, H>
+impl<K, T, H> Deserialize for HashMap<K, T, H>
 where
+    K: Deserialize + Eq + std::hash::Hash,
     T: Deserialize,
     H: std::hash::BuildHasher + std::default::Default,
 {
     fn deserialize(edn: &Edn) -> Result<Self, Error> {
-        match edn {
-            Edn::Map(_) => edn
-                .map_iter()
-                .ok_or_else(|| Error::Iter(format!("Could not create iter from {edn:?}")))?
-                .map(|(key, e)| {
-                    Ok((
-                        key.to_string(),
-                        Deserialize::deserialize(e).map_err(|_| {
-                            Error::Deserialize(format!(
-                                "Cannot safely deserialize {:?} to {}",
-                                edn, "HashMap"
-                            ))
-                        })?,
-                    ))
-                })
-                .collect::<Result<Self, Error>>(),
-            _ => Err(build_deserialize_error(edn, any::type_name::<Self>())),
-        }
+        deserialize_map(edn, "HashMap")
     }
 }
 
-impl<T> Deserialize for BTreeMap<String, T>
+impl<K, T> Deserialize for BTreeMap<K, T>
 where
+    K: Deserialize + Ord,
     T: Deserialize,
 {
+    fn deserialize(edn: &Edn) -> Result<Self, Error> {
+        deserialize_map(edn, "BTreeMap")
+    }
+}
+
+impl Deserialize for Keyword {
     fn deserialize(edn: &Edn) -> Result<Self, Error> {
         match edn {
-            Edn::Map(_) => edn
-                .map_iter()
-                .ok_or_else(|| Error::Iter(format!("Could not create iter from {edn:?}")))?
-                .map(|(key, e)| {
-                    Ok((
-                        key.to_string(),
-                        Deserialize::deserialize(e).map_err(|_| {
-                            Error::Deserialize(format!(
-                                "Cannot safely deserialize {:?} to {}",
-                                edn, "BTreeMap"
-                            ))
-                        })?,
-                    ))
-                })
-                .collect::<Result<Self, Error>>(),
-            _ => Err(build_deserialize_error(edn, any::type_name::<Self>())),
+            Edn::Key(k) => Ok(Self::new(k.strip_prefix(':').unwrap_or(k))),
+            _ => Err(build_deserialize_error(edn, "Keyword")),
         }
     }
 }
 
-#[cfg(all(feature = "sets", feature = "std"))]
+#[cfg(feature = "std")]
 impl<T, H> Deserialize for HashSet<T, H>
 where
     T: std::cmp::Eq + std::hash::Hash + Deserialize,
@@ -271,7 +350,6 @@ where
     }
 }
 
-#[cfg(feature = "sets")]
 impl<T> Deserialize for BTreeSet<T>
 where
     T: core::cmp::Eq + core::hash::Hash + core::cmp::Ord + Deserialize,
@@ -307,6 +385,256 @@ where
     }
 }
 
+// Integers out of the 64 bit range are read from the strings `Serialize` writes for them.
+macro_rules! impl_deserialize_wide_int {
+    ( $( $name:ty ),+ ) => {
+        $(
+            impl Deserialize for $name {
+                fn deserialize(edn: &Edn) -> Result<Self, Error> {
+                    match edn {
+                        Edn::Str(s) => s.parse().map_err(|_| build_deserialize_error(edn, "int")),
+                        Edn::UInt(u) => Self::try_from(*u)
+                            .map_err(|_| build_deserialize_error(edn, stringify!($name))),
+                        _ => {
+                            let int = edn
+                                .to_int()
+                                .ok_or_else(|| build_deserialize_error(edn, "int"))?;
+                            Self::try_from(int)
+                                .map_err(|_| build_deserialize_error(edn, stringify!($name)))
+                        }
+                    }
+                }
+            }
+        )+
+    };
+}
+
+impl_deserialize_wide_int!(i128, u128);
+
+macro_rules! impl_deserialize_non_zero {
+    ( $( $name:ty => $int:ty ),+ ) => {
+        $(
+            impl Deserialize for $name {
+                fn deserialize(edn: &Edn) -> Result<Self, Error> {
+                    Self::new(from_edn::<$int>(edn)?)
+                        .ok_or_else(|| build_deserialize_error(edn, stringify!($name)))
+                }
+            }
+        )+
+    };
+}
+
+impl_deserialize_non_zero!(
+    NonZeroI8 => i8,
+    NonZeroI16 => i16,
+    NonZeroI32 => i32,
+    NonZeroI64 => i64,
+    NonZeroI128 => i128,
+    NonZeroIsize => isize,
+    NonZeroU8 => u8,
+    NonZeroU16 => u16,
+    NonZeroU32 => u32,
+    NonZeroU64 => u64,
+    NonZeroU128 => u128,
+    NonZeroUsize => usize
+);
+
+macro_rules! impl_deserialize_wrapper {
+    ( $( $name:ident ),+ ) => {
+        $(
+            impl<T> Deserialize for $name<T>
+            where
+                T: Deserialize,
+            {
+                fn deserialize(edn: &Edn) -> Result<Self, Error> {
+                    from_edn(edn).map($name::new)
+                }
+
+                fn deserialize_key(edn: &Edn) -> Result<Self, Error> {
+                    T::deserialize_key(edn).map($name::new)
+                }
+            }
+        )+
+    };
+}
+
+impl_deserialize_wrapper!(Box, Rc, Arc);
+
+impl<T> Deserialize for Wrapping<T>
+where
+    T: Deserialize,
+{
+    fn deserialize(edn: &Edn) -> Result<Self, Error> {
+        from_edn(edn).map(Wrapping)
+    }
+}
+
+impl<T> Deserialize for Reverse<T>
+where
+    T: Deserialize,
+{
+    fn deserialize(edn: &Edn) -> Result<Self, Error> {
+        from_edn(edn).map(Reverse)
+    }
+}
+
+impl Deserialize for Box<str> {
+    fn deserialize(edn: &Edn) -> Result<Self, Error> {
+        from_edn::<String>(edn).map(String::into_boxed_str)
+    }
+
+    fn deserialize_key(edn: &Edn) -> Result<Self, Error> {
+        String::deserialize_key(edn).map(String::into_boxed_str)
+    }
+}
+
+impl<T> Deserialize for Cow<'_, T>
+where
+    T: ToOwned + ?Sized,
+    T::Owned: Deserialize,
+{
+    fn deserialize(edn: &Edn) -> Result<Self, Error> {
+        from_edn(edn).map(Cow::Owned)
+    }
+
+    fn deserialize_key(edn: &Edn) -> Result<Self, Error> {
+        T::Owned::deserialize_key(edn).map(Cow::Owned)
+    }
+}
+
+impl<T, E> Deserialize for Result<T, E>
+where
+    T: Deserialize,
+    E: Deserialize,
+{
+    fn deserialize(edn: &Edn) -> Result<Self, Error> {
+        match edn {
+            Edn::Tagged(t) if t.tag() == "Ok" => from_edn(t.value()).map(Ok),
+            Edn::Tagged(t) if t.tag() == "Err" => from_edn(t.value()).map(Err),
+            _ => Err(build_deserialize_error(edn, any::type_name::<Self>())),
+        }
+    }
+}
+
+// Any integer works, read by its sign like the result of Clojure's `compare`.
+impl Deserialize for Ordering {
+    fn deserialize(edn: &Edn) -> Result<Self, Error> {
+        match edn {
+            Edn::Int(i) => Ok(i.cmp(&0)),
+            Edn::UInt(u) => Ok(u.cmp(&0)),
+            _ => Err(build_deserialize_error(edn, "core::cmp::Ordering")),
+        }
+    }
+}
+
+// The string a `#tag "..."` holds.
+fn tagged_str<'a>(edn: &'a Edn, tag: &str) -> Option<&'a str> {
+    match edn {
+        Edn::Tagged(t) if t.tag() == tag => match t.value() {
+            Edn::Str(s) => Some(s),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+impl Deserialize for Duration {
+    fn deserialize(edn: &Edn) -> Result<Self, Error> {
+        let duration = match edn {
+            Edn::Tagged(t) if t.tag() == DURATION_TAG => match t.value() {
+                Edn::Map(map) => match (map.get(":secs"), map.get(":nanos")) {
+                    (Some(Edn::UInt(secs)), Some(Edn::UInt(nanos))) if *nanos < 1_000_000_000 => {
+                        u32::try_from(*nanos)
+                            .ok()
+                            .map(|nanos| Self::new(*secs, nanos))
+                    }
+                    _ => None,
+                },
+                _ => None,
+            },
+            _ => None,
+        };
+        duration.ok_or_else(|| build_deserialize_error(edn, "core::time::Duration"))
+    }
+}
+
+macro_rules! impl_deserialize_tagged_from_str {
+    ( $tag:ident: $( $name:ty ),+ ) => {
+        $(
+            impl Deserialize for $name {
+                fn deserialize(edn: &Edn) -> Result<Self, Error> {
+                    tagged_str(edn, $tag)
+                        .and_then(|s| s.parse().ok())
+                        .ok_or_else(|| build_deserialize_error(edn, any::type_name::<Self>()))
+                }
+            }
+        )+
+    };
+}
+
+impl_deserialize_tagged_from_str![IP_TAG: IpAddr, Ipv4Addr, Ipv6Addr];
+impl_deserialize_tagged_from_str![SOCKET_TAG: SocketAddr, SocketAddrV4, SocketAddrV6];
+
+impl Deserialize for Bytes {
+    fn deserialize(edn: &Edn) -> Result<Self, Error> {
+        base64::deserialize(edn).map(Bytes)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Deserialize for std::time::SystemTime {
+    fn deserialize(edn: &Edn) -> Result<Self, Error> {
+        tagged_str(edn, inst::TAG)
+            .and_then(inst::parse)
+            .ok_or_else(|| build_deserialize_error(edn, "std::time::SystemTime"))
+    }
+}
+
+#[cfg(feature = "std")]
+impl Deserialize for std::path::PathBuf {
+    fn deserialize(edn: &Edn) -> Result<Self, Error> {
+        match edn {
+            Edn::Str(s) => Ok(Self::from(&**s)),
+            _ => Err(build_deserialize_error(edn, "std::path::PathBuf")),
+        }
+    }
+}
+
+macro_rules! impl_deserialize_tuples {
+    ( $( ( $( $name:ident ),+ ) ),+ ) => {
+        $(
+            impl<$( $name: Deserialize ),+> Deserialize for ($( $name, )+) {
+                #[allow(non_snake_case)]
+                fn deserialize(edn: &Edn) -> Result<Self, Error> {
+                    let Some([$( $name ),+]) = ordered_items(edn) else {
+                        return Err(build_deserialize_error(edn, any::type_name::<Self>()));
+                    };
+                    Ok(($( from_edn($name)?, )+))
+                }
+            }
+        )+
+    };
+}
+
+impl_deserialize_tuples![
+    (A),
+    (A, B),
+    (A, B, C),
+    (A, B, C, D),
+    (A, B, C, D, E),
+    (A, B, C, D, E, F),
+    (A, B, C, D, E, F, G),
+    (A, B, C, D, E, F, G, H),
+    (A, B, C, D, E, F, G, H, I),
+    (A, B, C, D, E, F, G, H, I, J),
+    (A, B, C, D, E, F, G, H, I, J, K),
+    (A, B, C, D, E, F, G, H, I, J, K, L),
+    (A, B, C, D, E, F, G, H, I, J, K, L, M),
+    (A, B, C, D, E, F, G, H, I, J, K, L, M, N),
+    (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O),
+    (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P)
+];
+
 /// `from_str` deserializes an EDN String into type `T` that implements `Deserialize`. Response is `Result<T, EdnError>`
 ///
 /// # Errors
@@ -385,7 +713,7 @@ pub fn from_str<T: Deserialize>(s: &str) -> Result<T, Error> {
 /// }
 ///
 /// let edn = Edn::Map(Map::new(map! {
-///     ":name".to_string() => Edn::Str("rose".to_string()),
+///     ":name".to_string() => Edn::Str("rose".into()),
 ///     ":age".to_string() => Edn::UInt(66)
 /// }));
 /// let person: Person = edn_rs::from_edn(&edn).unwrap();
@@ -402,8 +730,8 @@ pub fn from_str<T: Deserialize>(s: &str) -> Result<T, Error> {
 /// );
 ///
 /// let bad_edn = Edn::Map(Map::new(map! {
-///     ":name".to_string() => Edn::Str("rose".to_string()),
-///     ":age".to_string() => Edn::Str("some text".to_string())
+///     ":name".to_string() => Edn::Str("rose".into()),
+///     ":age".to_string() => Edn::Str("some text".into())
 /// }));
 /// let person: Result<Person, EdnError> = edn_rs::from_edn(&bad_edn);
 ///
@@ -1,60 +1,64 @@
-use alloc::boxed::Box;
-use alloc::collections::BTreeMap;
-#[cfg(feature = "sets")]
-use alloc::collections::BTreeSet;
+use alloc::collections::{BTreeMap, BTreeSet};
 use alloc::string::{String, ToString};
 use alloc::vec::Vec;
 use alloc::{format, vec};
 use core::iter;
 use core::primitive::str;
 
-#[cfg(feature = "sets")]
-use crate::edn::Set;
-use crate::edn::{Edn, Error, List, Map, Vector};
+use crate::edn::{Atom, Edn, Error, Interner, List, Set, Tagged, Vector};
 
-const DELIMITERS: [char; 8] = [',', ']', '}', ')', ';', '(', '[', '{'];
+pub const DELIMITERS: [char; 8] = [',', ']', '}', ')', ';', '(', '[', '{'];
 
 pub fn parse(edn: &str) -> Result<Edn, Error> {
+    parse_with(edn, &mut Interner::new())
+}
+
+pub fn parse_with(edn: &str, interner: &mut Interner) -> Result<Edn, Error> {
     let owned = String::from(edn);
     let mut tokens = owned.chars().enumerate();
-    (parse_internal(tokens.next(), &mut tokens)?).map_or_else(|| Ok(Edn::Empty), Ok)
+    (parse_internal(tokens.next(), &mut tokens, interner)?).map_or_else(|| Ok(Edn::Empty), Ok)
 }
 
 fn parse_consuming(
     c: Option<(usize, char)>,
     chars: &mut iter::Enumerate<core::str::Chars<'_>>,
+    interner: &mut Interner,
 ) -> Result<Edn, Error> {
-    (parse_internal(c, chars)?).map_or_else(|| Ok(Edn::Empty), Ok)
+    (parse_internal(c, chars, interner)?).map_or_else(|| Ok(Edn::Empty), Ok)
 }
 
 fn parse_internal(
     c: Option<(usize, char)>,
     chars: &mut iter::Enumerate<core::str::Chars<'_>>,
+    interner: &mut Interner,
 ) -> Result<Option<Edn>, Error> {
     Ok(match c {
-        Some((_, '[')) => Some(read_vec(chars)?),
-        Some((_, '(')) => Some(read_list(chars)?),
-        Some((_, '#')) => tagged_or_set_or_discard(chars)?,
-        Some((_, '{')) => Some(read_map(chars)?),
+        Some((_, '[')) => Some(read_vec(chars, interner)?),
+        Some((_, '(')) => Some(read_list(chars, interner)?),
+        Some((_, '#')) => tagged_or_set_or_discard(chars, interner)?,
+        Some((_, '{')) => Some(read_map(chars, interner)?),
         Some((_, ';')) => {
             // Consumes the content
             chars.find(|c| c.1 == '\n');
-            read_if_not_container_end(chars)?
+            read_if_not_container_end(chars, interner)?
+        }
+        Some((_, s)) if s.is_whitespace() || s == ',' => {
+            read_if_not_container_end(chars, interner)?
         }
-        Some((_, s)) if s.is_whitespace() || s == ',' => read_if_not_container_end(chars)?,
         None => None,
-        edn => Some(edn_element(edn, chars)?),
+        edn => Some(edn_element(edn, chars, interner)?),
     })
 }
 
 fn edn_element(
     c: Option<(usize, char)>,
     chars: &mut iter::Enumerate<core::str::Chars<'_>>,
+    interner: &mut Interner,
 ) -> Result<Edn, Error> {
     match c {
         Some((_, '\"')) => read_str(chars),
-        Some((_, ':')) => Ok(read_key(chars)),
-        Some((_, n)) if n.is_numeric() => Ok(read_number(n, chars)?),
+        Some((_, ':')) => Ok(read_key(chars, interner)),
+        Some((_, n)) if n.is_numeric() => Ok(read_number(n, chars, interner)?),
         Some((_, n))
             if (n == '-' || n == '+')
                 && chars
@@ -63,26 +67,46 @@ fn edn_element(
                     .peek()
                     .is_some_and(|n| n.1.is_numeric()) =>
         {
-            Ok(read_number(n, chars)?)
+            Ok(read_number(n, chars, interner)?)
         }
         Some((_, '\\')) => Ok(read_char(chars)?),
-        Some((_, b)) if b == 't' || b == 'f' || b == 'n' => Ok(read_bool_or_nil(b, chars)?),
-        Some((_, a)) => Ok(read_symbol(a, chars)?),
+        Some((_, b)) if b == 't' || b == 'f' || b == 'n' => {
+            Ok(read_bool_or_nil(b, chars, interner)?)
+        }
+        Some((_, a)) => Ok(read_symbol(a, chars, interner)?),
         None => Err(Error::ParseEdn("Edn could not be parsed".to_string())),
     }
 }
 
 fn tagged_or_set_or_discard(
     chars: &mut iter::Enumerate<core::str::Chars<'_>>,
+    interner: &mut Interner,
 ) -> Result<Option<Edn>, Error> {
     match chars.clone().next() {
-        Some((_, '{')) => read_set(chars).map(Some),
-        Some((_, '_')) => read_discard(chars),
-        _ => read_tagged(chars).map(Some),
+        Some((_, '{')) => read_set(chars, interner).map(Some),
+        Some((_, '_')) => read_discard(chars, interner),
+        Some((_, '#')) => read_symbolic_value(chars).map(Some),
+        _ => read_tagged(chars, interner).map(Some),
+    }
+}
+
+// `##Inf`, `##-Inf` and `##NaN`, the floats that can't be written as digits.
+fn read_symbolic_value(chars: &mut iter::Enumerate<core::str::Chars<'_>>) -> Result<Edn, Error> {
+    let _second_hash = chars.next();
+    let c_len = chars
+        .clone()
+        .take_while(|(_, c)| !c.is_whitespace() && !DELIMITERS.contains(c))
+        .count();
+    let value = chars.take(c_len).map(|c| c.1).collect::<String>();
+    match value.as_str() {
+        "Inf" => Ok(Edn::Double(f64::INFINITY.into())),
+        "-Inf" => Ok(Edn::Double(f64::NEG_INFINITY.into())),
+        "NaN" => Ok(Edn::Double(f64::NAN.into())),
+        _ => Err(Error::ParseEdn(format!("Unknown symbolic value ##{value}"))),
     }
 }
 
-fn read_key(chars: &mut iter::Enumerate<core::str::Chars<'_>>) -> Edn {
+fn read_key(chars: &mut iter::Enumerate<core::str::Chars<'_>>, interner: &mut Interner) -> Edn {
     let key_chars = chars
         .clone()
         .take_while(|c| !c.1.is_whitespace() && !DELIMITERS.contains(&c.1));
@@ -91,51 +115,53 @@ fn read_key(chars: &mut iter::Enumerate<core::str::Chars<'_>>) -> Edn {
     let mut key = String::from(":");
     let key_chars = chars.take(c_len).map(|c| c.1).collect::<String>();
     key.push_str(&key_chars);
-    Edn::Key(key)
+    Edn::Key(interner.intern(&key))
 }
 
 fn read_str(chars: &mut iter::Enumerate<core::str::Chars<'_>>) -> Result<Edn, Error> {
-    let result = chars.try_fold(
-        (false, String::new()),
-        |(last_was_escape, mut s), (_, c)| {
-            if last_was_escape {
-                // Supported escape characters, per https://github.com/edn-format/edn#strings
-                match c {
-                    't' => s.push('\t'),
-                    'r' => s.push('\r'),
-                    'n' => s.push('\n'),
-                    '\\' => s.push('\\'),
-                    '\"' => s.push('\"'),
-                    _ => {
-                        return Err(Err(Error::ParseEdn(format!(
-                            "Invalid escape sequence \\{c}"
-                        ))))
-                    }
-                };
-
-                Ok((false, s))
-            } else if c == '\"' {
-                // Unescaped quote means we're done
-                Err(Ok(s))
-            } else if c == '\\' {
-                Ok((true, s))
-            } else {
-                s.push(c);
-                Ok((false, s))
-            }
-        },
-    );
-
-    match result {
-        // An Ok means we actually finished parsing *without* seeing the end of the string, so that's
-        // an error.
-        Ok(_) => Err(Error::ParseEdn("Unterminated string".to_string())),
-        Err(Err(e)) => Err(e),
-        Err(Ok(string)) => Ok(Edn::Str(string)),
+    let mut s = String::new();
+    loop {
+        match chars.next() {
+            // Unescaped quote means we're done
+            Some((_, '\"')) => return Ok(Edn::Str(s.into())),
+            // Supported escape characters, per https://github.com/edn-format/edn#strings
+            Some((_, '\\')) => match chars.next() {
+                Some((_, 't')) => s.push('\t'),
+                Some((_, 'r')) => s.push('\r'),
+                Some((_, 'n')) => s.push('\n'),
+                Some((_, '\\')) => s.push('\\'),
+                Some((_, '\"')) => s.push('\"'),
+                Some((_, 'u')) => {
+                    let hex = chars.by_ref().take(4).map(|c| c.1).collect::<String>();
+                    s.push(unicode_escape(&hex).ok_or_else(|| {
+                        Error::ParseEdn(format!("Invalid escape sequence \\u{hex}"))
+                    })?);
+                }
+                Some((_, c)) => {
+                    return Err(Error::ParseEdn(format!("Invalid escape sequence \\{c}")))
+                }
+                None => break,
+            },
+            Some((_, c)) => s.push(c),
+            None => break,
+        }
     }
+    Err(Error::ParseEdn("Unterminated string".to_string()))
 }
 
-fn read_symbol(a: char, chars: &mut iter::Enumerate<core::str::Chars<'_>>) -> Result<Edn, Error> {
+// The character for the 4 hex digits of a `\uXXXX` escape, in strings and chars alike.
+fn unicode_escape(hex: &str) -> Option<char> {
+    if hex.len() != 4 || !hex.bytes().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    char::from_u32(u32::from_str_radix(hex, 16).ok()?)
+}
+
+fn read_symbol(
+    a: char,
+    chars: &mut iter::Enumerate<core::str::Chars<'_>>,
+    interner: &mut Interner,
+) -> Result<Edn, Error> {
     let c_len = chars
         .clone()
         .enumerate()
@@ -156,38 +182,49 @@ fn read_symbol(a: char, chars: &mut iter::Enumerate<core::str::Chars<'_>>) -> Re
     let mut symbol = String::from(a);
     let symbol_chars = chars.take(c_len).map(|c| c.1).collect::<String>();
     symbol.push_str(&symbol_chars);
-    Ok(Edn::Symbol(symbol))
+    Ok(Edn::Symbol(interner.intern(&symbol)))
 }
 
-fn read_tagged(chars: &mut iter::Enumerate<core::str::Chars<'_>>) -> Result<Edn, Error> {
+fn read_tagged(
+    chars: &mut iter::Enumerate<core::str::Chars<'_>>,
+    interner: &mut Interner,
+) -> Result<Edn, Error> {
     let tag = chars
         .take_while(|c| !c.1.is_whitespace() && c.1 != ',')
         .map(|c| c.1)
         .collect::<String>();
 
-    Ok(Edn::Tagged(
+    let tag = interner.intern(&tag);
+    Ok(Edn::Tagged(Tagged::new(
         tag,
-        Box::new(parse_consuming(chars.next(), chars)?),
-    ))
+        parse_consuming(chars.next(), chars, interner)?,
+    )))
 }
 
-fn read_discard(chars: &mut iter::Enumerate<core::str::Chars<'_>>) -> Result<Option<Edn>, Error> {
+fn read_discard(
+    chars: &mut iter::Enumerate<core::str::Chars<'_>>,
+    interner: &mut Interner,
+) -> Result<Option<Edn>, Error> {
     let _discard_underscore = chars.next();
     let i = chars
         .clone()
         .next()
         .ok_or_else(|| Error::ParseEdn("Could not identify symbol index".to_string()))?
         .0;
-    match parse_consuming(chars.next(), chars) {
+    match parse_consuming(chars.next(), chars, interner) {
         Err(e) => Err(e),
         Ok(Edn::Empty) => Err(Error::ParseEdn(format!(
             "Discard sequence must have a following element at char count {i}"
         ))),
-        _ => read_if_not_container_end(chars),
+        _ => read_if_not_container_end(chars, interner),
     }
 }
 
-fn read_number(n: char, chars: &mut iter::Enumerate<core::str::Chars<'_>>) -> Result<Edn, Error> {
+fn read_number(
+    n: char,
+    chars: &mut iter::Enumerate<core::str::Chars<'_>>,
+    interner: &mut Interner,
+) -> Result<Edn, Error> {
     let c_len = chars
         .clone()
         .take_while(|(_, c)| !c.is_whitespace() && !DELIMITERS.contains(c))
@@ -259,11 +296,11 @@ fn read_number(n: char, chars: &mut iter::Enumerate<core::str::Chars<'_>>) -> Re
         }
         n if n.parse::<f64>().is_ok() => Ok(Edn::Double(n.parse::<f64>()?.into())),
         n if n.contains('/') && n.split('/').all(|d| d.parse::<f64>().is_ok()) => {
-            Ok(Edn::Rational(n))
+            Ok(Edn::Rational(n.into()))
         }
         n if n.to_uppercase().chars().filter(|c| c == &'E').count() > 1 => {
             let mut n = n.chars();
-            read_symbol(n.next().unwrap_or(' '), &mut n.enumerate())
+            read_symbol(n.next().unwrap_or(' '), &mut n.enumerate(), interner)
         }
         _ => Err(Error::ParseEdn(format!(
             "{number} could not be parsed with radix {radix}"
@@ -284,6 +321,15 @@ fn read_char(chars: &mut iter::Enumerate<core::str::Chars<'_>>) -> Result<Edn, E
         let _ = chars.take(n).map(|c| c.1).collect::<String>();
     };
 
+    let unicode = element
+        .strip_prefix('u')
+        .and_then(|hex| hex.get(..4))
+        .and_then(unicode_escape);
+    if let Some(c) = unicode {
+        consume_chars(5);
+        return Ok(Edn::Char(c));
+    }
+
     match element {
         _ if element.starts_with("newline") => {
             consume_chars(7);
@@ -301,6 +347,7 @@ fn read_char(chars: &mut iter::Enumerate<core::str::Chars<'_>>) -> Result<Edn, E
             consume_chars(5);
             Ok(Edn::Char(' '))
         }
+
         c if !c.is_empty() => {
             consume_chars(1);
             Ok(Edn::Char(c.chars().next().unwrap()))
@@ -314,6 +361,7 @@ fn read_char(chars: &mut iter::Enumerate<core::str::Chars<'_>>) -> Result<Edn, E
 fn read_bool_or_nil(
     c: char,
     chars: &mut iter::Enumerate<core::str::Chars<'_>>,
+    interner: &mut Interner,
 ) -> Result<Edn, Error> {
     let i = chars
         .clone()
@@ -371,11 +419,14 @@ fn read_bool_or_nil(
                 ))),
             }
         }
-        _ => read_symbol(c, chars),
+        _ => read_symbol(c, chars, interner),
     }
 }
 
-fn read_vec(chars: &mut iter::Enumerate<core::str::Chars<'_>>) -> Result<Edn, Error> {
+fn read_vec(
+    chars: &mut iter::Enumerate<core::str::Chars<'_>>,
+    interner: &mut Interner,
+) -> Result<Edn, Error> {
     let i = chars
         .clone()
         .next()
@@ -386,7 +437,7 @@ fn read_vec(chars: &mut iter::Enumerate<core::str::Chars<'_>>) -> Result<Edn, Er
         match chars.next() {
             Some((_, ']')) => return Ok(Edn::Vector(Vector::new(res))),
             Some(c) => {
-                if let Some(e) = parse_internal(Some(c), chars)? {
+                if let Some(e) = parse_internal(Some(c), chars, interner)? {
                     res.push(e);
                 }
             }
@@ -399,7 +450,10 @@ fn read_vec(chars: &mut iter::Enumerate<core::str::Chars<'_>>) -> Result<Edn, Er
     }
 }
 
-fn read_list(chars: &mut iter::Enumerate<core::str::Chars<'_>>) -> Result<Edn, Error> {
+fn read_list(
+    chars: &mut iter::Enumerate<core::str::Chars<'_>>,
+    interner: &mut Interner,
+) -> Result<Edn, Error> {
     let i = chars
         .clone()
         .next()
@@ -410,7 +464,7 @@ fn read_list(chars: &mut iter::Enumerate<core::str::Chars<'_>>) -> Result<Edn, E
         match chars.next() {
             Some((_, ')')) => return Ok(Edn::List(List::new(res))),
             Some(c) => {
-                if let Some(e) = parse_internal(Some(c), chars)? {
+                if let Some(e) = parse_internal(Some(c), chars, interner)? {
                     res.push(e);
                 }
             }
@@ -423,8 +477,10 @@ fn read_list(chars: &mut iter::Enumerate<core::str::Chars<'_>>) -> Result<Edn, E
     }
 }
 
-#[cfg(feature = "sets")]
-fn read_set(chars: &mut iter::Enumerate<core::str::Chars<'_>>) -> Result<Edn, Error> {
+fn read_set(
+    chars: &mut iter::Enumerate<core::str::Chars<'_>>,
+    interner: &mut Interner,
+) -> Result<Edn, Error> {
     let _discard_brackets = chars.next();
     let i = chars
         .clone()
@@ -436,7 +492,7 @@ fn read_set(chars: &mut iter::Enumerate<core::str::Chars<'_>>) -> Result<Edn, Er
         match chars.next() {
             Some((_, '}')) => return Ok(Edn::Set(Set::new(res))),
             Some(c) => {
-                if let Some(e) = parse_internal(Some(c), chars)? {
+                if let Some(e) = parse_internal(Some(c), chars, interner)? {
                     res.insert(e);
                 }
             }
@@ -449,30 +505,26 @@ fn read_set(chars: &mut iter::Enumerate<core::str::Chars<'_>>) -> Result<Edn, Er
     }
 }
 
-#[cfg(not(feature = "sets"))]
-fn read_set(_chars: &mut iter::Enumerate<core::str::Chars<'_>>) -> Result<Edn, Error> {
-    Err(Error::ParseEdn(
-        "Could not parse set due to feature not being enabled".to_string(),
-    ))
-}
-
-fn read_map(chars: &mut iter::Enumerate<core::str::Chars<'_>>) -> Result<Edn, Error> {
+fn read_map(
+    chars: &mut iter::Enumerate<core::str::Chars<'_>>,
+    interner: &mut Interner,
+) -> Result<Edn, Error> {
     let i = chars
         .clone()
         .next()
         .ok_or_else(|| Error::ParseEdn("Could not identify symbol index".to_string()))?
         .0;
-    let mut res: BTreeMap<String, Edn> = BTreeMap::new();
+    let mut res: BTreeMap<Atom, Edn> = BTreeMap::new();
     let mut key: Option<Edn> = None;
     let mut val: Option<Edn> = None;
     loop {
         match chars.next() {
-            Some((_, '}')) => return Ok(Edn::Map(Map::new(res))),
+            Some((_, '}')) => return Ok(Edn::Map(res.into())),
             Some(c) => {
                 if key.is_some() {
-                    val = Some(parse_consuming(Some(c), chars)?);
+                    val = Some(parse_consuming(Some(c), chars, interner)?);
                 } else {
-                    key = parse_internal(Some(c), chars)?;
+                    key = parse_internal(Some(c), chars, interner)?;
                 }
             }
             err => {
@@ -483,19 +535,29 @@ fn read_map(chars: &mut iter::Enumerate<core::str::Chars<'_>>) -> Result<Edn, Er
         }
 
         if key.is_some() && val.is_some() {
-            res.insert(key.unwrap().to_string(), val.unwrap());
+            res.insert(map_key(key.unwrap(), interner), val.unwrap());
             key = None;
             val = None;
         }
     }
 }
 
+// Keywords and symbols are interned as they are read, so a map keyed by them shares the parsed
+// atom. Other keys are interned by their printed form.
+fn map_key(key: Edn, interner: &mut Interner) -> Atom {
+    match key {
+        Edn::Key(atom) | Edn::Symbol(atom) => atom,
+        key => interner.intern(&key.to_string()),
+    }
+}
+
 fn read_if_not_container_end(
     chars: &mut iter::Enumerate<core::str::Chars<'_>>,
+    interner: &mut Interner,
 ) -> Result<Option<Edn>, Error> {
     Ok(match chars.clone().next() {
         Some(c) if c.1 == ']' || c.1 == ')' || c.1 == '}' => None,
-        Some(_) => parse_internal(chars.next(), chars)?,
+        Some(_) => parse_internal(chars.next(), chars, interner)?,
         None => None,
     })
 }
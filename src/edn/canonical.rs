@@ -0,0 +1,218 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::Write;
+use core::num::FpCategory;
+
+use crate::edn::Edn;
+
+// Control characters, and whitespace in chars, are written as `\uXXXX` so they survive editors.
+fn escape_control(out: &mut String, c: char) {
+    let _ = write!(out, "\\u{:04x}", u32::from(c));
+}
+
+fn write_str(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => escape_control(out, c),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+fn write_char(out: &mut String, c: char) {
+    match c {
+        '\n' => out.push_str("\\newline"),
+        '\r' => out.push_str("\\return"),
+        ' ' => out.push_str("\\space"),
+        '\t' => out.push_str("\\tab"),
+        c if c.is_control() || c.is_whitespace() => escape_control(out, c),
+        c => {
+            out.push('\\');
+            out.push(c);
+        }
+    }
+}
+
+// Scientific notation with the shortest digits that read back as the same `f64`, like `1e0` or
+// `-2.5e-7`. Both zeroes and every `NaN` collapse, as they do for `Eq` and `Hash`.
+fn write_double(out: &mut String, f: f64) {
+    match f.classify() {
+        FpCategory::Nan => out.push_str("##NaN"),
+        FpCategory::Infinite if f > 0.0 => out.push_str("##Inf"),
+        FpCategory::Infinite => out.push_str("##-Inf"),
+        FpCategory::Zero => out.push_str("0e0"),
+        _ => {
+            let _ = write!(out, "{f:e}");
+        }
+    }
+}
+
+const fn gcd(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+// Rationals are reduced to lowest terms with the sign on the numerator, and whole ones are written
+// as integers. Parts that overflow an `i128` can't be reduced without big integers, so those
+// rationals are written as they were read.
+fn write_rational(out: &mut String, r: &str) {
+    let parsed = r
+        .split_once('/')
+        .and_then(|(n, d)| Some((n.parse::<i128>().ok()?, d.parse::<i128>().ok()?)));
+    let Some((numerator, denominator)) = parsed.filter(|&(_, d)| d != 0) else {
+        out.push_str(r);
+        return;
+    };
+    let negative = (numerator < 0) != (denominator < 0);
+    let (numerator, denominator) = (numerator.unsigned_abs(), denominator.unsigned_abs());
+    let divisor = gcd(numerator, denominator).max(1);
+    let sign = if negative && numerator != 0 { "-" } else { "" };
+    let _ = match denominator / divisor {
+        1 => write!(out, "{sign}{}", numerator / divisor),
+        d => write!(out, "{sign}{}/{d}", numerator / divisor),
+    };
+}
+
+fn write_sorted(out: &mut String, open: &str, mut items: Vec<String>, close: char) {
+    items.sort_unstable();
+    out.push_str(open);
+    out.push_str(&items.join(" "));
+    out.push(close);
+}
+
+fn write_seq<'a>(out: &mut String, open: char, items: impl Iterator<Item = &'a Edn>, close: char) {
+    out.push(open);
+    for (i, item) in items.enumerate() {
+        if i > 0 {
+            out.push(' ');
+        }
+        write(out, item);
+    }
+    out.push(close);
+}
+
+fn write(out: &mut String, edn: &Edn) {
+    match edn {
+        Edn::Vector(v) => write_seq(out, '[', v.iter(), ']'),
+        Edn::List(l) => write_seq(out, '(', l.iter(), ')'),
+        Edn::Set(s) => write_sorted(out, "#{", s.iter().map(to_string).collect(), '}'),
+        Edn::Map(m) => write_sorted(
+            out,
+            "{",
+            m.iter()
+                .map(|(k, v)| format!("{} {}", to_string(&Edn::read_key(k)), to_string(v)))
+                .collect(),
+            '}',
+        ),
+        Edn::Tagged(t) => {
+            out.push('#');
+            out.push_str(t.tag());
+            out.push(' ');
+            write(out, t.value());
+        }
+        Edn::Str(s) => write_str(out, s),
+        Edn::Char(c) => write_char(out, *c),
+        Edn::Double(d) => write_double(out, d.to_float()),
+        Edn::Rational(r) => write_rational(out, r),
+        Edn::Int(i) => {
+            let _ = write!(out, "{i}");
+        }
+        edn => out.push_str(&edn.to_string()),
+    }
+}
+
+pub fn to_string(edn: &Edn) -> String {
+    let mut out = String::new();
+    write(&mut out, edn);
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use core::str::FromStr;
+
+    use super::*;
+
+    fn canonical(s: &str) -> String {
+        to_string(&Edn::from_str(s).unwrap())
+    }
+
+    #[test]
+    fn maps_and_sets_are_sorted_by_their_canonical_form() {
+        assert_eq!(
+            canonical("{:b #{3 1 \"2\"}, \"a\" [1, 2] :a nil}"),
+            "{\"a\" [1 2] :a nil :b #{\"2\" 1 3}}"
+        );
+        assert_eq!(canonical("{1 :x 1.5 :y}"), canonical("{1.5 :y, 1 :x}"));
+    }
+
+    #[test]
+    fn numbers_have_one_form() {
+        assert_eq!(
+            canonical("[1 1.0 -0.0 0.5 1.5e10 2/4 -6/3]"),
+            "[1 1e0 0e0 5e-1 1.5e10 1/2 -2]"
+        );
+        assert_eq!(
+            to_string(&Edn::Double(f64::NAN.into())),
+            to_string(&Edn::Double((-f64::NAN).into()))
+        );
+        assert_eq!(to_string(&Edn::Double(f64::NEG_INFINITY.into())), "##-Inf");
+        assert_eq!(to_string(&Edn::Int(5)), to_string(&Edn::UInt(5)));
+    }
+
+    #[test]
+    fn strings_and_chars_are_escaped() {
+        assert_eq!(
+            to_string(&Edn::Str("tab\t \"q\" \\ é \u{7}".into())),
+            "\"tab\\t \\\"q\\\" \\\\ é \\u0007\""
+        );
+        assert_eq!(
+            to_string(&Edn::Vector(crate::edn::Vector::new(alloc::vec![
+                Edn::Char(' '),
+                Edn::Char('\u{a0}'),
+                Edn::Char('λ')
+            ]))),
+            "[\\space \\u00a0 \\λ]"
+        );
+    }
+
+    #[test]
+    fn canonical_strings_read_back_as_the_same_value() {
+        let edn = Edn::from_str("{:a #{1.25 \"x\\ny\"} :b (#tag [nil true \\c]) :c -3}").unwrap();
+        assert_eq!(Edn::from_str(&to_string(&edn)).unwrap(), edn);
+    }
+
+    #[test]
+    fn escaped_characters_read_back() {
+        let edn = Edn::Vector(crate::edn::Vector::new(alloc::vec![
+            Edn::Str("bell \u{7} nul \u{0} del \u{7f}".into()),
+            Edn::Char('\u{7}'),
+            Edn::Char('\u{a0}'),
+            Edn::Char('\u{2003}'),
+            Edn::Char('\u{3000}'),
+            Edn::Char('u'),
+        ]));
+        let canonical = to_string(&edn);
+        assert_eq!(
+            canonical,
+            "[\"bell \\u0007 nul \\u0000 del \\u007f\" \\u0007 \\u00a0 \\u2003 \\u3000 \\u]"
+        );
+        assert_eq!(Edn::from_str(&canonical), Ok(edn));
+    }
+
+    #[test]
+    fn unreduced_rationals_are_kept_as_read() {
+        let huge = "340282366920938463463374607431768211456/2";
+        assert_eq!(canonical(huge), huge);
+    }
+}
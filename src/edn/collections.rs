@@ -0,0 +1,356 @@
+use alloc::collections::{btree_map, btree_set, BTreeSet};
+use alloc::vec::Vec;
+use core::ops::{Deref, DerefMut};
+use core::slice;
+
+use crate::edn::shared::Shared;
+use crate::edn::{Atom, Edn, List, Map, Set, Vector};
+
+// `Vector` and `List` are both a `Vec<Edn>` underneath, so they share one collection API. Read-only
+// slice methods, like `get`, `first` or `contains`, come from `Deref<Target = [Edn]>`.
+macro_rules! impl_seq {
+    ( $( $name:ident ),+ ) => {
+        $(
+            impl $name {
+                #[must_use]
+                pub fn len(&self) -> usize {
+                    self.0.len()
+                }
+
+                #[must_use]
+                pub fn is_empty(&self) -> bool {
+                    self.0.is_empty()
+                }
+
+                pub fn iter(&self) -> slice::Iter<'_, Edn> {
+                    self.0.iter()
+                }
+
+                pub fn iter_mut(&mut self) -> slice::IterMut<'_, Edn> {
+                    self.0.iter_mut()
+                }
+
+                /// Appends `value` after the last element.
+                pub fn push(&mut self, value: Edn) {
+                    self.0.push(value);
+                }
+
+                /// Removes the last element and returns it, or `None` if there is none.
+                pub fn pop(&mut self) -> Option<Edn> {
+                    self.0.pop()
+                }
+
+                /// Inserts `value` at `index`, shifting every element after it.
+                ///
+                /// # Errors
+                ///
+                /// Gives `value` back if `index` is greater than `len()`.
+                pub fn insert(&mut self, index: usize, value: Edn) -> Result<(), Edn> {
+                    if index > self.0.len() {
+                        return Err(value);
+                    }
+                    self.0.insert(index, value);
+                    Ok(())
+                }
+
+                /// Removes the element at `index` and returns it, or `None` if `index` is out of
+                /// bounds.
+                pub fn remove(&mut self, index: usize) -> Option<Edn> {
+                    (index < self.0.len()).then(|| self.0.remove(index))
+                }
+            }
+
+            impl Deref for $name {
+                type Target = [Edn];
+
+                fn deref(&self) -> &[Edn] {
+                    self.0.as_slice()
+                }
+            }
+
+            impl DerefMut for $name {
+                fn deref_mut(&mut self) -> &mut [Edn] {
+                    self.0.as_mut_slice()
+                }
+            }
+
+            impl IntoIterator for $name {
+                type Item = Edn;
+                type IntoIter = alloc::vec::IntoIter<Edn>;
+
+                fn into_iter(self) -> Self::IntoIter {
+                    self.to_vec().into_iter()
+                }
+            }
+
+            impl<'a> IntoIterator for &'a $name {
+                type Item = &'a Edn;
+                type IntoIter = slice::Iter<'a, Edn>;
+
+                fn into_iter(self) -> Self::IntoIter {
+                    self.iter()
+                }
+            }
+
+            impl<'a> IntoIterator for &'a mut $name {
+                type Item = &'a mut Edn;
+                type IntoIter = slice::IterMut<'a, Edn>;
+
+                fn into_iter(self) -> Self::IntoIter {
+                    self.iter_mut()
+                }
+            }
+
+            impl FromIterator<Edn> for $name {
+                fn from_iter<T: IntoIterator<Item = Edn>>(iter: T) -> Self {
+                    Self::new(iter.into_iter().collect::<Vec<_>>())
+                }
+            }
+
+            impl Extend<Edn> for $name {
+                fn extend<T: IntoIterator<Item = Edn>>(&mut self, iter: T) {
+                    self.0.extend(iter);
+                }
+            }
+        )+
+    };
+}
+
+impl_seq!(Vector, List);
+
+impl Set {
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> btree_set::Iter<'_, Edn> {
+        self.0.iter()
+    }
+
+    #[must_use]
+    pub fn contains(&self, value: &Edn) -> bool {
+        self.0.contains(value)
+    }
+
+    /// The element of the set equal to `value`, if any.
+    #[must_use]
+    pub fn get(&self, value: &Edn) -> Option<&Edn> {
+        self.0.get(value)
+    }
+
+    /// Adds `value` to the set. Returns whether it was new.
+    pub fn insert(&mut self, value: Edn) -> bool {
+        self.0.insert(value)
+    }
+
+    /// Removes `value` from the set. Returns whether it was there.
+    pub fn remove(&mut self, value: &Edn) -> bool {
+        self.0.remove(value)
+    }
+}
+
+impl IntoIterator for Set {
+    type Item = Edn;
+    type IntoIter = btree_set::IntoIter<Edn>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.to_set().into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Set {
+    type Item = &'a Edn;
+    type IntoIter = btree_set::Iter<'a, Edn>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl FromIterator<Edn> for Set {
+    fn from_iter<T: IntoIterator<Item = Edn>>(iter: T) -> Self {
+        Self::new(iter.into_iter().collect::<BTreeSet<_>>())
+    }
+}
+
+impl Extend<Edn> for Set {
+    fn extend<T: IntoIterator<Item = Edn>>(&mut self, iter: T) {
+        self.0.extend(iter);
+    }
+}
+
+/// Map keys are the printed form of the key, like `":a"` or `"\"a\""`, the same strings
+/// `Edn::get` takes and `to_map` returns. They are kept as `Atom`s, so keys read by the same
+/// parse share their text.
+impl Map {
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> btree_map::Iter<'_, Atom, Edn> {
+        self.0.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> btree_map::IterMut<'_, Atom, Edn> {
+        self.0.iter_mut()
+    }
+
+    pub fn keys(&self) -> btree_map::Keys<'_, Atom, Edn> {
+        self.0.keys()
+    }
+
+    pub fn values(&self) -> btree_map::Values<'_, Atom, Edn> {
+        self.0.values()
+    }
+
+    pub fn values_mut(&mut self) -> btree_map::ValuesMut<'_, Atom, Edn> {
+        self.0.values_mut()
+    }
+
+    #[must_use]
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.0.contains_key(key)
+    }
+
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<&Edn> {
+        self.0.get(key)
+    }
+
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut Edn> {
+        self.0.get_mut(key)
+    }
+
+    /// Sets `key` to `value`, returning the value it replaced.
+    pub fn insert(&mut self, key: impl Into<Atom>, value: Edn) -> Option<Edn> {
+        self.0.insert(key.into(), value)
+    }
+
+    /// Removes `key` from the map, returning its value.
+    pub fn remove(&mut self, key: &str) -> Option<Edn> {
+        self.0.remove(key)
+    }
+
+    /// The entry for `key`, to read or update it in place.
+    /// ```rust
+    /// use edn_rs::edn::{Edn, Map};
+    ///
+    /// let mut counts = Map::empty();
+    /// for word in [":a", ":b", ":a"] {
+    ///     let count = counts.entry(word).or_insert(Edn::UInt(0));
+    ///     *count = Edn::UInt(count.to_uint().unwrap() + 1);
+    /// }
+    /// assert_eq!(counts.get(":a"), Some(&Edn::UInt(2)));
+    /// ```
+    pub fn entry(&mut self, key: impl Into<Atom>) -> btree_map::Entry<'_, Atom, Edn> {
+        self.0.entry(key.into())
+    }
+}
+
+impl IntoIterator for Map {
+    type Item = (Atom, Edn);
+    type IntoIter = btree_map::IntoIter<Atom, Edn>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_inner().into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Map {
+    type Item = (&'a Atom, &'a Edn);
+    type IntoIter = btree_map::Iter<'a, Atom, Edn>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a mut Map {
+    type Item = (&'a Atom, &'a mut Edn);
+    type IntoIter = btree_map::IterMut<'a, Atom, Edn>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<K: Into<Atom>> FromIterator<(K, Edn)> for Map {
+    fn from_iter<T: IntoIterator<Item = (K, Edn)>>(iter: T) -> Self {
+        Self(Shared::new(
+            iter.into_iter().map(|(k, v)| (k.into(), v)).collect(),
+        ))
+    }
+}
+
+impl<K: Into<Atom>> Extend<(K, Edn)> for Map {
+    fn extend<T: IntoIterator<Item = (K, Edn)>>(&mut self, iter: T) {
+        self.0.extend(iter.into_iter().map(|(k, v)| (k.into(), v)));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::string::ToString;
+    use core::str::FromStr;
+
+    use super::*;
+
+    fn edn(s: &str) -> Edn {
+        Edn::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn sequences_work_like_vecs() {
+        let mut vector = (1..=3).map(Edn::UInt).collect::<Vector>();
+        assert_eq!(vector.len(), 3);
+        assert_eq!(vector.first(), Some(&Edn::UInt(1)));
+        assert!(vector.contains(&Edn::UInt(2)));
+
+        vector.push(Edn::UInt(4));
+        vector.insert(0, Edn::UInt(0)).unwrap();
+        assert_eq!(vector.insert(9, Edn::Nil), Err(Edn::Nil));
+        assert_eq!(vector.remove(1), Some(Edn::UInt(1)));
+        assert_eq!(vector.remove(10), None);
+        for value in &mut vector {
+            *value = Edn::UInt(value.to_uint().unwrap() * 10);
+        }
+        vector[0] = Edn::Nil;
+        assert_eq!(Edn::Vector(vector.clone()), edn("[nil 20 30 40]"));
+
+        let mut list = List::empty();
+        list.extend(vector);
+        assert_eq!(list.pop(), Some(Edn::UInt(40)));
+        assert_eq!(list.iter().count(), 3);
+        assert_eq!(Edn::List(list), edn("(nil 20 30)"));
+    }
+
+    #[test]
+    fn sets_and_maps() {
+        let mut set = [edn(":a"), edn(":b")].into_iter().collect::<Set>();
+        assert!(!set.insert(edn(":a")));
+        assert!(set.remove(&edn(":b")));
+        assert_eq!(set.get(&edn(":a")), Some(&edn(":a")));
+        assert_eq!(set.into_iter().collect::<Vec<_>>(), [edn(":a")]);
+
+        let mut map = core::iter::once((":a".to_string(), Edn::UInt(1))).collect::<Map>();
+        assert_eq!(map.insert(":a", Edn::UInt(2)), Some(Edn::UInt(1)));
+        map.entry(":b").or_insert(Edn::Nil);
+        *map.get_mut(":b").unwrap() = Edn::Bool(true);
+        assert!(map.contains_key(":b"));
+        assert_eq!(map.remove(":c"), None);
+        assert_eq!(map.keys().collect::<Vec<_>>(), [":a", ":b"]);
+        assert_eq!(Edn::Map(map), edn("{:a 2 :b true}"));
+    }
+}
@@ -0,0 +1,390 @@
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::str::FromStr;
+
+use crate::edn::{Edn, Error, Map, Set, Vector};
+
+const fn type_error(expected: &'static str, found: &Edn) -> Error {
+    Error::Type {
+        expected,
+        found: found.type_name(),
+    }
+}
+
+// Integers are stored the way the parser reads them, `UInt` when non-negative and `Int` otherwise.
+macro_rules! impl_from_int {
+    ( $( $name:ty ),+ ) => {
+        $(
+            impl From<$name> for Edn {
+                #[allow(clippy::cast_lossless)]
+                fn from(n: $name) -> Self {
+                    u64::try_from(n).map_or_else(|_| Self::Int(n as i64), Self::UInt)
+                }
+            }
+        )+
+    };
+}
+
+impl_from_int!(i8, i16, i32, i64, isize);
+
+macro_rules! impl_from_uint {
+    ( $( $name:ty ),+ ) => {
+        $(
+            impl From<$name> for Edn {
+                #[allow(clippy::cast_lossless)]
+                fn from(n: $name) -> Self {
+                    Self::UInt(n as u64)
+                }
+            }
+        )+
+    };
+}
+
+impl_from_uint!(u8, u16, u32, u64, usize);
+
+// EDN integers are 64 bits wide, so wider integers only convert when they fit.
+macro_rules! impl_try_from_wide_int {
+    ( $( $name:ty ),+ ) => {
+        $(
+            impl TryFrom<$name> for Edn {
+                type Error = Error;
+
+                fn try_from(n: $name) -> Result<Self, Error> {
+                    match u64::try_from(n) {
+                        Ok(u) => Ok(Self::UInt(u)),
+                        Err(_) => Ok(Self::Int(i64::try_from(n)?)),
+                    }
+                }
+            }
+        )+
+    };
+}
+
+impl_try_from_wide_int!(i128, u128);
+
+impl From<f32> for Edn {
+    fn from(f: f32) -> Self {
+        Self::Double(f64::from(f).into())
+    }
+}
+
+impl From<f64> for Edn {
+    fn from(f: f64) -> Self {
+        Self::Double(f.into())
+    }
+}
+
+impl From<bool> for Edn {
+    fn from(b: bool) -> Self {
+        Self::Bool(b)
+    }
+}
+
+impl From<char> for Edn {
+    fn from(c: char) -> Self {
+        Self::Char(c)
+    }
+}
+
+impl From<&str> for Edn {
+    fn from(s: &str) -> Self {
+        Self::Str(s.into())
+    }
+}
+
+impl From<String> for Edn {
+    fn from(s: String) -> Self {
+        Self::Str(s.into())
+    }
+}
+
+impl<T: Into<Self>> From<Vec<T>> for Edn {
+    fn from(v: Vec<T>) -> Self {
+        Self::Vector(Vector::new(v.into_iter().map(Into::into).collect()))
+    }
+}
+
+impl<T: Into<Self>> From<BTreeSet<T>> for Edn {
+    fn from(s: BTreeSet<T>) -> Self {
+        Self::Set(Set::new(s.into_iter().map(Into::into).collect()))
+    }
+}
+
+/// Keys are converted to `Edn` too, so `String` keys become EDN strings like `"a"`, not keywords.
+impl<K: Into<Self>, V: Into<Self>> From<BTreeMap<K, V>> for Edn {
+    fn from(m: BTreeMap<K, V>) -> Self {
+        Self::Map(Map::new(
+            m.into_iter()
+                .map(|(k, v)| (k.into().to_string(), v.into()))
+                .collect(),
+        ))
+    }
+}
+
+impl<T: Into<Self>> From<Option<T>> for Edn {
+    fn from(o: Option<T>) -> Self {
+        o.map_or(Self::Nil, Into::into)
+    }
+}
+
+macro_rules! impl_try_from_int {
+    ( $( $name:ty ),+ ) => {
+        $(
+            impl TryFrom<&Edn> for $name {
+                type Error = Error;
+
+                fn try_from(edn: &Edn) -> Result<Self, Error> {
+                    match edn {
+                        Edn::Int(i) => Ok(Self::try_from(*i)?),
+                        Edn::UInt(u) => Ok(Self::try_from(*u)?),
+                        _ => Err(type_error("integer", edn)),
+                    }
+                }
+            }
+        )+
+    };
+}
+
+impl_try_from_int!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+impl TryFrom<&Edn> for f64 {
+    type Error = Error;
+
+    fn try_from(edn: &Edn) -> Result<Self, Error> {
+        match edn {
+            Edn::Double(d) => Ok(d.to_float()),
+            _ => Err(type_error("double", edn)),
+        }
+    }
+}
+
+/// Only doubles that an `f32` holds exactly convert, like `0.5` but not `0.1`.
+impl TryFrom<&Edn> for f32 {
+    type Error = Error;
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn try_from(edn: &Edn) -> Result<Self, Error> {
+        let double = f64::try_from(edn)?;
+        let float = double as Self;
+        if f64::from(float).to_bits() == double.to_bits() || double.is_nan() {
+            Ok(float)
+        } else {
+            Err(type_error("f32", edn))
+        }
+    }
+}
+
+impl TryFrom<&Edn> for bool {
+    type Error = Error;
+
+    fn try_from(edn: &Edn) -> Result<Self, Error> {
+        match edn {
+            Edn::Bool(b) => Ok(*b),
+            _ => Err(type_error("boolean", edn)),
+        }
+    }
+}
+
+impl TryFrom<&Edn> for char {
+    type Error = Error;
+
+    fn try_from(edn: &Edn) -> Result<Self, Error> {
+        match edn {
+            Edn::Char(c) => Ok(*c),
+            _ => Err(type_error("char", edn)),
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a Edn> for &'a str {
+    type Error = Error;
+
+    fn try_from(edn: &'a Edn) -> Result<Self, Error> {
+        match edn {
+            Edn::Str(s) => Ok(s),
+            _ => Err(type_error("string", edn)),
+        }
+    }
+}
+
+impl TryFrom<&Edn> for String {
+    type Error = Error;
+
+    fn try_from(edn: &Edn) -> Result<Self, Error> {
+        <&str>::try_from(edn).map(Into::into)
+    }
+}
+
+impl<T> TryFrom<&Edn> for Vec<T>
+where
+    T: for<'a> TryFrom<&'a Edn, Error = Error>,
+{
+    type Error = Error;
+
+    fn try_from(edn: &Edn) -> Result<Self, Error> {
+        match edn {
+            Edn::Vector(v) => v.0.iter().map(T::try_from).collect(),
+            Edn::List(l) => l.0.iter().map(T::try_from).collect(),
+            _ => Err(type_error("vector", edn)),
+        }
+    }
+}
+
+impl<T> TryFrom<&Edn> for BTreeSet<T>
+where
+    T: Ord + for<'a> TryFrom<&'a Edn, Error = Error>,
+{
+    type Error = Error;
+
+    fn try_from(edn: &Edn) -> Result<Self, Error> {
+        match edn {
+            Edn::Set(s) => s.0.iter().map(T::try_from).collect(),
+            _ => Err(type_error("set", edn)),
+        }
+    }
+}
+
+/// Keys are read back from their printed form, so a `BTreeMap<String, V>` only accepts maps keyed
+/// by EDN strings.
+impl<K, V> TryFrom<&Edn> for BTreeMap<K, V>
+where
+    K: Ord + for<'a> TryFrom<&'a Edn, Error = Error>,
+    V: for<'a> TryFrom<&'a Edn, Error = Error>,
+{
+    type Error = Error;
+
+    fn try_from(edn: &Edn) -> Result<Self, Error> {
+        match edn {
+            Edn::Map(m) => {
+                m.0.iter()
+                    .map(|(k, v)| Ok((K::try_from(&Edn::from_str(k)?)?, V::try_from(v)?)))
+                    .collect()
+            }
+            _ => Err(type_error("map", edn)),
+        }
+    }
+}
+
+impl<T> TryFrom<&Edn> for Option<T>
+where
+    T: for<'a> TryFrom<&'a Edn, Error = Error>,
+{
+    type Error = Error;
+
+    fn try_from(edn: &Edn) -> Result<Self, Error> {
+        match edn {
+            Edn::Nil => Ok(None),
+            _ => T::try_from(edn).map(Some),
+        }
+    }
+}
+
+impl TryFrom<&Self> for Edn {
+    type Error = Error;
+
+    fn try_from(edn: &Self) -> Result<Self, Error> {
+        Ok(edn.clone())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::vec;
+
+    use super::*;
+
+    #[test]
+    fn integers_match_the_parser() {
+        assert_eq!(Edn::from(5i8), Edn::from_str("5").unwrap());
+        assert_eq!(Edn::from(-5i64), Edn::from_str("-5").unwrap());
+        assert_eq!(Edn::from(u64::MAX), Edn::UInt(u64::MAX));
+        assert_eq!(Edn::from(i64::MIN), Edn::Int(i64::MIN));
+    }
+
+    #[test]
+    fn collections_convert_their_elements() {
+        assert_eq!(
+            Edn::from(vec![Some(1), None]),
+            Edn::from_str("[1 nil]").unwrap()
+        );
+        assert_eq!(
+            Edn::from(BTreeMap::from([("a", 1.5)])),
+            Edn::from_str("{\"a\" 1.5}").unwrap()
+        );
+        assert_eq!(
+            Edn::from(BTreeSet::from(['a', 'b'])),
+            Edn::from_str("#{\\a \\b}").unwrap()
+        );
+    }
+
+    #[test]
+    fn try_from_round_trips() {
+        let edn = Edn::from_str("{\"a\" [1 2] \"b\" nil}").unwrap();
+        let map = BTreeMap::<String, Option<Vec<u8>>>::try_from(&edn).unwrap();
+
+        assert_eq!(
+            map,
+            BTreeMap::from([("a".to_string(), Some(vec![1, 2])), ("b".to_string(), None)])
+        );
+        assert_eq!(Edn::from(map), edn);
+    }
+
+    #[test]
+    fn try_from_reports_found_and_expected() {
+        assert_eq!(
+            i32::try_from(&Edn::Key(":a".into())),
+            Err(Error::Type {
+                expected: "integer",
+                found: "key"
+            })
+        );
+        assert_eq!(
+            Vec::<bool>::try_from(&Edn::from_str("[true 1]").unwrap()),
+            Err(Error::Type {
+                expected: "boolean",
+                found: "integer"
+            })
+        );
+        assert!(matches!(
+            u8::try_from(&Edn::from(300)),
+            Err(Error::TryFromInt(_))
+        ));
+    }
+
+    #[test]
+    fn wide_integers_convert_when_they_fit() {
+        assert_eq!(Edn::try_from(-5i128), Ok(Edn::Int(-5)));
+        assert_eq!(Edn::try_from(u128::from(u64::MAX)), Ok(Edn::UInt(u64::MAX)));
+        assert!(matches!(
+            Edn::try_from(i128::MIN),
+            Err(Error::TryFromInt(_))
+        ));
+        assert!(matches!(
+            Edn::try_from(u128::MAX),
+            Err(Error::TryFromInt(_))
+        ));
+        assert_eq!(
+            i128::try_from(&Edn::Int(i64::MIN)),
+            Ok(i128::from(i64::MIN))
+        );
+        assert_eq!(u128::try_from(&Edn::UInt(7)), Ok(7));
+        assert!(u128::try_from(&Edn::Int(-1)).is_err());
+    }
+
+    #[test]
+    fn f32_only_takes_exact_doubles() {
+        assert_eq!(f32::try_from(&Edn::from(0.5)), Ok(0.5));
+        assert_eq!(f32::try_from(&Edn::from(f64::INFINITY)), Ok(f32::INFINITY));
+        assert!(f32::try_from(&Edn::from(f64::NAN)).unwrap().is_nan());
+        assert_eq!(
+            f32::try_from(&Edn::from(0.1)),
+            Err(Error::Type {
+                expected: "f32",
+                found: "double"
+            })
+        );
+        assert!(f32::try_from(&Edn::from(1e300)).is_err());
+    }
+}
@@ -0,0 +1,514 @@
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::format;
+use alloc::string::ToString;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+
+use super::path::{dissoc_in, get_in_mut};
+use crate::edn::{Atom, Edn, Error, List, Path, Vector};
+
+/// A single change in an `EditScript`. Paths are resolved against the value as it is when the
+/// edit is applied, after the edits before it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Edit {
+    /// Inserts `value` at `path`: a new map key, a vector or list index, or a set element.
+    Add {
+        path: Path,
+        value: Edn,
+    },
+    Remove {
+        path: Path,
+    },
+    Replace {
+        path: Path,
+        value: Edn,
+    },
+    /// Removes the value at `from` and adds it at `path`.
+    Move {
+        from: Path,
+        path: Path,
+    },
+}
+
+/// `EditScript` is a list of edits that turns one `Edn` into another, made by `diff` and applied
+/// by `patch`.
+///
+/// It converts to and from EDN like `[[:replace [:a] 2] [:add [:b 0] "x"] [:remove [:c]]
+/// [:move [:d] [:e]]]`, so it can be stored or sent.
+/// ```rust
+/// use edn_rs::edn::{diff, patch, EditScript};
+/// use edn_rs::Edn;
+/// use std::str::FromStr;
+///
+/// let old = Edn::from_str("{:name \"Ana\" :langs [:clj :rust] :zip 123}").unwrap();
+/// let new = Edn::from_str("{:name \"Ana\" :langs [:rust :clj :go] :postcode 123}").unwrap();
+///
+/// let script = diff(&old, &new);
+/// assert_eq!(
+///     script.to_string(),
+///     "[[:move [:langs 0] [:langs 1]] [:add [:langs 2] :go] [:move [:zip] [:postcode]]]"
+/// );
+///
+/// let mut edn = old;
+/// patch(&mut edn, &EditScript::from_str(&script.to_string()).unwrap()).unwrap();
+/// assert_eq!(edn, new);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EditScript(Vec<Edit>);
+
+impl EditScript {
+    #[must_use]
+    pub const fn new(edits: Vec<Edit>) -> Self {
+        Self(edits)
+    }
+
+    #[must_use]
+    pub fn edits(&self) -> &[Edit] {
+        &self.0
+    }
+
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl From<Vec<Edit>> for EditScript {
+    fn from(edits: Vec<Edit>) -> Self {
+        Self(edits)
+    }
+}
+
+fn path_edn(path: &Path) -> Edn {
+    Edn::Vector(Vector::new(path.steps().to_vec()))
+}
+
+impl From<&Edit> for Edn {
+    fn from(edit: &Edit) -> Self {
+        let op = |name: &str| Self::Key(format!(":{name}").into());
+        Self::Vector(Vector::new(match edit {
+            Edit::Add { path, value } => vec![op("add"), path_edn(path), value.clone()],
+            Edit::Remove { path } => vec![op("remove"), path_edn(path)],
+            Edit::Replace { path, value } => vec![op("replace"), path_edn(path), value.clone()],
+            Edit::Move { from, path } => vec![op("move"), path_edn(from), path_edn(path)],
+        }))
+    }
+}
+
+impl From<&EditScript> for Edn {
+    fn from(script: &EditScript) -> Self {
+        Self::Vector(Vector::new(script.0.iter().map(Self::from).collect()))
+    }
+}
+
+impl TryFrom<&Edn> for Edit {
+    type Error = Error;
+
+    fn try_from(edn: &Edn) -> Result<Self, Error> {
+        let Edn::Vector(v) = edn else {
+            return Err(Error::Deserialize(format!("invalid edit {edn}")));
+        };
+        match &v.0[..] {
+            [Edn::Key(op), path, value] if op.as_str() == ":add" => Ok(Self::Add {
+                path: Path::try_from(path)?,
+                value: value.clone(),
+            }),
+            [Edn::Key(op), path] if op.as_str() == ":remove" => Ok(Self::Remove {
+                path: Path::try_from(path)?,
+            }),
+            [Edn::Key(op), path, value] if op.as_str() == ":replace" => Ok(Self::Replace {
+                path: Path::try_from(path)?,
+                value: value.clone(),
+            }),
+            [Edn::Key(op), from, path] if op.as_str() == ":move" => Ok(Self::Move {
+                from: Path::try_from(from)?,
+                path: Path::try_from(path)?,
+            }),
+            _ => Err(Error::Deserialize(format!("invalid edit {edn}"))),
+        }
+    }
+}
+
+impl TryFrom<&Edn> for EditScript {
+    type Error = Error;
+
+    fn try_from(edn: &Edn) -> Result<Self, Error> {
+        match edn {
+            Edn::Vector(v) => {
+                v.0.iter()
+                    .map(Edit::try_from)
+                    .collect::<Result<_, _>>()
+                    .map(Self)
+            }
+            _ => Err(Error::Type {
+                expected: "vector",
+                found: edn.type_name(),
+            }),
+        }
+    }
+}
+
+impl FromStr for EditScript {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        Self::try_from(&Edn::from_str(s)?)
+    }
+}
+
+impl fmt::Display for EditScript {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&Edn::from(self), f)
+    }
+}
+
+fn child(path: &Path, step: impl Into<Edn>) -> Path {
+    let mut path = path.clone();
+    path.push(step);
+    path
+}
+
+enum Op {
+    Keep,
+    Delete(usize),
+    Insert(usize),
+}
+
+// Aligns `old` and `new` on a longest common subsequence, with deletions before insertions in
+// each gap. Indices in the result are into `old` and `new`.
+fn align(old: &[Edn], new: &[Edn]) -> Vec<Op> {
+    let prefix = old.iter().zip(new).take_while(|(x, y)| x == y).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(x, y)| x == y)
+        .count();
+    let removed = &old[prefix..old.len() - suffix];
+    let added = &new[prefix..new.len() - suffix];
+
+    let mut lcs = vec![vec![0usize; added.len() + 1]; removed.len() + 1];
+    for (row, x) in removed.iter().enumerate().rev() {
+        for (col, y) in added.iter().enumerate().rev() {
+            lcs[row][col] = if x == y {
+                lcs[row + 1][col + 1] + 1
+            } else {
+                lcs[row + 1][col].max(lcs[row][col + 1])
+            };
+        }
+    }
+
+    let mut ops = Vec::with_capacity(old.len() + new.len());
+    ops.extend((0..prefix).map(|_| Op::Keep));
+    let (mut row, mut col) = (0, 0);
+    while row < removed.len() || col < added.len() {
+        match (removed.get(row), added.get(col)) {
+            (Some(x), Some(y)) if x == y => {
+                ops.push(Op::Keep);
+                row += 1;
+                col += 1;
+            }
+            (Some(_), None) => {
+                ops.push(Op::Delete(prefix + row));
+                row += 1;
+            }
+            (Some(_), Some(_)) if lcs[row + 1][col] >= lcs[row][col + 1] => {
+                ops.push(Op::Delete(prefix + row));
+                row += 1;
+            }
+            _ => {
+                ops.push(Op::Insert(prefix + col));
+                col += 1;
+            }
+        }
+    }
+    ops.extend((0..suffix).map(|_| Op::Keep));
+    ops
+}
+
+#[derive(PartialEq, Eq)]
+enum Token {
+    Old(usize),
+    New,
+}
+
+// Walks the alignment keeping `tokens` in step with the edits, so every index is the one the
+// value has when its edit is applied. Deleted values that are inserted again become moves, and a
+// deletion right before an insertion is diffed in place.
+fn diff_seq(path: &Path, old: &[Edn], new: &[Edn], edits: &mut Vec<Edit>) {
+    let ops = align(old, new);
+    // Inserted index to the deleted index it moves from.
+    let mut moves = BTreeMap::new();
+    for op in &ops {
+        if let Op::Insert(inserted) = op {
+            let deleted = ops.iter().find_map(|op| match op {
+                Op::Delete(deleted)
+                    if old[*deleted] == new[*inserted] && !moves.values().any(|m| m == deleted) =>
+                {
+                    Some(*deleted)
+                }
+                _ => None,
+            });
+            if let Some(deleted) = deleted {
+                moves.insert(*inserted, deleted);
+            }
+        }
+    }
+    let is_moved = |deleted: usize| moves.values().any(|m| *m == deleted);
+
+    let mut tokens = (0..old.len()).map(Token::Old).collect::<Vec<_>>();
+    let mut at = 0;
+    let mut ops = ops.iter().peekable();
+    while let Some(op) = ops.next() {
+        match op {
+            Op::Keep => at += 1,
+            Op::Delete(deleted) if is_moved(*deleted) => {
+                if tokens.contains(&Token::Old(*deleted)) {
+                    at += 1;
+                }
+            }
+            Op::Delete(deleted) => match ops.peek() {
+                Some(Op::Insert(inserted)) if !moves.contains_key(inserted) => {
+                    diff_into(&child(path, at), &old[*deleted], &new[*inserted], edits);
+                    tokens[at] = Token::New;
+                    at += 1;
+                    ops.next();
+                }
+                _ => {
+                    edits.push(Edit::Remove {
+                        path: child(path, at),
+                    });
+                    tokens.remove(at);
+                }
+            },
+            Op::Insert(inserted) => {
+                if let Some(from) = moves
+                    .get(inserted)
+                    .and_then(|deleted| tokens.iter().position(|t| *t == Token::Old(*deleted)))
+                {
+                    tokens.remove(from);
+                    if from < at {
+                        at -= 1;
+                    }
+                    edits.push(Edit::Move {
+                        from: child(path, from),
+                        path: child(path, at),
+                    });
+                } else {
+                    edits.push(Edit::Add {
+                        path: child(path, at),
+                        value: new[*inserted].clone(),
+                    });
+                }
+                tokens.insert(at, Token::New);
+                at += 1;
+            }
+        }
+    }
+}
+
+fn diff_map(path: &Path, a: &BTreeMap<Atom, Edn>, b: &BTreeMap<Atom, Edn>, edits: &mut Vec<Edit>) {
+    for (k, v) in a {
+        if let Some(w) = b.get(k) {
+            diff_into(&child(path, Edn::read_key(k)), v, w, edits);
+        }
+    }
+    let mut added = b
+        .iter()
+        .filter(|(k, _)| !a.contains_key(*k))
+        .collect::<Vec<_>>();
+    for (k, v) in a.iter().filter(|(k, _)| !b.contains_key(*k)) {
+        let from = child(path, Edn::read_key(k));
+        if let Some(pos) = added.iter().position(|(_, w)| *w == v) {
+            let (to, _) = added.remove(pos);
+            edits.push(Edit::Move {
+                from,
+                path: child(path, Edn::read_key(to)),
+            });
+        } else {
+            edits.push(Edit::Remove { path: from });
+        }
+    }
+    for (k, v) in added {
+        edits.push(Edit::Add {
+            path: child(path, Edn::read_key(k)),
+            value: v.clone(),
+        });
+    }
+}
+
+fn diff_set(path: &Path, a: &BTreeSet<Edn>, b: &BTreeSet<Edn>, edits: &mut Vec<Edit>) {
+    for e in a.difference(b) {
+        edits.push(Edit::Remove {
+            path: child(path, e.clone()),
+        });
+    }
+    for e in b.difference(a) {
+        edits.push(Edit::Add {
+            path: child(path, e.clone()),
+            value: e.clone(),
+        });
+    }
+}
+
+fn diff_into(path: &Path, a: &Edn, b: &Edn, edits: &mut Vec<Edit>) {
+    match (a, b) {
+        _ if a == b => {}
+        (Edn::Map(x), Edn::Map(y)) => diff_map(path, &x.0, &y.0, edits),
+        (Edn::Vector(x), Edn::Vector(y)) => diff_seq(path, &x.0, &y.0, edits),
+        (Edn::List(x), Edn::List(y)) => diff_seq(path, &x.0, &y.0, edits),
+        (Edn::Set(x), Edn::Set(y)) => diff_set(path, &x.0, &y.0, edits),
+        _ => edits.push(Edit::Replace {
+            path: path.clone(),
+            value: b.clone(),
+        }),
+    }
+}
+
+/// Computes the edits that turn `a` into `b`, so that `patch(&mut a, &diff(&a, &b))` leaves `a`
+/// equal to `b`.
+///
+/// Maps are compared key by key, and a key whose value moved to a new key is a `Move`. Vectors and
+/// lists are aligned on their longest common subsequence, with values that moved elsewhere in the
+/// same sequence as `Move`s. Sets add and remove elements. Anything else that differs, including
+/// tagged elements, is replaced whole.
+#[must_use]
+pub fn diff(a: &Edn, b: &Edn) -> EditScript {
+    let mut edits = Vec::new();
+    diff_into(&Path::default(), a, b, &mut edits);
+    EditScript(edits)
+}
+
+fn add(edn: &mut Edn, path: &Path, value: Edn) -> Result<(), Error> {
+    let Some((step, parent)) = path.steps().split_last() else {
+        *edn = value;
+        return Ok(());
+    };
+    match get_in_mut(edn, &Path::new(parent.to_vec()))? {
+        Edn::Map(m) => {
+            m.0.insert(step.to_string().into(), value);
+        }
+        Edn::Vector(Vector(v)) | Edn::List(List(v)) => match usize::try_from(step) {
+            Ok(i) if i <= v.len() => v.insert(i, value),
+            _ => return Err(Error::Path(format!("cannot add at {path}"))),
+        },
+        Edn::Set(s) => {
+            s.0.insert(value);
+        }
+        found => {
+            return Err(Error::Path(format!(
+                "cannot add to {} at {path}",
+                found.type_name()
+            )))
+        }
+    }
+    Ok(())
+}
+
+/// Applies the edits in `script` to `edn`, in order.
+///
+/// # Errors
+///
+/// `Error::Path` if an edit's path doesn't exist, or an index is out of range. Edits before the
+/// failing one stay applied.
+pub fn patch(edn: &mut Edn, script: &EditScript) -> Result<(), Error> {
+    for edit in &script.0 {
+        match edit {
+            Edit::Add { path, value } => add(edn, path, value.clone())?,
+            Edit::Remove { path } => {
+                dissoc_in(edn, path)?;
+            }
+            Edit::Replace { path, value } => *get_in_mut(edn, path)? = value.clone(),
+            Edit::Move { from, path } => {
+                let value = dissoc_in(edn, from)?;
+                add(edn, path, value)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn edn(s: &str) -> Edn {
+        Edn::from_str(s).unwrap()
+    }
+
+    fn round_trip(a: &str, b: &str) -> EditScript {
+        let (a, b) = (edn(a), edn(b));
+        let script = diff(&a, &b);
+        let mut patched = a;
+        patch(&mut patched, &script).unwrap();
+        assert_eq!(patched, b, "{script}");
+        script
+    }
+
+    #[test]
+    fn patch_of_diff_gives_the_target() {
+        let pairs = [
+            ("1", "2"),
+            ("[1 2 3]", "[1 2 3]"),
+            ("[1 2 3]", "[3 1 2]"),
+            ("[1 2 3 4 5]", "[5 4 3 2 1]"),
+            ("[1 2 3]", "[0 1 [2] 3 4]"),
+            ("(1 1 2 2)", "(2 1 2 1 1)"),
+            ("[[1 2] {:a 1}]", "[[1 3] {:a 2 :b 3}]"),
+            ("{:a {:b #{1 2}} :c [1]}", "{:a {:b #{2 3}} :c (1)}"),
+            ("{:a 1 :b 2}", "{:c 1 :d 2 :e 3}"),
+            ("{[1 2] :x \"k\" nil}", "{[1 2] :y}"),
+            ("[#inst \"a\" :k]", "[#inst \"b\" :k]"),
+            ("[]", "[1 [2] #{3}]"),
+        ];
+        for (a, b) in pairs {
+            round_trip(a, b);
+        }
+    }
+
+    #[test]
+    fn diffs_are_small() {
+        assert!(round_trip("[1 2 3]", "[1 2 3]").is_empty());
+        assert_eq!(
+            round_trip("[:a :b :c :d]", "[:a :x :c :d]").to_string(),
+            "[[:replace [1] :x]]"
+        );
+        assert_eq!(
+            round_trip("{:a {:b [1 2 3]}}", "{:a {:b [1 3]}}").to_string(),
+            "[[:remove [:a :b 1]]]"
+        );
+        assert_eq!(
+            round_trip("[:a :b :c]", "[:c :a :b]").to_string(),
+            "[[:move [2] [0]]]"
+        );
+        assert_eq!(
+            round_trip("#{1 2}", "#{2 3}").to_string(),
+            "[[:remove [1]] [:add [3] 3]]"
+        );
+    }
+
+    #[test]
+    fn scripts_read_back_from_edn() {
+        let script = diff(&edn("{:a 1 :b [1]}"), &edn("{:c 1 :b [1 2]}"));
+        assert_eq!(EditScript::from_str(&script.to_string()), Ok(script));
+
+        assert_eq!(
+            EditScript::from_str("[[:rename [:a] [:b]]]"),
+            Err(Error::Deserialize(
+                "invalid edit [:rename [:a] [:b]]".to_string()
+            ))
+        );
+        assert!(patch(
+            &mut edn("[1]"),
+            &EditScript::from_str("[[:add [3] 3]]").unwrap()
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,257 @@
+use alloc::vec::Vec;
+use core::str::FromStr;
+
+use crate::edn::Edn;
+
+// Port of `clojure.lang.Murmur3`, which is Murmur3_x86_32 with a seed of 0.
+#[allow(clippy::cast_possible_wrap)]
+const C1: i32 = 0xcc9e_2d51_u32 as i32;
+#[allow(clippy::cast_possible_wrap)]
+const C2: i32 = 0x1b87_3593_u32 as i32;
+#[allow(clippy::cast_possible_wrap)]
+const GOLDEN_RATIO: i32 = 0x9e37_79b9_u32 as i32;
+
+const fn mix_k1(k1: i32) -> i32 {
+    k1.wrapping_mul(C1).rotate_left(15).wrapping_mul(C2)
+}
+
+#[allow(clippy::cast_possible_wrap)]
+const fn mix_h1(h1: i32, k1: i32) -> i32 {
+    (h1 ^ k1)
+        .rotate_left(13)
+        .wrapping_mul(5)
+        .wrapping_add(0xe654_6b64_u32 as i32)
+}
+
+#[allow(clippy::cast_sign_loss, clippy::cast_possible_wrap)]
+const fn fmix(h1: i32, length: i32) -> i32 {
+    let mut h1 = (h1 ^ length) as u32;
+    h1 ^= h1 >> 16;
+    h1 = h1.wrapping_mul(0x85eb_ca6b);
+    h1 ^= h1 >> 13;
+    h1 = h1.wrapping_mul(0xc2b2_ae35);
+    h1 ^= h1 >> 16;
+    h1 as i32
+}
+
+const fn hash_int(input: i32) -> i32 {
+    if input == 0 {
+        return 0;
+    }
+    fmix(mix_h1(0, mix_k1(input)), 4)
+}
+
+#[allow(clippy::cast_possible_truncation)]
+const fn hash_long(input: i64) -> i32 {
+    if input == 0 {
+        return 0;
+    }
+    let low = input as i32;
+    let high = (input >> 32) as i32;
+    let h1 = mix_h1(0, mix_k1(low));
+    fmix(mix_h1(h1, mix_k1(high)), 8)
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+fn hash_unencoded_chars(input: &str) -> i32 {
+    let units = input.encode_utf16().collect::<Vec<u16>>();
+    let mut h1 = 0;
+    let mut pairs = units.chunks_exact(2);
+    for pair in pairs.by_ref() {
+        let k1 = i32::from(pair[0]) | (i32::from(pair[1]) << 16);
+        h1 = mix_h1(h1, mix_k1(k1));
+    }
+    if let [last] = pairs.remainder() {
+        h1 ^= mix_k1(i32::from(*last));
+    }
+    fmix(h1, 2 * units.len() as i32)
+}
+
+const fn mix_coll_hash(hash: i32, count: i32) -> i32 {
+    fmix(mix_h1(0, mix_k1(hash)), count)
+}
+
+fn hash_ordered(hashes: impl Iterator<Item = i32>) -> i32 {
+    let (hash, count) = hashes.fold((1i32, 0i32), |(hash, count), h| {
+        (hash.wrapping_mul(31).wrapping_add(h), count + 1)
+    });
+    mix_coll_hash(hash, count)
+}
+
+fn hash_unordered(hashes: impl Iterator<Item = i32>) -> i32 {
+    let (hash, count) = hashes.fold((0i32, 0i32), |(hash, count), h| {
+        (hash.wrapping_add(h), count + 1)
+    });
+    mix_coll_hash(hash, count)
+}
+
+// `clojure.lang.Util.hashCombine`
+const fn hash_combine(seed: i32, hash: i32) -> i32 {
+    seed ^ (hash
+        .wrapping_add(GOLDEN_RATIO)
+        .wrapping_add(seed << 6)
+        .wrapping_add(seed >> 2))
+}
+
+// `java.lang.String.hashCode`
+fn string_hash_code(s: &str) -> i32 {
+    s.encode_utf16()
+        .fold(0i32, |h, c| h.wrapping_mul(31).wrapping_add(i32::from(c)))
+}
+
+// `java.lang.Double.hashCode`, `doubleToLongBits` collapses every `NaN` into one value.
+#[allow(clippy::cast_possible_truncation)]
+const fn double_hash_code(d: f64) -> i32 {
+    let bits = if d.is_nan() {
+        0x7ff8_0000_0000_0000
+    } else {
+        d.to_bits()
+    };
+    (bits ^ (bits >> 32)) as i32
+}
+
+// `java.math.BigInteger.hashCode` for a value with the given sign and magnitude.
+#[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+fn big_integer_hash_code(negative: bool, magnitude: u128) -> i32 {
+    let hash = (0..4)
+        .rev()
+        .map(|i| (magnitude >> (32 * i)) as u32)
+        .skip_while(|word| *word == 0)
+        .fold(0i32, |h, word| h.wrapping_mul(31).wrapping_add(word as i32));
+    if negative {
+        hash.wrapping_neg()
+    } else {
+        hash
+    }
+}
+
+// Integers that fit in a `long` hash as `Long`, anything bigger as `clojure.lang.BigInt`.
+fn integer_hasheq(negative: bool, magnitude: u128) -> i32 {
+    let value = i128::try_from(magnitude)
+        .ok()
+        .map(|m| if negative { -m } else { m })
+        .and_then(|v| i64::try_from(v).ok());
+    value.map_or_else(|| big_integer_hash_code(negative, magnitude), hash_long)
+}
+
+const fn gcd(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        let t = a % b;
+        a = b;
+        b = t;
+    }
+    a
+}
+
+// Splits a ratio like `-3/4` into its sign, numerator and denominator.
+fn rational_to_parts(r: &str) -> Option<(bool, u128, u128)> {
+    let (negative, r) = r
+        .strip_prefix('-')
+        .map_or_else(|| (false, r.strip_prefix('+').unwrap_or(r)), |r| (true, r));
+    let (num, den) = r.split_once('/')?;
+    let den = den.parse::<u128>().ok().filter(|d| *d != 0)?;
+    Some((negative, num.parse::<u128>().ok()?, den))
+}
+
+// The reader normalizes ratios, so `2/4` is `1/2` and `4/2` is the `Long` `2`.
+fn rational_hasheq(r: &str) -> i32 {
+    let Some((negative, num, den)) = rational_to_parts(r) else {
+        return hash_int(string_hash_code(r));
+    };
+    let divisor = gcd(num, den);
+    let (num, den) = (num / divisor, den / divisor);
+    if den == 1 {
+        integer_hasheq(negative, num)
+    } else {
+        big_integer_hash_code(negative, num) ^ big_integer_hash_code(false, den)
+    }
+}
+
+fn symbol_hasheq(symbol: &str) -> i32 {
+    match symbol.split_once('/') {
+        Some((ns, name)) if !ns.is_empty() && !name.is_empty() => {
+            hash_combine(hash_unencoded_chars(name), string_hash_code(ns))
+        }
+        _ => hash_combine(hash_unencoded_chars(symbol), 0),
+    }
+}
+
+// `java.util.UUID.hashCode`
+#[allow(clippy::cast_possible_truncation)]
+fn uuid_hash_code(uuid: &str) -> Option<i32> {
+    let hex = uuid.replace('-', "");
+    if hex.len() != 32 || uuid.len() != 36 {
+        return None;
+    }
+    let bits = u128::from_str_radix(&hex, 16).ok()?;
+    let hilo = ((bits >> 64) as u64) ^ (bits as u64);
+    Some(((hilo >> 32) as i32) ^ (hilo as i32))
+}
+
+// Map keys are stored in their printed form, so they are read back to hash them as the JVM would.
+fn map_key_hasheq(key: &str) -> i32 {
+    Edn::from_str(key).map_or_else(|_| hash_int(string_hash_code(key)), |k| k.hasheq())
+}
+
+pub fn hasheq(edn: &Edn) -> i32 {
+    match edn {
+        Edn::Nil | Edn::Empty => 0,
+        Edn::Bool(true) => 1231,
+        Edn::Bool(false) => 1237,
+        Edn::Int(i) => hash_long(*i),
+        Edn::UInt(u) => integer_hasheq(false, u128::from(*u)),
+        Edn::Double(d) => double_hash_code(d.to_float()),
+        Edn::Rational(r) => rational_hasheq(r),
+        #[allow(clippy::cast_possible_wrap)]
+        Edn::Char(c) => *c as i32,
+        Edn::Str(s) => hash_int(string_hash_code(s)),
+        Edn::Symbol(s) => symbol_hasheq(s),
+        Edn::Key(k) => {
+            symbol_hasheq(k.strip_prefix(':').unwrap_or(k.as_str())).wrapping_add(GOLDEN_RATIO)
+        }
+        Edn::Vector(v) => hash_ordered(v.0.iter().map(Edn::hasheq)),
+        Edn::List(l) => hash_ordered(l.0.iter().map(Edn::hasheq)),
+        Edn::Set(s) => hash_unordered(s.0.iter().map(Edn::hasheq)),
+        // Each entry hashes like the `[k v]` vector of a `clojure.lang.MapEntry`
+        Edn::Map(m) => hash_unordered(
+            m.0.iter()
+                .map(|(k, v)| hash_ordered([map_key_hasheq(k), v.hasheq()].into_iter())),
+        ),
+        Edn::Tagged(t) => match (t.tag(), t.value()) {
+            ("uuid", Edn::Str(uuid)) => {
+                uuid_hash_code(uuid).unwrap_or_else(|| hash_int(string_hash_code(uuid)))
+            }
+            (tag, value) => hash_combine(symbol_hasheq(tag), value.hasheq()),
+        },
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn murmur3_primitives() {
+        assert_eq!(hash_int(0), 0);
+        assert_eq!(hash_long(0), 0);
+        assert_eq!(hash_long(1), 1_392_991_556);
+        assert_eq!(mix_coll_hash(1, 0), -2_017_569_654);
+        assert_eq!(mix_coll_hash(0, 0), -15_128_758);
+    }
+
+    #[test]
+    fn java_hash_codes() {
+        assert_eq!(string_hash_code(""), 0);
+        assert_eq!(string_hash_code("hello"), 99_162_322);
+        assert_eq!(double_hash_code(1.0), 1_072_693_248);
+        assert_eq!(big_integer_hash_code(false, 1), 1);
+        assert_eq!(big_integer_hash_code(true, 1), -1);
+    }
+
+    #[test]
+    fn ratios_are_normalized() {
+        assert_eq!(rational_hasheq("2/4"), rational_hasheq("1/2"));
+        assert_eq!(rational_hasheq("4/2"), hash_long(2));
+        assert_eq!(rational_hasheq("-4/2"), hash_long(-2));
+    }
+}
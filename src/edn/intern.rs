@@ -0,0 +1,299 @@
+use alloc::borrow::Borrow;
+use alloc::boxed::Box;
+use alloc::collections::BTreeSet;
+use alloc::string::String;
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::Deref;
+
+use crate::deserialize::parse;
+use crate::edn::shared::RefCounted;
+use crate::edn::{Edn, Error};
+
+/// `Atom` is a cheap, shared handle to the text of an `Edn::Key`, `Edn::Symbol` or tag.
+///
+/// Text of up to 14 bytes, like most keywords, is stored inline without allocating. Longer text
+/// is reference counted, so cloning an `Atom` never copies it, and atoms created by the same
+/// `Interner` compare by pointer before falling back to comparing their text.
+/// ```rust
+/// use edn_rs::edn::{Atom, Edn};
+///
+/// let key = Edn::Key(":db/id".into());
+/// assert_eq!(key, Edn::Key(Atom::from(":db/id")));
+/// assert_eq!(Atom::from(":db/id"), ":db/id");
+/// ```
+#[derive(Clone)]
+pub struct Atom(Repr);
+
+const INLINE_CAPACITY: usize = 14;
+
+#[derive(Clone)]
+enum Repr {
+    Inline {
+        len: u8,
+        bytes: [u8; INLINE_CAPACITY],
+    },
+    Shared(RefCounted<Box<str>>),
+}
+
+impl Atom {
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        match &self.0 {
+            // `bytes` always holds the first `len` bytes of a `str`, so this never falls back.
+            Repr::Inline { len, bytes } => {
+                core::str::from_utf8(&bytes[..usize::from(*len)]).unwrap_or_default()
+            }
+            Repr::Shared(s) => s,
+        }
+    }
+
+    /// Returns `true` if both atoms share the same allocation, as long atoms from the same
+    /// `Interner` do. Inline atoms never share an allocation.
+    #[must_use]
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        match (&this.0, &other.0) {
+            (Repr::Shared(a), Repr::Shared(b)) => RefCounted::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+
+    /// Returns `true` if the text is stored inline, without a heap allocation.
+    #[must_use]
+    pub const fn is_inline(&self) -> bool {
+        matches!(self.0, Repr::Inline { .. })
+    }
+}
+
+impl Deref for Atom {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for Atom {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl Borrow<str> for Atom {
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl From<&str> for Atom {
+    fn from(s: &str) -> Self {
+        match u8::try_from(s.len()) {
+            Ok(len) if s.len() <= INLINE_CAPACITY => {
+                let mut bytes = [0; INLINE_CAPACITY];
+                bytes[..s.len()].copy_from_slice(s.as_bytes());
+                Self(Repr::Inline { len, bytes })
+            }
+            _ => Self(Repr::Shared(RefCounted::new(Box::from(s)))),
+        }
+    }
+}
+
+impl From<String> for Atom {
+    fn from(s: String) -> Self {
+        if s.len() <= INLINE_CAPACITY {
+            Self::from(s.as_str())
+        } else {
+            Self(Repr::Shared(RefCounted::new(s.into_boxed_str())))
+        }
+    }
+}
+
+impl From<Atom> for String {
+    fn from(a: Atom) -> Self {
+        Self::from(a.as_str())
+    }
+}
+
+impl PartialEq for Atom {
+    fn eq(&self, other: &Self) -> bool {
+        Self::ptr_eq(self, other) || self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for Atom {}
+
+impl PartialEq<str> for Atom {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for Atom {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl PartialEq<String> for Atom {
+    fn eq(&self, other: &String) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialOrd for Atom {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Atom {
+    fn cmp(&self, other: &Self) -> Ordering {
+        if Self::ptr_eq(self, other) {
+            Ordering::Equal
+        } else {
+            self.as_str().cmp(other.as_str())
+        }
+    }
+}
+
+impl Hash for Atom {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state);
+    }
+}
+
+impl fmt::Debug for Atom {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl fmt::Display for Atom {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// `Interner` hands out one shared `Atom` per distinct keyword or symbol.
+///
+/// `Edn::from_str` interns within a single parse. Keeping an `Interner` around and parsing with
+/// `Interner::parse` shares atoms across every document it reads.
+/// ```rust
+/// use edn_rs::edn::{Atom, Edn, Interner};
+///
+/// let mut interner = Interner::new();
+/// let first = interner.parse("{:user/favorite-color :blue}").unwrap();
+/// let second = interner.parse("[:user/favorite-color]").unwrap();
+///
+/// let (key, _) = first.map_iter().unwrap().next().unwrap();
+/// let Edn::Key(atom) = &second[0] else { panic!() };
+/// assert_eq!(key, ":user/favorite-color");
+/// assert!(Atom::ptr_eq(atom, &interner.intern(":user/favorite-color")));
+/// assert_eq!(interner.len(), 1);
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct Interner {
+    atoms: BTreeSet<Atom>,
+}
+
+impl Interner {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            atoms: BTreeSet::new(),
+        }
+    }
+
+    /// Returns the `Atom` for `s`, allocating it only the first time `s` is seen. Short text is
+    /// stored inline and never needs to be remembered.
+    pub fn intern(&mut self, s: &str) -> Atom {
+        if s.len() <= INLINE_CAPACITY {
+            return Atom::from(s);
+        }
+        if let Some(atom) = self.atoms.get(s) {
+            return atom.clone();
+        }
+        let atom = Atom::from(s);
+        self.atoms.insert(atom.clone());
+        atom
+    }
+
+    /// Number of distinct atoms too long to be stored inline interned so far.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.atoms.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.atoms.is_empty()
+    }
+
+    /// Parses an EDN string like `Edn::from_str`, sharing keywords and symbols with every other
+    /// document parsed by this `Interner`.
+    ///
+    /// # Errors
+    ///
+    /// Same as `Edn::from_str`.
+    pub fn parse(&mut self, edn: &str) -> Result<Edn, Error> {
+        parse::parse_with(edn, self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn interning_shares_allocations() {
+        let mut interner = Interner::new();
+        let a = interner.intern(":user/favorite-color");
+        let b = interner.intern(":user/favorite-color");
+
+        assert!(Atom::ptr_eq(&a, &b));
+        assert!(!Atom::ptr_eq(&a, &Atom::from(":user/favorite-color")));
+        assert_eq!(a, Atom::from(":user/favorite-color"));
+        assert_eq!(interner.len(), 1);
+    }
+
+    #[test]
+    fn short_atoms_are_inline() {
+        let mut interner = Interner::new();
+        let short = interner.intern(":user/name");
+        let long = Atom::from(String::from(":user/favorite-color"));
+
+        assert!(short.is_inline());
+        assert!(!long.is_inline());
+        assert_eq!(short, Atom::from(String::from(":user/name")));
+        assert_eq!(long.as_str(), ":user/favorite-color");
+        assert!(Atom::from("é".repeat(7).as_str()).is_inline());
+        assert!(interner.is_empty());
+    }
+
+    #[test]
+    fn map_keys_share_atoms() {
+        let edn = parse::parse("[{:db/id 1} {:db/id 2}]").unwrap();
+        let keys = edn
+            .iter_some()
+            .unwrap()
+            .flat_map(|m| m.map_iter().unwrap().map(|(k, _)| k))
+            .collect::<alloc::vec::Vec<_>>();
+        assert_eq!(keys, [":db/id", ":db/id"]);
+        assert!(keys.iter().all(|k| k.is_inline()));
+
+        let edn = parse::parse("[{:user/favorite-color 1} {:user/favorite-color 2}]").unwrap();
+        let keys = edn
+            .iter_some()
+            .unwrap()
+            .flat_map(|m| m.map_iter().unwrap().map(|(k, _)| k))
+            .collect::<alloc::vec::Vec<_>>();
+        assert!(Atom::ptr_eq(keys[0], keys[1]));
+    }
+
+    #[test]
+    fn atoms_order_like_strings() {
+        assert!(Atom::from(":a") < Atom::from(":b"));
+        assert_eq!(Atom::from(":a").cmp(&Atom::from(":a")), Ordering::Equal);
+    }
+}
@@ -0,0 +1,243 @@
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::vec::Vec;
+
+use super::visit::postwalk;
+use crate::edn::{Atom, Edn, Error, List, Path, Set, Vector};
+
+/// Tag that makes a value replace the one it is merged into, instead of merging with it.
+pub const REPLACE_TAG: &str = "merge/replace";
+/// Tag that makes a value a fallback, used only where there is nothing to merge it into.
+pub const DISPLACE_TAG: &str = "merge/displace";
+
+/// How two vectors, or two lists, are merged.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SeqMerge {
+    /// The right elements after the left ones.
+    #[default]
+    Concat,
+    /// The right elements before the left ones.
+    Prepend,
+    /// Only the right elements.
+    Replace,
+}
+
+/// How two sets are merged.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SetMerge {
+    #[default]
+    Union,
+    Replace,
+}
+
+/// What happens when a collection is merged with a value of another type, like a map with a
+/// vector or a vector with a list.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Mismatch {
+    /// The right value wins.
+    #[default]
+    Right,
+    /// The left value is kept.
+    Left,
+    /// Merging fails with `Error::Merge`.
+    Error,
+}
+
+/// `MergeStrategy` configures `Edn::merge_with`. The default concatenates sequences, unions sets
+/// and lets the right value win on a type mismatch.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MergeStrategy {
+    pub sequences: SeqMerge,
+    pub sets: SetMerge,
+    pub mismatch: Mismatch,
+}
+
+fn is_marker(tag: &str) -> bool {
+    tag == REPLACE_TAG || tag == DISPLACE_TAG
+}
+
+fn marker(edn: &Edn) -> Option<(&str, &Edn)> {
+    match edn {
+        Edn::Tagged(t) if is_marker(t.tag()) => Some((t.tag(), t.value())),
+        _ => None,
+    }
+}
+
+// Markers only mean something while merging, so none are left in the result.
+fn strip(edn: &Edn) -> Edn {
+    postwalk(edn.clone(), |e| match e {
+        Edn::Tagged(t) if is_marker(t.tag()) => t.into_parts().1,
+        e => e,
+    })
+}
+
+const fn is_collection(edn: &Edn) -> bool {
+    matches!(
+        edn,
+        Edn::Map(_) | Edn::Vector(_) | Edn::List(_) | Edn::Set(_)
+    )
+}
+
+fn sequence(left: &[Edn], right: &[Edn], strategy: SeqMerge) -> Vec<Edn> {
+    let (first, second) = match strategy {
+        SeqMerge::Concat => (left, right),
+        SeqMerge::Prepend => (right, left),
+        SeqMerge::Replace => (right, &[][..]),
+    };
+    first.iter().chain(second).map(strip).collect()
+}
+
+fn merge(
+    left: &Edn,
+    right: &Edn,
+    strategy: MergeStrategy,
+    path: &mut Vec<Edn>,
+) -> Result<Edn, Error> {
+    let left = match (marker(left), marker(right)) {
+        (_, Some((REPLACE_TAG, right))) => return Ok(strip(right)),
+        (_, Some(_)) => return Ok(strip(left)),
+        (Some((DISPLACE_TAG, _)), _) => return Ok(strip(right)),
+        (Some((_, left)), _) => left,
+        (None, None) => left,
+    };
+    Ok(match (left, right) {
+        (Edn::Map(l), Edn::Map(r)) => {
+            let mut merged =
+                l.0.iter()
+                    .map(|(k, v)| (k.clone(), strip(v)))
+                    .collect::<BTreeMap<Atom, Edn>>();
+            for (k, v) in r.0.iter() {
+                let value = match l.0.get(k) {
+                    Some(existing) => {
+                        path.push(Edn::read_key(k));
+                        let value = merge(existing, v, strategy, path)?;
+                        path.pop();
+                        value
+                    }
+                    None => strip(v),
+                };
+                merged.insert(k.clone(), value);
+            }
+            Edn::Map(merged.into())
+        }
+        (Edn::Vector(l), Edn::Vector(r)) => {
+            Edn::Vector(Vector::new(sequence(&l.0, &r.0, strategy.sequences)))
+        }
+        (Edn::List(l), Edn::List(r)) => {
+            Edn::List(List::new(sequence(&l.0, &r.0, strategy.sequences)))
+        }
+        (Edn::Set(l), Edn::Set(r)) => Edn::Set(Set::new(match strategy.sets {
+            SetMerge::Union => l.0.iter().chain(r.0.iter()).map(strip).collect(),
+            SetMerge::Replace => r.0.iter().map(strip).collect(),
+        })),
+        (l, r) if !is_collection(l) && !is_collection(r) => strip(r),
+        (l, r) => match strategy.mismatch {
+            Mismatch::Right => strip(r),
+            Mismatch::Left => strip(l),
+            Mismatch::Error => {
+                return Err(Error::Merge(format!(
+                    "cannot merge {} into {} at {}",
+                    r.type_name(),
+                    l.type_name(),
+                    Path::new(path.clone())
+                )))
+            }
+        },
+    })
+}
+
+pub fn merge_with(left: &Edn, right: &Edn, strategy: MergeStrategy) -> Result<Edn, Error> {
+    merge(left, right, strategy, &mut Vec::new())
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::string::ToString;
+    use core::str::FromStr;
+
+    use super::*;
+
+    fn edn(s: &str) -> Edn {
+        Edn::from_str(s).unwrap()
+    }
+
+    fn merged(left: &str, right: &str, strategy: MergeStrategy) -> Result<Edn, Error> {
+        merge_with(&edn(left), &edn(right), strategy)
+    }
+
+    #[test]
+    fn maps_merge_deeply() {
+        assert_eq!(
+            merged(
+                "{:db {:host \"localhost\" :port 5432} :tags [:a] :roles #{:x}}",
+                "{:db {:port 6543} :tags [:b] :roles #{:y} :debug true}",
+                MergeStrategy::default()
+            ),
+            Ok(edn(
+                "{:db {:host \"localhost\" :port 6543} :tags [:a :b] :roles #{:x :y} :debug true}"
+            ))
+        );
+    }
+
+    #[test]
+    fn strategies_choose_how_collections_merge() {
+        let strategy = |sequences, sets| MergeStrategy {
+            sequences,
+            sets,
+            ..MergeStrategy::default()
+        };
+
+        assert_eq!(
+            merged("[1 2]", "[3]", strategy(SeqMerge::Prepend, SetMerge::Union)),
+            Ok(edn("[3 1 2]"))
+        );
+        assert_eq!(
+            merged("(1 2)", "(3)", strategy(SeqMerge::Replace, SetMerge::Union)),
+            Ok(edn("(3)"))
+        );
+        assert_eq!(
+            merged(
+                "#{1 2}",
+                "#{3}",
+                strategy(SeqMerge::Concat, SetMerge::Replace)
+            ),
+            Ok(edn("#{3}"))
+        );
+    }
+
+    #[test]
+    fn tags_override_the_strategy() {
+        assert_eq!(
+            merged(
+                "{:a [1] :b [1] :c #merge/displace [1] :d {:e 1}}",
+                "{:a #merge/replace [2] :b #merge/displace [2] :c [2] :d {:f #merge/replace [3]} :g #merge/displace 4}",
+                MergeStrategy::default()
+            ),
+            Ok(edn("{:a [2] :b [1] :c [2] :d {:e 1 :f [3]} :g 4}"))
+        );
+    }
+
+    #[test]
+    fn mismatched_types() {
+        let strategy = |mismatch| MergeStrategy {
+            mismatch,
+            ..MergeStrategy::default()
+        };
+        let (left, right) = ("{:a {:b [1]} :c 1}", "{:a {:b (2)} :c \"x\"}");
+
+        assert_eq!(
+            merged(left, right, strategy(Mismatch::Right)),
+            Ok(edn("{:a {:b (2)} :c \"x\"}"))
+        );
+        assert_eq!(
+            merged(left, right, strategy(Mismatch::Left)),
+            Ok(edn("{:a {:b [1]} :c \"x\"}"))
+        );
+        assert_eq!(
+            merged(left, right, strategy(Mismatch::Error)),
+            Err(Error::Merge(
+                "cannot merge list into vector at [:a :b]".to_string()
+            ))
+        );
+    }
+}
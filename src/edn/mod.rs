@@ -1,171 +1,282 @@
 use alloc::boxed::Box;
-use alloc::collections::BTreeMap;
-#[cfg(feature = "sets")]
-use alloc::collections::BTreeSet;
+use alloc::collections::{BTreeMap, BTreeSet};
 use alloc::string::{String, ToString};
 use alloc::vec::Vec;
 use alloc::{fmt, format};
-#[cfg(feature = "sets")]
-use core::cmp::{Ord, PartialOrd};
+use core::cmp::{Ord, Ordering, PartialOrd};
 use core::convert::{Infallible, TryFrom};
-use core::num;
+use core::hash::{Hash, Hasher};
+use core::num::{self, FpCategory};
 
 use crate::deserialize::parse::{self};
+pub use diff::{diff, patch, Edit, EditScript};
+pub use intern::{Atom, Interner};
+pub use merge::{MergeStrategy, Mismatch, SeqMerge, SetMerge, DISPLACE_TAG, REPLACE_TAG};
+pub use path::Path;
+use shared::Shared;
 use utils::index::Index;
-
-#[cfg(feature = "sets")]
-use ordered_float::OrderedFloat;
-
+pub use visit::{Visitor, VisitorMut};
+pub use zipper::Zipper;
+
+mod canonical;
+mod collections;
+mod convert;
+mod diff;
+mod hasheq;
+mod intern;
+mod merge;
+mod path;
+#[cfg(feature = "digest")]
+mod sha256;
+mod shared;
 #[doc(hidden)]
 pub mod utils;
+pub mod visit;
+mod zipper;
 
 /// `EdnType` is an Enum with possible values for an EDN type
 /// Symbol and Char are not yet implemented
 /// String implementation of Edn can be obtained with `.to_string()`
-#[derive(Debug, Clone, PartialEq)]
-#[cfg_attr(feature = "sets", derive(Eq, PartialOrd, Ord))]
+///
+/// Every variant fits in two words, so an `Edn` is 24 bytes on 64-bit targets. Collections and
+/// tagged values live behind a pointer, and short keywords and symbols are stored inline.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[non_exhaustive]
 pub enum Edn {
-    Tagged(String, Box<Edn>),
+    Tagged(Tagged),
     Vector(Vector),
-    #[cfg(feature = "sets")]
     Set(Set),
     Map(Map),
     List(List),
-    Key(String),
-    Symbol(String),
-    Str(String),
+    Key(Atom),
+    Symbol(Atom),
+    Str(Box<str>),
     Int(i64),
     UInt(u64),
     Double(Double),
-    Rational(String),
+    Rational(Box<str>),
     Char(char),
     Bool(bool),
     Nil,
     Empty,
 }
 
-#[derive(Clone, Ord, Debug, Eq, PartialEq, PartialOrd, Hash)]
-#[cfg(feature = "sets")]
-pub struct Double(pub(crate) OrderedFloat<f64>);
-
-#[derive(Clone, Debug, PartialEq)]
-#[cfg(not(feature = "sets"))]
+/// `Double` wraps an `f64` with a total ordering.
+///
+/// This lets `Edn` be ordered, hashed and stored in sets regardless of enabled features.
+/// `NaN` is equal to itself and greater than any other value, and `-0.0` is equal to `0.0`.
+#[derive(Clone, Debug)]
 pub struct Double(f64);
 
 impl fmt::Display for Double {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.0)
+        write_float(f, self.0, &self.0)
     }
 }
 
-#[cfg(feature = "sets")]
-impl Double {
-    fn to_float(&self) -> f64 {
-        self.0.into_inner()
+/// Writes a float the way `Display for Edn` and `Serialize` both do: `digits` with the shortest
+/// digits that read back the same, always with a `.` or an exponent so they don't read back as an
+/// integer, or `##NaN`, `##Inf` and `##-Inf` for what `value` can't write as digits. `digits` is
+/// the float itself, so an `f32` keeps its own shortest digits instead of those of its `f64`.
+pub(crate) fn write_float(
+    w: &mut dyn fmt::Write,
+    value: f64,
+    digits: &dyn fmt::Debug,
+) -> fmt::Result {
+    match value.classify() {
+        FpCategory::Nan => w.write_str("##NaN"),
+        FpCategory::Infinite if value > 0.0 => w.write_str("##Inf"),
+        FpCategory::Infinite => w.write_str("##-Inf"),
+        // `Debug` is the shortest round trip, and adds `.0` or uses an exponent when `Display`
+        // would print bare digits like `1` or `100000000000000000000`.
+        _ => write!(w, "{digits:?}"),
     }
 }
 
-#[cfg(not(feature = "sets"))]
 impl Double {
-    const fn to_float(&self) -> f64 {
+    pub(crate) const fn to_float(&self) -> f64 {
         self.0
     }
-}
 
-#[cfg(feature = "sets")]
-impl From<f64> for Double {
-    fn from(f: f64) -> Self {
-        Self(OrderedFloat(f))
+    // Every `NaN` and both zeroes collapse into a single representation, matching `Ord`.
+    const fn canonical_bits(&self) -> u64 {
+        match self.0.classify() {
+            FpCategory::Nan => f64::NAN.to_bits(),
+            FpCategory::Zero => 0,
+            _ => self.0.to_bits(),
+        }
     }
 }
 
-#[cfg(not(feature = "sets"))]
 impl From<f64> for Double {
     fn from(f: f64) -> Self {
         Self(f)
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
-#[cfg_attr(feature = "sets", derive(Eq, PartialOrd, Ord))]
-pub struct Vector(Vec<Edn>);
+impl PartialEq for Double {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Double {}
+
+impl PartialOrd for Double {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Double {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0
+            .partial_cmp(&other.0)
+            .unwrap_or_else(|| self.0.is_nan().cmp(&other.0.is_nan()))
+    }
+}
+
+impl Hash for Double {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(self.canonical_bits());
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Vector(pub(crate) Shared<Vec<Edn>>);
 impl Vector {
     #[must_use]
-    pub const fn new(v: Vec<Edn>) -> Self {
-        Self(v)
+    pub fn new(v: Vec<Edn>) -> Self {
+        Self(Shared::new(v))
     }
 
     #[must_use]
-    pub const fn empty() -> Self {
-        Self(Vec::new())
+    pub fn empty() -> Self {
+        Self(Shared::new(Vec::new()))
     }
 
     #[must_use]
     pub fn to_vec(self) -> Vec<Edn> {
-        self.0
+        self.0.into_inner()
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
-#[cfg_attr(feature = "sets", derive(Eq, PartialOrd, Ord))]
-pub struct List(Vec<Edn>);
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct List(pub(crate) Shared<Vec<Edn>>);
 impl List {
     #[must_use]
     pub fn new(v: Vec<Edn>) -> Self {
-        Self(v)
+        Self(Shared::new(v))
     }
 
     #[must_use]
-    pub const fn empty() -> Self {
-        Self(Vec::new())
+    pub fn empty() -> Self {
+        Self(Shared::new(Vec::new()))
     }
 
     #[must_use]
     pub fn to_vec(self) -> Vec<Edn> {
-        self.0
+        self.0.into_inner()
     }
 }
 
-#[cfg(feature = "sets")]
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
-pub struct Set(BTreeSet<Edn>);
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Set(pub(crate) Shared<BTreeSet<Edn>>);
 
-#[cfg(feature = "sets")]
 impl Set {
     #[must_use]
-    pub const fn new(v: BTreeSet<Edn>) -> Self {
-        Self(v)
+    pub fn new(v: BTreeSet<Edn>) -> Self {
+        Self(Shared::new(v))
     }
 
     #[must_use]
-    pub const fn empty() -> Self {
-        Self(BTreeSet::new())
+    pub fn empty() -> Self {
+        Self(Shared::new(BTreeSet::new()))
     }
 
     #[must_use]
     pub fn to_set(self) -> BTreeSet<Edn> {
-        self.0
+        self.0.into_inner()
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
-#[cfg_attr(feature = "sets", derive(Eq, PartialOrd, Ord))]
-pub struct Map(BTreeMap<String, Edn>);
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Map(pub(crate) Shared<BTreeMap<Atom, Edn>>);
 impl Map {
     #[must_use]
     pub fn new(m: BTreeMap<String, Edn>) -> Self {
-        Self(m)
+        m.into_iter().collect()
     }
 
     #[must_use]
-    pub const fn empty() -> Self {
-        Self(BTreeMap::new())
+    pub fn empty() -> Self {
+        Self(Shared::new(BTreeMap::new()))
     }
 
     #[must_use]
     pub fn to_map(self) -> BTreeMap<String, Edn> {
-        self.0
+        self.into_iter().map(|(k, v)| (k.into(), v)).collect()
+    }
+}
+
+impl From<BTreeMap<Atom, Edn>> for Map {
+    fn from(m: BTreeMap<Atom, Edn>) -> Self {
+        Self(Shared::new(m))
+    }
+}
+
+/// `Tagged` is a tag and the value it applies to, like `#inst "1985-04-12T23:20:50.52Z"`.
+///
+/// Both live in a single allocation, so `Edn::Tagged` is one pointer wide.
+/// ```rust
+/// use edn_rs::edn::{Edn, Tagged};
+///
+/// let inst = Edn::Tagged(Tagged::new("inst", Edn::Str("1985-04-12T23:20:50.52Z".into())));
+/// assert_eq!(inst.to_string(), "#inst \"1985-04-12T23:20:50.52Z\"");
+/// ```
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Tagged(pub(crate) Box<(Atom, Edn)>);
+
+impl Tagged {
+    #[must_use]
+    pub fn new(tag: impl Into<Atom>, value: Edn) -> Self {
+        Self(Box::new((tag.into(), value)))
+    }
+
+    /// The tag without its leading `#`.
+    #[must_use]
+    pub fn tag(&self) -> &str {
+        &self.0 .0
+    }
+
+    #[must_use]
+    pub fn value(&self) -> &Edn {
+        &self.0 .1
+    }
+
+    #[must_use]
+    pub fn value_mut(&mut self) -> &mut Edn {
+        &mut self.0 .1
+    }
+
+    #[must_use]
+    pub fn into_parts(self) -> (Atom, Edn) {
+        *self.0
+    }
+}
+
+impl fmt::Debug for Tagged {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Tagged")
+            .field(&self.0 .0)
+            .field(&self.0 .1)
+            .finish()
+    }
+}
+
+impl core::fmt::Display for Tagged {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "#{} {}", self.tag(), self.value())
     }
 }
 
@@ -199,7 +310,6 @@ impl core::fmt::Display for List {
     }
 }
 
-#[cfg(feature = "sets")]
 impl core::fmt::Display for Set {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "#{{")?;
@@ -230,38 +340,37 @@ impl core::fmt::Display for Map {
     }
 }
 
-fn char_to_edn(c: char) -> String {
+fn write_char(f: &mut core::fmt::Formatter<'_>, c: char) -> core::fmt::Result {
     match c {
-        '\n' => "\\newline".to_string(),
-        '\r' => "\\return".to_string(),
-        ' ' => "\\space".to_string(),
-        '\t' => "\\tab".to_string(),
-        _ => format!("\\{c}"),
+        '\n' => f.write_str("\\newline"),
+        '\r' => f.write_str("\\return"),
+        ' ' => f.write_str("\\space"),
+        '\t' => f.write_str("\\tab"),
+        _ => write!(f, "\\{c}"),
     }
 }
 
+// Each value is written straight into `f`, so nested collections don't build a `String` apiece.
 impl core::fmt::Display for Edn {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        let text = match self {
-            Self::Vector(v) => format!("{v}"),
-            #[cfg(feature = "sets")]
-            Self::Set(s) => format!("{s}"),
-            Self::Map(m) => format!("{m}"),
-            Self::List(l) => format!("{l}"),
-            Self::Symbol(sy) => sy.to_string(),
-            Self::Key(k) => k.to_string(),
-            Self::Str(s) => format!("{s:?}"),
-            Self::Int(i) => format!("{i}"),
-            Self::UInt(u) => format!("{u}"),
-            Self::Double(d) => format!("{d}"),
-            Self::Rational(r) => r.to_string(),
-            Self::Bool(b) => format!("{b}"),
-            Self::Char(c) => char_to_edn(*c),
-            Self::Nil => String::from("nil"),
-            Self::Empty => String::new(),
-            Self::Tagged(tag, edn) => format!("#{tag} {edn}"),
-        };
-        write!(f, "{text}")
+        match self {
+            Self::Vector(v) => write!(f, "{v}"),
+            Self::Set(s) => write!(f, "{s}"),
+            Self::Map(m) => write!(f, "{m}"),
+            Self::List(l) => write!(f, "{l}"),
+            Self::Symbol(sy) => f.write_str(sy),
+            Self::Key(k) => f.write_str(k),
+            Self::Str(s) => write!(f, "{s:?}"),
+            Self::Int(i) => write!(f, "{i}"),
+            Self::UInt(u) => write!(f, "{u}"),
+            Self::Double(d) => write!(f, "{d}"),
+            Self::Rational(r) => f.write_str(r),
+            Self::Bool(b) => write!(f, "{b}"),
+            Self::Char(c) => write_char(f, *c),
+            Self::Nil => f.write_str("nil"),
+            Self::Empty => Ok(()),
+            Self::Tagged(t) => write!(f, "{t}"),
+        }
     }
 }
 
@@ -270,8 +379,8 @@ impl Edn {
     /// ```rust
     /// use edn_rs::edn::{Edn, Vector};
     ///
-    /// let key = Edn::Key(String::from(":1234"));
-    /// let q = Edn::Rational(String::from("3/4"));
+    /// let key = Edn::Key(":1234".into());
+    /// let q = Edn::Rational("3/4".into());
     /// let i = Edn::Int(12i64);
     ///
     /// assert_eq!(Edn::Vector(Vector::empty()).to_float(), None);
@@ -296,8 +405,8 @@ impl Edn {
     /// ```rust
     /// use edn_rs::edn::{Edn, Vector};
     ///
-    /// let key = Edn::Key(String::from(":1234"));
-    /// let q = Edn::Rational(String::from("3/4"));
+    /// let key = Edn::Key(":1234".into());
+    /// let q = Edn::Rational("3/4".into());
     /// let f = Edn::Double(12.3f64.into());
     ///
     /// assert_eq!(Edn::Vector(Vector::empty()).to_float(), None);
@@ -354,8 +463,8 @@ impl Edn {
     /// use edn_rs::edn::{Edn};
     ///
     /// let b = Edn::Bool(true);
-    /// let s = Edn::Str("true".to_string());
-    /// let symbol = Edn::Symbol("false".to_string());
+    /// let s = Edn::Str("true".into());
+    /// let symbol = Edn::Symbol("false".into());
     ///
     /// assert_eq!(b.to_bool().unwrap(),true);
     /// assert_eq!(s.to_bool().unwrap(),true);
@@ -365,7 +474,8 @@ impl Edn {
     pub fn to_bool(&self) -> Option<bool> {
         match self {
             Self::Bool(b) => Some(*b),
-            Self::Str(s) | Self::Symbol(s) => s.parse::<bool>().ok(),
+            Self::Str(s) => s.parse::<bool>().ok(),
+            Self::Symbol(s) => s.parse::<bool>().ok(),
             _ => None,
         }
     }
@@ -375,7 +485,7 @@ impl Edn {
     /// use edn_rs::edn::{Edn};
     ///
     /// let c = Edn::Char('c');
-    /// let symbol = Edn::Symbol("false".to_string());
+    /// let symbol = Edn::Symbol("false".into());
     ///
     /// assert_eq!(c.to_char().unwrap(),'c');
     /// assert_eq!(symbol.to_char(), None);
@@ -396,7 +506,7 @@ impl Edn {
             Self::Vector(_) => Some(
                 self.iter_some()?
                     .map(|e| match e {
-                        Self::Str(s) => s.clone(),
+                        Self::Str(s) => s.to_string(),
                         _ => e.to_string(),
                     })
                     .collect::<Vec<String>>(),
@@ -404,16 +514,15 @@ impl Edn {
             Self::List(_) => Some(
                 self.iter_some()?
                     .map(|e| match e {
-                        Self::Str(s) => s.clone(),
+                        Self::Str(s) => s.to_string(),
                         _ => e.to_string(),
                     })
                     .collect::<Vec<String>>(),
             ),
-            #[cfg(feature = "sets")]
             Self::Set(_) => Some(
                 self.iter_some()?
                     .map(|e| match e {
-                        Self::Str(s) => s.clone(),
+                        Self::Str(s) => s.to_string(),
                         _ => e.to_string(),
                     })
                     .collect::<Vec<String>>(),
@@ -437,7 +546,6 @@ impl Edn {
                     .map(Self::to_int)
                     .collect::<Option<Vec<i64>>>()?,
             ),
-            #[cfg(feature = "sets")]
             Self::Set(_) if !self.iter_some()?.any(|e| e.to_int().is_none()) => Some(
                 self.iter_some()?
                     .map(Self::to_int)
@@ -462,7 +570,6 @@ impl Edn {
                     .map(Self::to_uint)
                     .collect::<Option<Vec<u64>>>()?,
             ),
-            #[cfg(feature = "sets")]
             Self::Set(_) if !self.iter_some()?.any(|e| e.to_uint().is_none()) => Some(
                 self.iter_some()?
                     .map(Self::to_uint)
@@ -487,7 +594,6 @@ impl Edn {
                     .map(Self::to_float)
                     .collect::<Option<Vec<f64>>>()?,
             ),
-            #[cfg(feature = "sets")]
             Self::Set(_) if !self.iter_some()?.any(|e| e.to_float().is_none()) => Some(
                 self.iter_some()?
                     .map(Self::to_float)
@@ -512,7 +618,6 @@ impl Edn {
                     .map(Self::to_bool)
                     .collect::<Option<Vec<bool>>>()?,
             ),
-            #[cfg(feature = "sets")]
             Self::Set(_) if !self.iter_some()?.any(|e| e.to_bool().is_none()) => Some(
                 self.iter_some()?
                     .map(Self::to_bool)
@@ -549,13 +654,32 @@ impl Edn {
         format!("{self:?}")
     }
 
+    /// `hasheq` returns the same value as Clojure's `hash` would for this `Edn` on the JVM,
+    /// so Rust and Clojure processes can agree on cache keys, buckets and shards.
+    ///
+    /// Numbers, strings, chars, keywords, symbols, vectors, lists, sets and maps follow
+    /// `clojure.lang.Murmur3` and the Java `hashCode` of their reader types. Ratios are normalized
+    /// like the reader does, so `2/4` hashes as `1/2`. `#uuid` tagged elements hash as
+    /// `java.util.UUID`; other tagged elements and `Edn::Empty` have no JVM counterpart.
+    /// ```rust
+    /// use edn_rs::edn::{Edn, Vector};
+    ///
+    /// assert_eq!(Edn::Int(1).hasheq(), 1392991556);
+    /// assert_eq!(Edn::Vector(Vector::empty()).hasheq(), -2017569654);
+    /// ```
+    #[must_use]
+    pub fn hasheq(&self) -> i32 {
+        hasheq::hasheq(self)
+    }
+
     /// Index into a EDN vector, list, set or map. A string index can be used to access a
-    /// value in a map, and a u64 index can be used to access an element of a
-    /// seqs.
+    /// value in a map, and an integer index can be used to access an element of a
+    /// seq, counting from the end when negative. An `Edn` index can access a map value, a
+    /// set element or, when it is an integer, an element of a seq.
     ///
     /// Returns `None` if the type of `self` does not match the type of the
     /// index, for example if the index is a string and `self` is a seq or a
-    /// number. Also returns `None` if the given key does not exist in the map
+    /// number. Also returns `None` if the given key does not exist in the map or set
     /// or the given index is not within the bounds of the seq.
     ///
     /// ```rust
@@ -569,7 +693,7 @@ impl Edn {
     ///     assert_eq!(edn[1], edn!(1.2));
     ///     assert_eq!(edn.get(1).unwrap(), &edn!(1.2));
     ///     assert_eq!(edn[3]["false"], edn!(:f));
-    ///     assert_eq!(edn[3].get("false").unwrap(), &Edn::Key(":f".to_string()));
+    ///     assert_eq!(edn[3].get("false").unwrap(), &Edn::Key(":f".into()));
     /// }
     /// ```
     #[must_use]
@@ -577,9 +701,8 @@ impl Edn {
         index.index_into(self)
     }
 
-    /// Mutably index into a EDN vector, set, list or map. A string index can be used to
-    /// access a value in a map, and a u64 index can be used to access an
-    /// element of a seq.
+    /// Mutably index into a EDN vector, list or map, with the same indices as `get`. Set
+    /// elements can't be borrowed mutably, so indexing a set returns `None`.
     ///
     /// Returns `None` if the type of `self` does not match the type of the
     /// index, for example if the index is a string and `self` is a seq or a
@@ -597,7 +720,7 @@ impl Edn {
     ///     assert_eq!(edn[1], edn!(1.2));
     ///     assert_eq!(edn.get_mut(1).unwrap(), &edn!(1.2));
     ///     assert_eq!(edn[3]["false"], edn!(:f));
-    ///     assert_eq!(edn[3].get_mut("false").unwrap(), &Edn::Key(":f".to_string()));
+    ///     assert_eq!(edn[3].get_mut("false").unwrap(), &Edn::Key(":f".into()));
     /// }
     /// ```
     #[must_use]
@@ -605,6 +728,188 @@ impl Edn {
         index.index_into_mut(self)
     }
 
+    /// Like `get_mut`, but inserts what's missing the way `edn[index] = value` does: a `nil` for a
+    /// missing map key or for the index one past the end of a vector or list, and an empty map or
+    /// vector in place of `nil`. Returns `None` for anything else instead of panicking.
+    ///
+    /// ```rust
+    /// use edn_rs::Edn;
+    /// use std::str::FromStr;
+    ///
+    /// let mut edn = Edn::from_str("{:a [1]}").unwrap();
+    /// *edn.get_or_insert(":a").unwrap().get_or_insert(1).unwrap() = Edn::UInt(2);
+    /// *edn.get_or_insert(":b").unwrap() = Edn::Bool(true);
+    /// assert_eq!(edn, Edn::from_str("{:a [1 2] :b true}").unwrap());
+    /// assert_eq!(edn[":a"].get_or_insert(5), None);
+    /// ```
+    pub fn get_or_insert<I: Index>(&mut self, index: I) -> Option<&mut Self> {
+        index.index_or_insert(self)
+    }
+
+    /// `get_in` follows `path` through nested maps, vectors, lists and sets, like Clojure's `get-in`.
+    ///
+    /// ```rust
+    /// use edn_rs::edn::{Edn, Path};
+    /// use std::str::FromStr;
+    ///
+    /// let edn = Edn::from_str("{:people [{:name \"Ana\"}]}").unwrap();
+    /// let path = Path::from_str("[:people 0 :name]").unwrap();
+    ///
+    /// assert_eq!(edn.get_in(&path).unwrap(), &Edn::Str("Ana".into()));
+    /// assert!(edn.get_in(&Path::from_str("[:people 1]").unwrap()).is_err());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// `Error::Path` if a step is missing or `self` can't be looked up at that step.
+    pub fn get_in(&self, path: &Path) -> Result<&Self, Error> {
+        path::get_in(self, path)
+    }
+
+    /// Mutable version of `get_in`. Set elements can't be borrowed mutably.
+    ///
+    /// # Errors
+    ///
+    /// Same as `get_in`.
+    pub fn get_in_mut(&mut self, path: &Path) -> Result<&mut Self, Error> {
+        path::get_in_mut(self, path)
+    }
+
+    /// `assoc_in` sets the value at `path`, like Clojure's `assoc-in`.
+    ///
+    /// Missing steps and `nil` along the way are replaced by new collections: a vector when the
+    /// step is an integer, otherwise a map. An index one past the end of a vector or list appends.
+    /// ```rust
+    /// use edn_rs::edn::{Edn, Path};
+    /// use std::str::FromStr;
+    ///
+    /// let mut edn = Edn::from_str("{:people []}").unwrap();
+    /// edn.assoc_in(&Path::from_str("[:people 0 :name]").unwrap(), Edn::Str("Ana".into())).unwrap();
+    ///
+    /// assert_eq!(edn, Edn::from_str("{:people [{:name \"Ana\"}]}").unwrap());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// `Error::Path` if a step can't be looked up or created. `self` is left unchanged.
+    pub fn assoc_in(&mut self, path: &Path, value: Self) -> Result<(), Error> {
+        path::update_in(self, path, |_| value)
+    }
+
+    /// `update_in` replaces the value at `path` with `f` applied to it, like Clojure's
+    /// `update-in`. `f` receives `Edn::Nil` when the path doesn't exist yet, and missing steps are
+    /// created as in `assoc_in`.
+    /// ```rust
+    /// use edn_rs::edn::{Edn, Path};
+    /// use std::str::FromStr;
+    ///
+    /// let mut edn = Edn::from_str("{:visits 1}").unwrap();
+    /// edn.update_in(&Path::from_str("[:visits]").unwrap(), |v| Edn::from(v.to_uint().unwrap_or(0) + 1)).unwrap();
+    ///
+    /// assert_eq!(edn, Edn::from_str("{:visits 2}").unwrap());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Same as `assoc_in`.
+    pub fn update_in(&mut self, path: &Path, f: impl FnOnce(Self) -> Self) -> Result<(), Error> {
+        path::update_in(self, path, f)
+    }
+
+    /// `dissoc_in` removes and returns the value at `path`. Removing from a vector or list shifts
+    /// the following elements.
+    /// ```rust
+    /// use edn_rs::edn::{Edn, Path};
+    /// use std::str::FromStr;
+    ///
+    /// let mut edn = Edn::from_str("{:a {:b 1 :c 2}}").unwrap();
+    ///
+    /// assert_eq!(edn.dissoc_in(&Path::from_str("[:a :b]").unwrap()).unwrap(), Edn::UInt(1));
+    /// assert_eq!(edn, Edn::from_str("{:a {:c 2}}").unwrap());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// `Error::Path` if `path` is empty or doesn't exist.
+    pub fn dissoc_in(&mut self, path: &Path) -> Result<Self, Error> {
+        path::dissoc_in(self, path)
+    }
+
+    /// `merge_with` deep merges `other` into `self`, returning the merged value. Maps merge key by
+    /// key, vectors, lists and sets merge as `strategy` says, and any other value in `other`
+    /// replaces the one in `self`.
+    ///
+    /// A value in `other` tagged `#merge/replace` replaces the one in `self` instead of merging
+    /// with it, and one tagged `#merge/displace` is only used where `self` has nothing, so a
+    /// config layer can say how each key combines. A `#merge/displace` value in `self` is always
+    /// replaced. These tags are removed from the result.
+    /// ```rust
+    /// use edn_rs::edn::{Edn, MergeStrategy};
+    /// use std::str::FromStr;
+    ///
+    /// let defaults = Edn::from_str("{:port 80 :hosts [\"a\"] :log {:level :info}}").unwrap();
+    /// let local = Edn::from_str("{:hosts #merge/replace [\"b\"] :log {:file \"out.log\"}}").unwrap();
+    ///
+    /// assert_eq!(
+    ///     defaults.merge_with(&local, MergeStrategy::default()).unwrap(),
+    ///     Edn::from_str("{:port 80 :hosts [\"b\"] :log {:level :info :file \"out.log\"}}").unwrap()
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// `Error::Merge` if a collection meets a value of another type and `strategy.mismatch` is
+    /// `Mismatch::Error`.
+    pub fn merge_with(&self, other: &Self, strategy: MergeStrategy) -> Result<Self, Error> {
+        merge::merge_with(self, other, strategy)
+    }
+
+    /// `to_canonical_string` prints `self` in a single deterministic form, for signing and content
+    /// addressing. Equal values always print the same, and the form won't change between versions
+    /// of this crate.
+    ///
+    /// - Elements are separated by a single space, with no commas or newlines.
+    /// - Map entries and set elements are sorted by the canonical form of their keys and elements.
+    /// - Integers are plain decimals and rationals are reduced to lowest terms. Rationals whose
+    ///   numerator or denominator doesn't fit in an `i128` aren't reduced, and are written as read.
+    /// - Doubles use scientific notation with the shortest digits that read back the same, like
+    ///   `1e0` or `2.5e-3`, and `##NaN`, `##Inf` and `##-Inf`.
+    /// - Strings only escape `"`, `\`, `\n`, `\r`, `\t` and other control characters as `\uXXXX`.
+    ///   Chars use `\newline`, `\return`, `\space` and `\tab`, and `\uXXXX` for other control
+    ///   and whitespace characters. The parser reads `\uXXXX` back in both.
+    /// ```rust
+    /// use edn_rs::Edn;
+    /// use std::str::FromStr;
+    ///
+    /// let a = Edn::from_str("{:b #{2 1}, :a [1.0 2/4]}").unwrap();
+    /// let b = Edn::from_str("{:a [1.0 2/4] :b #{1 2}}").unwrap();
+    /// assert_eq!(a.to_canonical_string(), "{:a [1e0 1/2] :b #{1 2}}");
+    /// assert_eq!(a.canonical_bytes(), b.canonical_bytes());
+    /// ```
+    #[must_use]
+    pub fn to_canonical_string(&self) -> String {
+        canonical::to_string(self)
+    }
+
+    /// `canonical_bytes` is the UTF-8 of `to_canonical_string`.
+    #[must_use]
+    pub fn canonical_bytes(&self) -> Vec<u8> {
+        self.to_canonical_string().into_bytes()
+    }
+
+    /// `digest` is the SHA-256 of `canonical_bytes`, so equal values always have the same digest.
+    /// Needs feature `digest`.
+    /// ```rust
+    /// use edn_rs::Edn;
+    ///
+    /// assert_eq!(Edn::Nil.digest()[..4], [0x5d, 0xa3, 0xa4, 0xc7]);
+    /// ```
+    #[cfg(feature = "digest")]
+    #[must_use]
+    pub fn digest(&self) -> [u8; 32] {
+        sha256::sha256(&self.canonical_bytes())
+    }
+
     /// `iter_some` returns an `Option<Iter<Edn>>` with `Some` for types `Edn::Vector` and `Edn::List`
     /// Other types return `None`
     /// ```
@@ -629,7 +934,6 @@ impl Edn {
 
     /// `set_iter` returns am `Option<btree_set::Iter<Edn>>` with `Some` for type `Edn::Set`
     /// Other types return `None`
-    #[cfg(feature = "sets")]
     #[must_use]
     pub fn set_iter(&self) -> Option<alloc::collections::btree_set::Iter<'_, Self>> {
         match self {
@@ -638,10 +942,10 @@ impl Edn {
         }
     }
 
-    /// `map_iter` returns am `Option<btree_map::Iter<String, Edn>>` with `Some` for type `Edn::Map`
+    /// `map_iter` returns an `Option<btree_map::Iter<Atom, Edn>>` with `Some` for type `Edn::Map`
     /// Other types return `None`
     #[must_use]
-    pub fn map_iter(&self) -> Option<alloc::collections::btree_map::Iter<'_, String, Self>> {
+    pub fn map_iter(&self) -> Option<alloc::collections::btree_map::Iter<'_, Atom, Self>> {
         match self {
             Self::Map(m) => Some(m.0.iter()),
             _ => None,
@@ -689,6 +993,39 @@ impl Edn {
     pub fn to_json(&self) -> String {
         crate::json::display_as_json(self)
     }
+
+    /// Name of this value's type, as used in `Error::Type`.
+    pub(crate) const fn type_name(&self) -> &'static str {
+        match self {
+            Self::Empty => "empty",
+            Self::Nil => "null",
+            Self::Bool(_) => "boolean",
+            Self::Int(_) | Self::UInt(_) => "integer",
+            Self::Str(_) => "string",
+            Self::Vector(_) => "vector",
+            Self::Set(_) => "set",
+            Self::List(_) => "list",
+            Self::Map(_) => "map",
+            Self::Key(_) => "key",
+            Self::Char(_) => "char",
+            Self::Symbol(_) => "symbol",
+            Self::Double(_) => "double",
+            Self::Rational(_) => "rational",
+            Self::Tagged(_) => "tagged-element",
+        }
+    }
+
+    /// Reads a map key back from its printed form. Keywords share the key's atom without parsing
+    /// it, and keys the parser can't read are kept as symbols, which print the same way.
+    pub(crate) fn read_key(key: &Atom) -> Self {
+        let is_keyword = key.strip_prefix(':').is_some_and(|name| {
+            !name.contains(|c: char| c.is_whitespace() || parse::DELIMITERS.contains(&c))
+        });
+        if is_keyword {
+            return Self::Key(key.clone());
+        }
+        key.parse().unwrap_or_else(|_| Self::Symbol(key.clone()))
+    }
 }
 
 impl core::str::FromStr for Edn {
@@ -725,8 +1062,17 @@ pub(crate) fn rational_to_double(r: &str) -> Option<f64> {
 pub enum Error {
     ParseEdn(String),
     Deserialize(String),
+    Serialize(String),
     Iter(String),
+    Path(String),
+    Query(String),
+    Merge(String),
     TryFromInt(num::TryFromIntError),
+    /// An `Edn` of type `found` was converted into a Rust type that needs `expected`.
+    Type {
+        expected: &'static str,
+        found: &'static str,
+    },
     #[doc(hidden)]
     Infallable(), // Makes the compiler happy for converting u64 to u64 and i64 to i64
 }
@@ -770,13 +1116,24 @@ impl From<Infallible> for Error {
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Self::ParseEdn(s) | Self::Deserialize(s) | Self::Iter(s) => write!(f, "{}", &s),
+            Self::ParseEdn(s)
+            | Self::Deserialize(s)
+            | Self::Serialize(s)
+            | Self::Iter(s)
+            | Self::Path(s)
+            | Self::Query(s)
+            | Self::Merge(s) => {
+                write!(f, "{}", &s)
+            }
             Self::TryFromInt(e) => write!(f, "{e}"),
+            Self::Type { expected, found } => write!(f, "expected {expected}, found {found}"),
             Self::Infallable() => panic!("Infallable conversion"),
         }
     }
 }
 
+impl core::error::Error for Error {}
+
 #[cfg(test)]
 mod test {
     use alloc::borrow::ToOwned;
@@ -793,6 +1150,74 @@ mod test {
         assert_eq!(rational_to_double("text/moretext"), None);
     }
 
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn compact_layout() {
+        use core::mem::size_of;
+
+        assert_eq!(size_of::<Edn>(), 24);
+        assert_eq!(size_of::<Option<Edn>>(), 24);
+        assert_eq!(size_of::<Atom>(), 16);
+        assert_eq!(size_of::<Tagged>(), 8);
+        assert_eq!(size_of::<Vector>(), 8);
+        assert_eq!(size_of::<List>(), 8);
+        assert_eq!(size_of::<Set>(), 8);
+        assert_eq!(size_of::<Map>(), 8);
+    }
+
+    #[test]
+    fn tagged_parts() {
+        let mut tagged = Tagged::new("inst", Edn::Str("2020".into()));
+        *tagged.value_mut() = Edn::Str("2021".into());
+
+        assert_eq!(tagged.tag(), "inst");
+        assert_eq!(tagged.value(), &Edn::Str("2021".into()));
+        assert_eq!(
+            tagged.into_parts(),
+            (Atom::from("inst"), Edn::Str("2021".into()))
+        );
+    }
+
+    #[test]
+    fn double_total_ordering() {
+        let nan = Edn::Double(f64::NAN.into());
+        let inf = Edn::Double(f64::INFINITY.into());
+
+        assert_eq!(nan, Edn::Double((-f64::NAN).into()));
+        assert!(nan > inf);
+        assert_eq!(Edn::Double(0.0.into()), Edn::Double((-0.0).into()));
+        assert!(Edn::Double((-1.5).into()) < Edn::Double(1.5.into()));
+
+        let set = Set::new(
+            vec![
+                nan.clone(),
+                nan,
+                Edn::Double(0.0.into()),
+                Edn::Double((-0.0).into()),
+            ]
+            .into_iter()
+            .collect(),
+        );
+        assert_eq!(set.to_set().len(), 2);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn equal_doubles_hash_equally() {
+        use core::hash::BuildHasher;
+        use std::collections::hash_map::RandomState;
+
+        let state = RandomState::new();
+        assert_eq!(
+            state.hash_one(Edn::Double(0.0.into())),
+            state.hash_one(Edn::Double((-0.0).into()))
+        );
+        assert_eq!(
+            state.hash_one(Edn::Double(f64::NAN.into())),
+            state.hash_one(Edn::Double((-f64::NAN).into()))
+        );
+    }
+
     #[test]
     fn iterator() {
         let v = Edn::Vector(Vector::new(vec![Edn::Int(5), Edn::Int(6), Edn::Int(7)]));
@@ -817,7 +1242,7 @@ mod test {
     #[test]
     fn to_char() {
         let c = Edn::Char('c');
-        let symbol = Edn::Symbol("d".to_string());
+        let symbol = Edn::Symbol("d".into());
 
         assert_eq!(c.to_char().unwrap(), 'c');
         assert_eq!(symbol.to_char(), None);
@@ -877,7 +1302,7 @@ mod test {
     #[test]
     fn edn_to_string() {
         let edn = Edn::Map(Map::new(
-            map! {":a".to_string() => Edn::Key(":something".to_string()),
+            map! {":a".to_string() => Edn::Key(":something".into()),
             ":b".to_string() => Edn::Bool(false), ":c".to_string() => Edn::Nil},
         ));
         assert_eq!(edn.to_string(), "{:a :something, :b false, :c nil}");
@@ -886,7 +1311,7 @@ mod test {
     #[test]
     fn edn_to_debug() {
         let edn = Edn::Map(Map::new(
-            map! {":a".to_string() => Edn::Key(":something".to_string()),
+            map! {":a".to_string() => Edn::Key(":something".into()),
             ":b".to_string() => Edn::Bool(false), ":c".to_string() => Edn::Nil},
         ));
         let expected = "Map(Map({\":a\": Key(\":something\"), \":b\": Bool(false), \":c\": Nil}))";
@@ -924,10 +1349,10 @@ mod test {
     #[test]
     fn regression_to_vec() {
         let expected = vec!["true", ":b", "test"];
-        let edn = Edn::Vector(Vector(vec![
+        let edn = Edn::Vector(Vector::new(vec![
             Edn::Bool(true),
-            Edn::Key(":b".to_string()),
-            Edn::Str("test".to_string()),
+            Edn::Key(":b".into()),
+            Edn::Str("test".into()),
         ]));
         let edn_vec = edn.to_vec().unwrap();
 
@@ -936,11 +1361,11 @@ mod test {
 
     #[test]
     fn get_vec_at() {
-        let expected = &Edn::Key(":b".to_string());
-        let edn = Edn::Vector(Vector(vec![
+        let expected = &Edn::Key(":b".into());
+        let edn = Edn::Vector(Vector::new(vec![
             Edn::Bool(true),
-            Edn::Key(":b".to_string()),
-            Edn::Str("test".to_string()),
+            Edn::Key(":b".into()),
+            Edn::Str("test".into()),
         ]));
         let val = &edn[Edn::UInt(1)];
 
@@ -949,11 +1374,11 @@ mod test {
 
     #[test]
     fn get_list_at() {
-        let expected = &Edn::Str("test".to_string());
-        let edn = Edn::Vector(Vector(vec![
+        let expected = &Edn::Str("test".into());
+        let edn = Edn::Vector(Vector::new(vec![
             Edn::Bool(true),
-            Edn::Key(":b".to_string()),
-            Edn::Str("test".to_string()),
+            Edn::Key(":b".into()),
+            Edn::Str("test".into()),
         ]));
         let val = &edn[Edn::Int(2)];
 
@@ -962,13 +1387,13 @@ mod test {
 
     #[test]
     fn get_map() {
-        let expected = &Edn::Key(":val".to_string());
+        let expected = &Edn::Key(":val".into());
         let map = Edn::Map(Map::new(map! {
-            ":key".to_string() => Edn::Key(":val".to_string()),
-            "1".to_string() => Edn::Key(":value".to_string())
+            ":key".to_string() => Edn::Key(":val".into()),
+            "1".to_string() => Edn::Key(":value".into())
         }));
 
-        let val = &map[Edn::Key(":key".to_owned())];
+        let val = &map[Edn::Key(":key".into())];
         assert_eq!(expected, val);
     }
 }
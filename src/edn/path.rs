@@ -0,0 +1,315 @@
+use alloc::collections::btree_map::Entry;
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::ToString;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+
+use crate::edn::{Edn, Error, List, Map, Vector};
+
+/// `Path` is a sequence of keys and indices leading into nested `Edn`, like Clojure's `get-in`.
+///
+/// Map steps are looked up by their printed form, so `:name` finds the key `:name` and `"name"`
+/// finds the string key `"name"`. Vector and list steps are non-negative integers.
+/// ```rust
+/// use edn_rs::edn::{Edn, Path};
+/// use std::str::FromStr;
+///
+/// let path = Path::from_str("[:people 0 :name]").unwrap();
+/// assert_eq!(path, Path::from(vec![Edn::Key(":people".into()), Edn::UInt(0), Edn::Key(":name".into())]));
+/// assert_eq!(path.to_string(), "[:people 0 :name]");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Path(Vec<Edn>);
+
+impl Path {
+    #[must_use]
+    pub const fn new(steps: Vec<Edn>) -> Self {
+        Self(steps)
+    }
+
+    pub fn push(&mut self, step: impl Into<Edn>) {
+        self.0.push(step.into());
+    }
+
+    #[must_use]
+    pub fn steps(&self) -> &[Edn] {
+        &self.0
+    }
+
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl From<Vec<Edn>> for Path {
+    fn from(steps: Vec<Edn>) -> Self {
+        Self(steps)
+    }
+}
+
+impl<T: Clone + Into<Edn>> From<&[T]> for Path {
+    fn from(steps: &[T]) -> Self {
+        Self(steps.iter().cloned().map(Into::into).collect())
+    }
+}
+
+impl<T: Into<Edn>, const N: usize> From<[T; N]> for Path {
+    fn from(steps: [T; N]) -> Self {
+        Self(steps.into_iter().map(Into::into).collect())
+    }
+}
+
+impl TryFrom<&Edn> for Path {
+    type Error = Error;
+
+    fn try_from(edn: &Edn) -> Result<Self, Error> {
+        match edn {
+            Edn::Vector(v) => Ok(Self(v.0.to_vec())),
+            Edn::List(l) => Ok(Self(l.0.to_vec())),
+            _ => Err(Error::Type {
+                expected: "vector",
+                found: edn.type_name(),
+            }),
+        }
+    }
+}
+
+impl FromStr for Path {
+    type Err = Error;
+
+    /// Parses an EDN vector or list of steps, like `[:people 0 :name]`.
+    fn from_str(s: &str) -> Result<Self, Error> {
+        Self::try_from(&Edn::from_str(s)?)
+    }
+}
+
+impl fmt::Display for Path {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&Vector::new(self.0.clone()), f)
+    }
+}
+
+fn at(steps: &[Edn]) -> Path {
+    Path(steps.to_vec())
+}
+
+fn index(step: &Edn) -> Option<usize> {
+    match step {
+        Edn::UInt(u) => usize::try_from(*u).ok(),
+        Edn::Int(i) => usize::try_from(*i).ok(),
+        _ => None,
+    }
+}
+
+fn not_found(path: &Path, depth: usize) -> Error {
+    Error::Path(format!(
+        "{} not found at {}",
+        path.0[depth],
+        at(&path.0[..depth])
+    ))
+}
+
+fn not_navigable(found: &str, path: &Path, depth: usize) -> Error {
+    Error::Path(format!(
+        "cannot look up {} in {} at {}",
+        path.0[depth],
+        found,
+        at(&path.0[..depth])
+    ))
+}
+
+fn step_into<'e>(edn: &'e Edn, path: &Path, depth: usize) -> Result<&'e Edn, Error> {
+    let step = &path.0[depth];
+    match edn {
+        Edn::Map(m) => m.0.get(step.to_string().as_str()),
+        Edn::Vector(v) => index(step).and_then(|i| v.0.get(i)),
+        Edn::List(l) => index(step).and_then(|i| l.0.get(i)),
+        Edn::Set(s) => s.0.get(step),
+        _ => return Err(not_navigable(edn.type_name(), path, depth)),
+    }
+    .ok_or_else(|| not_found(path, depth))
+}
+
+fn step_into_mut<'e>(edn: &'e mut Edn, path: &Path, depth: usize) -> Result<&'e mut Edn, Error> {
+    let step = &path.0[depth];
+    let found = match edn {
+        Edn::Map(m) => m.0.get_mut(step.to_string().as_str()),
+        Edn::Vector(v) => index(step).and_then(|i| v.0.get_mut(i)),
+        Edn::List(l) => index(step).and_then(|i| l.0.get_mut(i)),
+        _ => return Err(not_navigable(edn.type_name(), path, depth)),
+    };
+    found.ok_or_else(|| not_found(path, depth))
+}
+
+pub fn get_in<'e>(edn: &'e Edn, path: &Path) -> Result<&'e Edn, Error> {
+    (0..path.len()).try_fold(edn, |edn, depth| step_into(edn, path, depth))
+}
+
+pub fn get_in_mut<'e>(edn: &'e mut Edn, path: &Path) -> Result<&'e mut Edn, Error> {
+    (0..path.len()).try_fold(edn, |edn, depth| step_into_mut(edn, path, depth))
+}
+
+// Builds the nested maps and vectors that `steps` leads through, with `leaf` at the end.
+// Integer steps create vectors, which can only start at index 0.
+fn build(path: &Path, depth: usize, leaf: impl FnOnce() -> Edn) -> Result<Edn, Error> {
+    let steps = &path.0[depth..];
+    if let Some(offset) = steps.iter().position(|s| index(s).is_some_and(|i| i > 0)) {
+        return Err(Error::Path(format!(
+            "cannot create index {} at {}",
+            steps[offset],
+            at(&path.0[..depth + offset])
+        )));
+    }
+    Ok(steps.iter().rev().fold(leaf(), |value, step| {
+        if index(step).is_some() {
+            Edn::Vector(Vector::new(vec![value]))
+        } else {
+            Edn::Map(Map::new(BTreeMap::from([(step.to_string(), value)])))
+        }
+    }))
+}
+
+pub fn update_in(edn: &mut Edn, path: &Path, f: impl FnOnce(Edn) -> Edn) -> Result<(), Error> {
+    let mut current = edn;
+    for depth in 0..path.len() {
+        let step = &path.0[depth];
+        let found = current.type_name();
+        current = match current {
+            Edn::Nil => {
+                *current = build(path, depth, || f(Edn::Nil))?;
+                return Ok(());
+            }
+            Edn::Map(m) => match m.0.entry(step.to_string().into()) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => {
+                    entry.insert(build(path, depth + 1, || f(Edn::Nil))?);
+                    return Ok(());
+                }
+            },
+            Edn::Vector(Vector(v)) | Edn::List(List(v)) => match index(step) {
+                Some(i) if i < v.len() => &mut v[i],
+                Some(i) if i == v.len() => {
+                    v.push(build(path, depth + 1, || f(Edn::Nil))?);
+                    return Ok(());
+                }
+                Some(_) => return Err(not_found(path, depth)),
+                None => return Err(not_navigable(found, path, depth)),
+            },
+            _ => return Err(not_navigable(found, path, depth)),
+        };
+    }
+    *current = f(core::mem::replace(current, Edn::Nil));
+    Ok(())
+}
+
+pub fn dissoc_in(edn: &mut Edn, path: &Path) -> Result<Edn, Error> {
+    let Some(depth) = path.len().checked_sub(1) else {
+        return Err(Error::Path("cannot dissociate an empty path".to_string()));
+    };
+    let parent = get_in_mut(edn, &at(&path.0[..depth]))?;
+    let step = &path.0[depth];
+    let removed = match parent {
+        Edn::Map(m) => m.0.remove(step.to_string().as_str()),
+        Edn::Vector(Vector(v)) | Edn::List(List(v)) => {
+            index(step).filter(|i| *i < v.len()).map(|i| v.remove(i))
+        }
+        Edn::Set(s) => s.0.take(step),
+        _ => return Err(not_navigable(parent.type_name(), path, depth)),
+    };
+    removed.ok_or_else(|| not_found(path, depth))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn people() -> Edn {
+        Edn::from_str("{:people [{:name \"Ana\"} {:name \"Bo\" :tags #{:admin}}]}").unwrap()
+    }
+
+    fn path(s: &str) -> Path {
+        Path::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn get_in_walks_maps_vectors_and_sets() {
+        let edn = people();
+
+        assert_eq!(
+            get_in(&edn, &path("[:people 1 :name]")),
+            Ok(&Edn::Str("Bo".into()))
+        );
+        assert_eq!(
+            get_in(&edn, &path("[:people 1 :tags :admin]")),
+            Ok(&Edn::Key(":admin".into()))
+        );
+        assert_eq!(get_in(&edn, &Path::default()), Ok(&edn));
+        assert_eq!(
+            get_in(&edn, &path("[:people 2 :name]")),
+            Err(Error::Path("2 not found at [:people]".to_string()))
+        );
+        assert_eq!(
+            get_in(&edn, &path("[:people 0 :name 0]")),
+            Err(Error::Path(
+                "cannot look up 0 in string at [:people 0 :name]".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn update_in_creates_missing_maps_and_vectors() {
+        let mut edn = Edn::Nil;
+        update_in(&mut edn, &path("[:a 0 :b]"), |_| Edn::UInt(1)).unwrap();
+        assert_eq!(edn, Edn::from_str("{:a [{:b 1}]}").unwrap());
+
+        update_in(&mut edn, &path("[:a 0 :b]"), |n| {
+            Edn::UInt(n.to_uint().unwrap() + 1)
+        })
+        .unwrap();
+        update_in(&mut edn, &path("[:a 1]"), |_| Edn::Bool(true)).unwrap();
+        assert_eq!(edn, Edn::from_str("{:a [{:b 2} true]}").unwrap());
+    }
+
+    #[test]
+    fn failed_updates_leave_the_value_untouched() {
+        let mut edn = people();
+
+        assert_eq!(
+            update_in(&mut edn, &path("[:people 5]"), |_| Edn::Nil),
+            Err(Error::Path("5 not found at [:people]".to_string()))
+        );
+        assert_eq!(
+            update_in(&mut edn, &path("[:other 3]"), |_| Edn::Nil),
+            Err(Error::Path("cannot create index 3 at [:other]".to_string()))
+        );
+        assert_eq!(edn, people());
+    }
+
+    #[test]
+    fn dissoc_in_removes_and_returns_the_value() {
+        let mut edn = people();
+
+        assert_eq!(
+            dissoc_in(&mut edn, &path("[:people 1 :tags :admin]")),
+            Ok(Edn::Key(":admin".into()))
+        );
+        assert_eq!(
+            dissoc_in(&mut edn, &path("[:people 0]")),
+            Ok(Edn::from_str("{:name \"Ana\"}").unwrap())
+        );
+        assert_eq!(
+            edn,
+            Edn::from_str("{:people [{:name \"Bo\" :tags #{}}]}").unwrap()
+        );
+        assert!(dissoc_in(&mut edn, &Path::default()).is_err());
+    }
+}
@@ -0,0 +1,137 @@
+#[cfg(not(feature = "persistent"))]
+use alloc::boxed::Box;
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::{Deref, DerefMut};
+
+/// Reference counted pointer for shared `Edn` data. Targets without atomic pointers, like
+/// `thumbv6m-none-eabi`, fall back to `Rc`.
+#[cfg(target_has_atomic = "ptr")]
+pub type RefCounted<T> = alloc::sync::Arc<T>;
+#[cfg(not(target_has_atomic = "ptr"))]
+pub type RefCounted<T> = alloc::rc::Rc<T>;
+
+/// Storage for the contents of `Vector`, `List`, `Set` and `Map`.
+///
+/// With feature `persistent` the contents are reference counted and copied on write, so cloning an
+/// `Edn` tree is O(1). Mutating a nested value copies each whole collection along its path that is
+/// still shared, which is O(width) per level, while everything off the path stays shared.
+/// Otherwise `Shared` owns its contents in a `Box`. Either way it is one pointer wide, which keeps
+/// collections from setting the size of every `Edn`.
+#[derive(Clone, Default)]
+pub struct Shared<T>(
+    #[cfg(feature = "persistent")] RefCounted<T>,
+    #[cfg(not(feature = "persistent"))] Box<T>,
+);
+
+#[cfg(feature = "persistent")]
+impl<T: Clone> Shared<T> {
+    pub fn new(t: T) -> Self {
+        Self(RefCounted::new(t))
+    }
+
+    pub fn into_inner(self) -> T {
+        RefCounted::unwrap_or_clone(self.0)
+    }
+}
+
+#[cfg(not(feature = "persistent"))]
+impl<T> Shared<T> {
+    pub fn new(t: T) -> Self {
+        Self(Box::new(t))
+    }
+
+    pub fn into_inner(self) -> T {
+        *self.0
+    }
+}
+
+impl<T> Deref for Shared<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+#[cfg(feature = "persistent")]
+impl<T: Clone> DerefMut for Shared<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        RefCounted::make_mut(&mut self.0)
+    }
+}
+
+#[cfg(not(feature = "persistent"))]
+impl<T> DerefMut for Shared<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Shared<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+impl<T: PartialEq> PartialEq for Shared<T> {
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+impl<T: Eq> Eq for Shared<T> {}
+
+impl<T: PartialOrd> PartialOrd for Shared<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        (**self).partial_cmp(&**other)
+    }
+}
+
+impl<T: Ord> Ord for Shared<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (**self).cmp(&**other)
+    }
+}
+
+impl<T: Hash> Hash for Shared<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (**self).hash(state);
+    }
+}
+
+#[cfg(all(test, feature = "persistent"))]
+mod test {
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    use super::*;
+
+    #[test]
+    fn clones_share_until_written() {
+        let original = Shared::new(vec![1, 2, 3]);
+        let mut copy = original.clone();
+        assert!(RefCounted::ptr_eq(&original.0, &copy.0));
+
+        copy.push(4);
+        assert!(!RefCounted::ptr_eq(&original.0, &copy.0));
+        assert_eq!(*original, vec![1, 2, 3]);
+        assert_eq!(*copy, vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn nested_writes_copy_only_the_path() {
+        let untouched = Shared::new(vec![0]);
+        let touched = Shared::new(vec![1]);
+        let root = Shared::new(vec![untouched, touched]);
+
+        let mut updated = root.clone();
+        updated[1].push(2);
+
+        assert!(RefCounted::ptr_eq(&root[0].0, &updated[0].0));
+        assert!(!RefCounted::ptr_eq(&root[1].0, &updated[1].0));
+        assert_eq!(root[1].clone().into_inner(), vec![1]);
+        assert_eq!(updated[1].clone().into_inner(), Vec::from([1, 2]));
+    }
+}
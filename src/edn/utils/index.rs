@@ -1,70 +1,133 @@
-use alloc::borrow::ToOwned;
 use alloc::string::{String, ToString};
+use alloc::vec::Vec;
 use core::convert::TryFrom;
-use core::{fmt, ops};
+use core::ops;
 
-use crate::edn::{Edn, Map};
+use crate::edn::{Edn, List, Map, Vector};
 
 /// This is a Copy of [`Serde_json::index`](https://docs.serde.rs/src/serde_json/value/index.rs.html)
+///
+/// Integers index vectors and lists, counting from the end when negative, and look up integer
+/// keys in maps. Strings look up map keys by their printed form, like `":a"`. An `Edn` index looks
+/// up its printed form in maps, its value in sets, and its position in vectors and lists when it is
+/// an integer.
+///
+/// `index_or_insert` inserts missing map keys as `nil`, and appends a `nil` to a vector or list
+/// indexed one past its end. `nil` is replaced by an empty map, or an empty vector for integer
+/// indices. Anything else, like an index further past the end or a value that can't be indexed
+/// that way, returns `None` and leaves the value as it was.
 pub trait Index: private::Sealed {
     fn index_into<'v>(&self, v: &'v Edn) -> Option<&'v Edn>;
 
     fn index_into_mut<'v>(&self, v: &'v mut Edn) -> Option<&'v mut Edn>;
 
-    fn index_or_insert<'v>(&self, v: &'v mut Edn) -> &'v mut Edn;
+    fn index_or_insert<'v>(&self, v: &'v mut Edn) -> Option<&'v mut Edn>;
 }
 
-impl Index for usize {
-    fn index_into<'v>(&self, v: &'v Edn) -> Option<&'v Edn> {
-        return match *v {
-            Edn::Vector(ref vec) => vec.0.get(*self),
-            Edn::List(ref vec) => vec.0.get(*self),
-            Edn::Map(ref map) => map.0.get(&self.to_string()),
-            _ => None,
-        };
+// Resolves a possibly negative index against a sequence of `len` elements.
+fn position(index: i128, len: usize) -> Option<usize> {
+    if index >= 0 {
+        usize::try_from(index).ok()
+    } else {
+        len.checked_sub(usize::try_from(index.unsigned_abs()).ok()?)
     }
-    fn index_into_mut<'v>(&self, v: &'v mut Edn) -> Option<&'v mut Edn> {
-        return match *v {
-            Edn::Vector(ref mut vec) => vec.0.get_mut(*self),
-            Edn::List(ref mut vec) => vec.0.get_mut(*self),
-            Edn::Map(ref mut map) => map.0.get_mut(&self.to_string()),
-            _ => None,
-        };
-    }
-    fn index_or_insert<'v>(&self, v: &'v mut Edn) -> &'v mut Edn {
-        match *v {
-            Edn::Vector(ref mut vec) => {
-                let len = vec.0.len();
-                vec.0.get_mut(*self).unwrap_or_else(|| {
-                    panic!("cannot access index {self} of EDN array of length {len}")
-                })
-            }
-            _ => panic!("cannot access index {} of EDN {}", self, Type(v)),
+}
+
+// The element at `index`, or a `nil` pushed at the end when `index` is exactly one past it.
+fn get_or_push(vec: &mut Vec<Edn>, index: i128) -> Option<&mut Edn> {
+    let i = position(index, vec.len())?;
+    if i == vec.len() && index >= 0 {
+        vec.push(Edn::Nil);
+    }
+    vec.get_mut(i)
+}
+
+fn get_int(v: &Edn, index: i128) -> Option<&Edn> {
+    match v {
+        Edn::Vector(vec) => vec.0.get(position(index, vec.0.len())?),
+        Edn::List(list) => list.0.get(position(index, list.0.len())?),
+        Edn::Map(map) => map.0.get(index.to_string().as_str()),
+        _ => None,
+    }
+}
+
+fn get_int_mut(v: &mut Edn, index: i128) -> Option<&mut Edn> {
+    match v {
+        Edn::Vector(Vector(vec)) | Edn::List(List(vec)) => {
+            let i = position(index, vec.len())?;
+            vec.get_mut(i)
+        }
+        Edn::Map(map) => map.0.get_mut(index.to_string().as_str()),
+        _ => None,
+    }
+}
+
+fn int_or_insert(v: &mut Edn, index: i128) -> Option<&mut Edn> {
+    match v {
+        Edn::Vector(Vector(vec)) | Edn::List(List(vec)) => get_or_push(vec, index),
+        Edn::Map(map) => Some(map.0.entry(index.to_string().into()).or_insert(Edn::Nil)),
+        Edn::Nil if index == 0 => {
+            *v = Edn::Vector(Vector::empty());
+            int_or_insert(v, index)
+        }
+        _ => None,
+    }
+}
+
+fn get_key<'v>(v: &'v Edn, key: &str) -> Option<&'v Edn> {
+    match v {
+        Edn::Map(map) => map.0.get(key),
+        _ => None,
+    }
+}
+
+fn get_key_mut<'v>(v: &'v mut Edn, key: &str) -> Option<&'v mut Edn> {
+    match v {
+        Edn::Map(map) => map.0.get_mut(key),
+        _ => None,
+    }
+}
+
+fn key_or_insert(v: &mut Edn, key: String) -> Option<&mut Edn> {
+    match v {
+        Edn::Map(map) => Some(map.0.entry(key.into()).or_insert(Edn::Nil)),
+        Edn::Nil => {
+            *v = Edn::Map(Map::empty());
+            key_or_insert(v, key)
         }
+        _ => None,
     }
 }
 
+macro_rules! impl_int_index {
+    ( $( $name:ty ),+ ) => {
+        $(
+            impl Index for $name {
+                fn index_into<'v>(&self, v: &'v Edn) -> Option<&'v Edn> {
+                    get_int(v, i128::try_from(*self).ok()?)
+                }
+                fn index_into_mut<'v>(&self, v: &'v mut Edn) -> Option<&'v mut Edn> {
+                    get_int_mut(v, i128::try_from(*self).ok()?)
+                }
+                fn index_or_insert<'v>(&self, v: &'v mut Edn) -> Option<&'v mut Edn> {
+                    int_or_insert(v, i128::try_from(*self).ok()?)
+                }
+            }
+        )+
+    };
+}
+
+impl_int_index!(usize, i32, i64, isize);
+
 impl Index for str {
     fn index_into<'v>(&self, v: &'v Edn) -> Option<&'v Edn> {
-        match *v {
-            Edn::Map(ref map) => map.0.get(self),
-            _ => None,
-        }
+        get_key(v, self)
     }
     fn index_into_mut<'v>(&self, v: &'v mut Edn) -> Option<&'v mut Edn> {
-        match *v {
-            Edn::Map(ref mut map) => map.0.get_mut(self),
-            _ => None,
-        }
+        get_key_mut(v, self)
     }
-    fn index_or_insert<'v>(&self, v: &'v mut Edn) -> &'v mut Edn {
-        if *v == Edn::Nil {
-            *v = Edn::Map(Map::new(alloc::collections::BTreeMap::new()));
-        }
-        match *v {
-            Edn::Map(ref mut map) => map.0.entry(self.to_owned()).or_insert(Edn::Nil),
-            _ => panic!("cannot access key {:?} in EDN {}", self, Type(v)),
-        }
+    fn index_or_insert<'v>(&self, v: &'v mut Edn) -> Option<&'v mut Edn> {
+        key_or_insert(v, self.to_string())
     }
 }
 
@@ -75,31 +138,38 @@ impl Index for String {
     fn index_into_mut<'v>(&self, v: &'v mut Edn) -> Option<&'v mut Edn> {
         self[..].index_into_mut(v)
     }
-    fn index_or_insert<'v>(&self, v: &'v mut Edn) -> &'v mut Edn {
+    fn index_or_insert<'v>(&self, v: &'v mut Edn) -> Option<&'v mut Edn> {
         self[..].index_or_insert(v)
     }
 }
 
+fn as_index(edn: &Edn) -> Option<i128> {
+    match edn {
+        Edn::Int(i) => Some(i128::from(*i)),
+        Edn::UInt(u) => Some(i128::from(*u)),
+        _ => None,
+    }
+}
+
 impl Index for Edn {
     fn index_into<'v>(&self, v: &'v Edn) -> Option<&'v Edn> {
-        let key = self.to_string();
-        let index = self.to_uint();
-
-        match (v, index) {
-            (Self::Map(ref map), _) => map.0.get(&key),
-            (Self::List(_) | Self::Vector(_), Some(idx)) => {
-                // A panic is expected behavior when trying to index beyond usize
-                let idx = usize::try_from(idx).unwrap();
-                idx.index_into(v)
-            }
-            _ => None,
+        match (v, as_index(self)) {
+            (Self::Set(set), _) => set.0.get(self),
+            (_, Some(index)) => get_int(v, index),
+            (_, None) => get_key(v, &self.to_string()),
         }
     }
-    fn index_into_mut<'v>(&self, _: &'v mut Edn) -> Option<&'v mut Edn> {
-        panic!("index_into_mut not implemented for edn")
+    fn index_into_mut<'v>(&self, v: &'v mut Edn) -> Option<&'v mut Edn> {
+        match as_index(self) {
+            Some(index) => get_int_mut(v, index),
+            None => get_key_mut(v, &self.to_string()),
+        }
     }
-    fn index_or_insert<'v>(&self, _: &'v mut Edn) -> &'v mut Edn {
-        panic!("index_or_insert not implemented for edn")
+    fn index_or_insert<'v>(&self, v: &'v mut Edn) -> Option<&'v mut Edn> {
+        match as_index(self) {
+            Some(index) => int_or_insert(v, index),
+            None => key_or_insert(v, self.to_string()),
+        }
     }
 }
 
@@ -113,7 +183,7 @@ where
     fn index_into_mut<'v>(&self, v: &'v mut Edn) -> Option<&'v mut Edn> {
         (**self).index_into_mut(v)
     }
-    fn index_or_insert<'v>(&self, v: &'v mut Edn) -> &'v mut Edn {
+    fn index_or_insert<'v>(&self, v: &'v mut Edn) -> Option<&'v mut Edn> {
         (**self).index_or_insert(v)
     }
 }
@@ -126,35 +196,14 @@ mod private {
 
     pub trait Sealed {}
     impl Sealed for usize {}
+    impl Sealed for i32 {}
+    impl Sealed for i64 {}
+    impl Sealed for isize {}
     impl Sealed for str {}
     impl Sealed for String {}
     impl Sealed for Edn {}
     impl<'a, T> Sealed for &'a T where T: ?Sized + Sealed {}
 }
-struct Type<'a>(&'a Edn);
-
-impl<'a> fmt::Display for Type<'a> {
-    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match *self.0 {
-            Edn::Empty => formatter.write_str("empty"),
-            Edn::Nil => formatter.write_str("null"),
-            Edn::Bool(_) => formatter.write_str("boolean"),
-            Edn::Int(_) | Edn::UInt(_) => formatter.write_str("integer"),
-            Edn::Str(_) => formatter.write_str("string"),
-            Edn::Vector(_) => formatter.write_str("vector"),
-            #[cfg(feature = "sets")]
-            Edn::Set(_) => formatter.write_str("set"),
-            Edn::List(_) => formatter.write_str("list"),
-            Edn::Map(_) => formatter.write_str("map"),
-            Edn::Key(_) => formatter.write_str("key"),
-            Edn::Char(_) => formatter.write_str("char"),
-            Edn::Symbol(_) => formatter.write_str("symbol"),
-            Edn::Double(_) => formatter.write_str("double"),
-            Edn::Rational(_) => formatter.write_str("rational"),
-            Edn::Tagged(_, _) => formatter.write_str("tagged-element"),
-        }
-    }
-}
 
 impl<I> ops::Index<I> for Edn
 where
@@ -167,11 +216,97 @@ where
     }
 }
 
+/// # Panics
+///
+/// If `index_or_insert` can't reach `index`, like an index more than one past the end of a
+/// vector, or a key into a value that isn't a map or `nil`. `Edn::get_or_insert` returns `None`
+/// instead.
 impl<I> ops::IndexMut<I> for Edn
 where
     I: Index,
 {
     fn index_mut(&mut self, index: I) -> &mut Self {
-        index.index_or_insert(self)
+        let type_name = self.type_name();
+        index
+            .index_or_insert(self)
+            .unwrap_or_else(|| panic!("cannot index or insert into EDN {type_name} at that index"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use core::str::FromStr;
+
+    use super::*;
+
+    fn edn(s: &str) -> Edn {
+        Edn::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn negative_indices_count_from_the_end() {
+        let data = edn("[1 (2 3) {1 :one 2 :two}]");
+
+        assert_eq!(data[-1], edn("{1 :one 2 :two}"));
+        assert_eq!(data[-2][-1], Edn::UInt(3));
+        assert_eq!(data[-1][1], edn(":one"));
+        assert_eq!(data.get(-4), None);
+        assert_eq!(data.get(3), None);
+    }
+
+    #[test]
+    fn edn_keys_look_up_maps_sets_and_sequences() {
+        let mut data = edn("{:a #{:x 2} \"s\" [10 20]}");
+
+        assert_eq!(data[edn(":a")][edn(":x")], edn(":x"));
+        assert_eq!(data[edn(":a")][Edn::UInt(2)], Edn::UInt(2));
+        assert_eq!(data[edn(":a")].get(edn(":y")), None);
+        assert_eq!(data[edn("\"s\"")][Edn::Int(-1)], Edn::UInt(20));
+
+        *data
+            .get_mut(edn("\"s\""))
+            .unwrap()
+            .get_mut(Edn::UInt(0))
+            .unwrap() = Edn::UInt(11);
+        data[edn(":b")][edn(":c")] = Edn::Bool(true);
+        assert_eq!(data, edn("{:a #{:x 2} \"s\" [11 20] :b {:c true}}"));
+    }
+
+    #[test]
+    fn index_or_insert_appends_and_fills_nil() {
+        let mut data = Edn::Nil;
+        data[0] = Edn::UInt(0);
+        data[1] = Edn::UInt(1);
+        data[-1] = Edn::UInt(2);
+        assert_eq!(data, edn("[0 2]"));
+
+        let mut data = Edn::Nil;
+        data[":k"] = Edn::UInt(1);
+        assert_eq!(data, edn("{:k 1}"));
+        data[":v"][0] = Edn::UInt(1);
+        assert_eq!(data, edn("{:k 1 :v [1]}"));
+    }
+
+    #[test]
+    fn index_or_insert_leaves_values_it_cannot_reach() {
+        let mut data = edn("{:k 1 :s #{1} :v [0]}");
+
+        assert_eq!(data.get_or_insert(":k").unwrap().get_or_insert(":a"), None);
+        assert_eq!(data[":s"].get_or_insert(0), None);
+        assert_eq!(data[":s"].get_or_insert(edn(":a")), None);
+        assert_eq!(data[":v"].get_or_insert(2), None);
+        assert_eq!(data[":v"].get_or_insert(-2), None);
+        assert_eq!(data[":v"].get_or_insert(usize::MAX), None);
+        assert_eq!(data[":v"].get_or_insert(Edn::UInt(u64::MAX)), None);
+        assert_eq!(data[":v"].get_or_insert(i64::MIN), None);
+        assert_eq!(Edn::Nil.get_or_insert(3), None);
+        assert_eq!(data, edn("{:k 1 :s #{1} :v [0]}"));
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot index or insert into EDN vector")]
+    fn index_mut_panics_past_the_end() {
+        let mut data = edn("[0]");
+        data[5] = Edn::Nil;
     }
 }
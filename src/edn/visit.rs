@@ -0,0 +1,417 @@
+//! Generic traversal of `Edn` trees.
+//!
+//! `Visitor` and `VisitorMut` have one method per kind of value, and each method recurses into
+//! children by default through the matching `walk_*` function. Override the methods you care about
+//! and call the `walk_*` function from them to keep recursing.
+//!
+//! `fold`, `prewalk` and `postwalk` cover the common cases with a single closure.
+//! ```rust
+//! use edn_rs::edn::visit::postwalk;
+//! use edn_rs::Edn;
+//! use std::str::FromStr;
+//!
+//! let edn = Edn::from_str("{:user/name \"Ana\" :user/roles [:user/admin]}").unwrap();
+//! let renamed = postwalk(edn, |e| match e {
+//!     Edn::Key(k) => Edn::Key(k.replace(":user/", ":person/").into()),
+//!     e => e,
+//! });
+//!
+//! assert_eq!(renamed, Edn::from_str("{:person/name \"Ana\" :person/roles [:person/admin]}").unwrap());
+//! ```
+
+use alloc::string::ToString;
+use alloc::vec::Vec;
+
+use crate::edn::{Atom, Edn, List, Map, Set, Tagged, Vector};
+
+/// Visits an `Edn` tree by reference.
+///
+/// Map keys are read back into `Edn` for `visit_map_entry`, which visits them like any other value.
+/// ```rust
+/// use edn_rs::edn::visit::{walk_tagged, Visitor};
+/// use edn_rs::edn::{Atom, Edn, Tagged};
+/// use std::str::FromStr;
+///
+/// #[derive(Default)]
+/// struct Keywords(Vec<String>);
+///
+/// impl Visitor for Keywords {
+///     fn visit_key(&mut self, key: &Atom) {
+///         self.0.push(key.to_string());
+///     }
+///
+///     // Skips everything inside `#secret` values.
+///     fn visit_tagged(&mut self, tagged: &Tagged) {
+///         if tagged.tag() != "secret" {
+///             walk_tagged(self, tagged);
+///         }
+///     }
+/// }
+///
+/// let mut keywords = Keywords::default();
+/// keywords.visit_edn(&Edn::from_str("[:a #{:b} {:c 1} #secret :d #ok :e]").unwrap());
+/// assert_eq!(keywords.0, vec![":a", ":b", ":c", ":e"]);
+/// ```
+pub trait Visitor {
+    fn visit_edn(&mut self, edn: &Edn) {
+        walk_edn(self, edn);
+    }
+
+    fn visit_vector(&mut self, vector: &Vector) {
+        walk_vector(self, vector);
+    }
+
+    fn visit_list(&mut self, list: &List) {
+        walk_list(self, list);
+    }
+
+    fn visit_set(&mut self, set: &Set) {
+        walk_set(self, set);
+    }
+
+    fn visit_map(&mut self, map: &Map) {
+        walk_map(self, map);
+    }
+
+    fn visit_map_entry(&mut self, key: &Edn, value: &Edn) {
+        self.visit_edn(key);
+        self.visit_edn(value);
+    }
+
+    fn visit_tagged(&mut self, tagged: &Tagged) {
+        walk_tagged(self, tagged);
+    }
+
+    fn visit_key(&mut self, _key: &Atom) {}
+
+    fn visit_symbol(&mut self, _symbol: &Atom) {}
+
+    /// Called for strings, numbers, characters, booleans and `nil`.
+    fn visit_scalar(&mut self, _edn: &Edn) {}
+}
+
+pub fn walk_edn<V: Visitor + ?Sized>(visitor: &mut V, edn: &Edn) {
+    match edn {
+        Edn::Vector(v) => visitor.visit_vector(v),
+        Edn::List(l) => visitor.visit_list(l),
+        Edn::Set(s) => visitor.visit_set(s),
+        Edn::Map(m) => visitor.visit_map(m),
+        Edn::Tagged(t) => visitor.visit_tagged(t),
+        Edn::Key(k) => visitor.visit_key(k),
+        Edn::Symbol(s) => visitor.visit_symbol(s),
+        scalar => visitor.visit_scalar(scalar),
+    }
+}
+
+pub fn walk_vector<V: Visitor + ?Sized>(visitor: &mut V, vector: &Vector) {
+    vector.0.iter().for_each(|e| visitor.visit_edn(e));
+}
+
+pub fn walk_list<V: Visitor + ?Sized>(visitor: &mut V, list: &List) {
+    list.0.iter().for_each(|e| visitor.visit_edn(e));
+}
+
+pub fn walk_set<V: Visitor + ?Sized>(visitor: &mut V, set: &Set) {
+    set.0.iter().for_each(|e| visitor.visit_edn(e));
+}
+
+pub fn walk_map<V: Visitor + ?Sized>(visitor: &mut V, map: &Map) {
+    map.0
+        .iter()
+        .for_each(|(k, v)| visitor.visit_map_entry(&Edn::read_key(k), v));
+}
+
+pub fn walk_tagged<V: Visitor + ?Sized>(visitor: &mut V, tagged: &Tagged) {
+    visitor.visit_edn(tagged.value());
+}
+
+/// Visits an `Edn` tree by mutable reference.
+///
+/// Map keys are read back into `Edn` for `visit_map_entry_mut`, so they can be rewritten too. Sets
+/// are rebuilt after their elements are visited, and maps only move the entries whose key changed.
+pub trait VisitorMut {
+    fn visit_edn_mut(&mut self, edn: &mut Edn) {
+        walk_edn_mut(self, edn);
+    }
+
+    fn visit_vector_mut(&mut self, vector: &mut Vector) {
+        walk_vector_mut(self, vector);
+    }
+
+    fn visit_list_mut(&mut self, list: &mut List) {
+        walk_list_mut(self, list);
+    }
+
+    fn visit_set_mut(&mut self, set: &mut Set) {
+        walk_set_mut(self, set);
+    }
+
+    fn visit_map_mut(&mut self, map: &mut Map) {
+        walk_map_mut(self, map);
+    }
+
+    fn visit_map_entry_mut(&mut self, key: &mut Edn, value: &mut Edn) {
+        self.visit_edn_mut(key);
+        self.visit_edn_mut(value);
+    }
+
+    fn visit_tagged_mut(&mut self, tagged: &mut Tagged) {
+        walk_tagged_mut(self, tagged);
+    }
+
+    fn visit_key_mut(&mut self, _key: &mut Atom) {}
+
+    fn visit_symbol_mut(&mut self, _symbol: &mut Atom) {}
+
+    /// Called for strings, numbers, characters, booleans and `nil`.
+    fn visit_scalar_mut(&mut self, _edn: &mut Edn) {}
+}
+
+pub fn walk_edn_mut<V: VisitorMut + ?Sized>(visitor: &mut V, edn: &mut Edn) {
+    match edn {
+        Edn::Vector(v) => visitor.visit_vector_mut(v),
+        Edn::List(l) => visitor.visit_list_mut(l),
+        Edn::Set(s) => visitor.visit_set_mut(s),
+        Edn::Map(m) => visitor.visit_map_mut(m),
+        Edn::Tagged(t) => visitor.visit_tagged_mut(t),
+        Edn::Key(k) => visitor.visit_key_mut(k),
+        Edn::Symbol(s) => visitor.visit_symbol_mut(s),
+        scalar => visitor.visit_scalar_mut(scalar),
+    }
+}
+
+pub fn walk_vector_mut<V: VisitorMut + ?Sized>(visitor: &mut V, vector: &mut Vector) {
+    vector.0.iter_mut().for_each(|e| visitor.visit_edn_mut(e));
+}
+
+pub fn walk_list_mut<V: VisitorMut + ?Sized>(visitor: &mut V, list: &mut List) {
+    list.0.iter_mut().for_each(|e| visitor.visit_edn_mut(e));
+}
+
+pub fn walk_set_mut<V: VisitorMut + ?Sized>(visitor: &mut V, set: &mut Set) {
+    *set.0 = core::mem::take(&mut *set.0)
+        .into_iter()
+        .map(|mut e| {
+            visitor.visit_edn_mut(&mut e);
+            e
+        })
+        .collect();
+}
+
+// Values are visited in place, and only entries whose key was rewritten are moved.
+pub fn walk_map_mut<V: VisitorMut + ?Sized>(visitor: &mut V, map: &mut Map) {
+    let mut rekeyed = Vec::new();
+    for (k, v) in map.0.iter_mut() {
+        let read = Edn::read_key(k);
+        let mut key = read.clone();
+        visitor.visit_map_entry_mut(&mut key, v);
+        if key != read {
+            rekeyed.push((k.clone(), key));
+        }
+    }
+    let moved = rekeyed
+        .into_iter()
+        .filter_map(|(old, key)| Some((key.to_string(), map.0.remove(&old)?)))
+        .collect::<Vec<_>>();
+    map.extend(moved);
+}
+
+pub fn walk_tagged_mut<V: VisitorMut + ?Sized>(visitor: &mut V, tagged: &mut Tagged) {
+    visitor.visit_edn_mut(tagged.value_mut());
+}
+
+fn fold_with<T>(edn: &Edn, acc: T, f: &mut dyn FnMut(T, &Edn) -> T) -> T {
+    let acc = f(acc, edn);
+    match edn {
+        Edn::Vector(v) => v.0.iter().fold(acc, |acc, e| fold_with(e, acc, f)),
+        Edn::List(l) => l.0.iter().fold(acc, |acc, e| fold_with(e, acc, f)),
+        Edn::Set(s) => s.0.iter().fold(acc, |acc, e| fold_with(e, acc, f)),
+        Edn::Map(m) => m.0.values().fold(acc, |acc, e| fold_with(e, acc, f)),
+        Edn::Tagged(t) => fold_with(t.value(), acc, f),
+        _ => acc,
+    }
+}
+
+/// Folds `f` over every value in `edn`, parents before their children. Map keys are skipped.
+/// ```rust
+/// use edn_rs::edn::visit::fold;
+/// use edn_rs::Edn;
+/// use std::str::FromStr;
+///
+/// let edn = Edn::from_str("{:a 1 :b [2 #{3}]}").unwrap();
+/// assert_eq!(fold(&edn, 0, |sum, e| sum + e.to_uint().unwrap_or(0)), 6);
+/// ```
+pub fn fold<T>(edn: &Edn, init: T, mut f: impl FnMut(T, &Edn) -> T) -> T {
+    fold_with(edn, init, &mut f)
+}
+
+// Applies `f` to each child of `edn`, including map keys, and rebuilds it.
+fn map_children(edn: Edn, f: &mut dyn FnMut(Edn) -> Edn) -> Edn {
+    match edn {
+        Edn::Vector(v) => Edn::Vector(Vector::new(v.to_vec().into_iter().map(f).collect())),
+        Edn::List(l) => Edn::List(List::new(l.to_vec().into_iter().map(f).collect())),
+        Edn::Set(s) => Edn::Set(Set::new(s.to_set().into_iter().map(f).collect())),
+        Edn::Map(m) => Edn::Map(
+            m.into_iter()
+                .map(|(k, v)| (f(Edn::read_key(&k)).to_string(), f(v)))
+                .collect(),
+        ),
+        Edn::Tagged(t) => {
+            let (tag, value) = t.into_parts();
+            Edn::Tagged(Tagged::new(tag, f(value)))
+        }
+        edn => edn,
+    }
+}
+
+fn prewalk_with(edn: Edn, f: &mut dyn FnMut(Edn) -> Edn) -> Edn {
+    let edn = f(edn);
+    map_children(edn, &mut |child| prewalk_with(child, f))
+}
+
+fn postwalk_with(edn: Edn, f: &mut dyn FnMut(Edn) -> Edn) -> Edn {
+    let edn = map_children(edn, &mut |child| postwalk_with(child, f));
+    f(edn)
+}
+
+/// Replaces every value in `edn` with `f` applied to it, parents first, then recurses into the
+/// children of what `f` returned. Map keys are walked too.
+/// ```rust
+/// use edn_rs::edn::visit::prewalk;
+/// use edn_rs::Edn;
+/// use std::str::FromStr;
+///
+/// let edn = Edn::from_str("[#inst \"2020-01-01\" #wrap [#wrap 1]]").unwrap();
+/// let untagged = prewalk(edn, |e| match e {
+///     Edn::Tagged(t) => t.into_parts().1,
+///     e => e,
+/// });
+///
+/// assert_eq!(untagged, Edn::from_str("[\"2020-01-01\" [1]]").unwrap());
+/// ```
+pub fn prewalk(edn: Edn, mut f: impl FnMut(Edn) -> Edn) -> Edn {
+    prewalk_with(edn, &mut f)
+}
+
+/// Replaces every value in `edn` with `f` applied to it, children first, so `f` sees parents
+/// already rebuilt from their transformed children. Map keys are walked too.
+pub fn postwalk(edn: Edn, mut f: impl FnMut(Edn) -> Edn) -> Edn {
+    postwalk_with(edn, &mut f)
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use core::str::FromStr;
+
+    use super::*;
+
+    fn edn(s: &str) -> Edn {
+        Edn::from_str(s).unwrap()
+    }
+
+    struct Upcase;
+
+    impl VisitorMut for Upcase {
+        fn visit_symbol_mut(&mut self, symbol: &mut Atom) {
+            *symbol = symbol.to_uppercase().into();
+        }
+
+        fn visit_scalar_mut(&mut self, edn: &mut Edn) {
+            if let Edn::Str(s) = edn {
+                *s = s.to_uppercase().into();
+            }
+        }
+    }
+
+    #[test]
+    fn visitor_mut_reaches_keys_sets_and_tags() {
+        let mut data = edn("{sym \"a\" :k #{b \"c\"} :t #tag (d)}");
+        Upcase.visit_edn_mut(&mut data);
+
+        assert_eq!(data, edn("{SYM \"A\" :k #{B \"C\"} :t #tag (D)}"));
+    }
+
+    struct Rename;
+
+    impl VisitorMut for Rename {
+        fn visit_key_mut(&mut self, key: &mut Atom) {
+            *key = key.replace(":old/", ":new/").into();
+        }
+    }
+
+    #[test]
+    fn visitor_mut_moves_only_renamed_keys() {
+        let mut data = edn("{:old/a 1 :user/favorite-color \"red\" \"b\" {:old/c 2}}");
+        let before = data.clone();
+        Upcase.visit_edn_mut(&mut data);
+        Rename.visit_edn_mut(&mut data);
+
+        assert_eq!(
+            data,
+            edn("{:new/a 1 :user/favorite-color \"RED\" \"B\" {:new/c 2}}")
+        );
+        let key = |edn: &Edn| {
+            edn.map_iter()
+                .unwrap()
+                .find(|(k, _)| k.starts_with(":user/"))
+                .unwrap()
+                .0
+                .clone()
+        };
+        assert!(Atom::ptr_eq(&key(&before), &key(&data)));
+    }
+
+    #[test]
+    fn fold_visits_parents_before_children() {
+        let data = edn("[1 {:a (2)} #t 3]");
+        let kinds = fold(&data, Vec::new(), |mut kinds, e| {
+            kinds.push(e.type_name());
+            kinds
+        });
+
+        assert_eq!(
+            kinds,
+            vec![
+                "vector",
+                "integer",
+                "map",
+                "list",
+                "integer",
+                "tagged-element",
+                "integer"
+            ]
+        );
+    }
+
+    #[test]
+    fn prewalk_and_postwalk_order() {
+        let data = edn("[[1] 2]");
+        let mut pre = Vec::new();
+        let mut post = Vec::new();
+
+        prewalk(data.clone(), |e| {
+            pre.push(e.to_string());
+            e
+        });
+        postwalk(data, |e| {
+            post.push(e.to_string());
+            e
+        });
+
+        assert_eq!(pre, vec!["[[1] 2]", "[1]", "1", "2"]);
+        assert_eq!(post, vec!["1", "[1]", "2", "[[1] 2]"]);
+    }
+
+    #[test]
+    fn postwalk_sees_rebuilt_children() {
+        let data = edn("{:a [1 2] :b [3]}");
+        let summed = postwalk(data, |e| match e {
+            Edn::Vector(v) => Edn::from(v.to_vec().iter().filter_map(Edn::to_uint).sum::<u64>()),
+            e => e,
+        });
+
+        assert_eq!(summed, edn("{:a 3 :b 3}"));
+    }
+}
@@ -0,0 +1,458 @@
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::edn::{Atom, Edn, Error, List, Map, Set, Tagged, Vector};
+
+enum Kind {
+    Vector,
+    List,
+    Set,
+    Map,
+    Tagged(Atom),
+}
+
+// The parent of the focus, split around it. `right` is reversed so the next sibling is its last
+// element.
+struct Frame {
+    kind: Kind,
+    left: Vec<Edn>,
+    right: Vec<Edn>,
+}
+
+/// `Zipper` moves a focus around an owned `Edn` tree and edits it in place, like `clojure.zip`.
+///
+/// The children of vectors, lists and sets are their elements, in order. The children of a map are
+/// its entries as `[key value]` vectors, and the child of a tagged element is its value.
+/// Moves return `None` and leave the zipper where it was when there is nowhere to go, and edits
+/// return an error when they don't make sense at the focus.
+/// ```rust
+/// use edn_rs::edn::{Edn, Zipper};
+/// use std::str::FromStr;
+///
+/// let mut zipper = Zipper::new(Edn::from_str("[1 [2 3] 4]").unwrap());
+/// zipper.down().unwrap().right().unwrap().down().unwrap();
+/// assert_eq!(zipper.node(), &Edn::UInt(2));
+///
+/// zipper.replace(Edn::UInt(20)).unwrap().insert_right(Edn::UInt(21)).unwrap();
+/// assert_eq!(zipper.root(), Edn::from_str("[1 [20 21 3] 4]"));
+/// ```
+pub struct Zipper {
+    focus: Edn,
+    path: Vec<Frame>,
+}
+
+fn children(edn: Edn) -> (Kind, Vec<Edn>) {
+    match edn {
+        Edn::Vector(v) => (Kind::Vector, v.to_vec()),
+        Edn::List(l) => (Kind::List, l.to_vec()),
+        Edn::Set(s) => (Kind::Set, s.to_set().into_iter().collect()),
+        Edn::Map(m) => (
+            Kind::Map,
+            m.into_iter()
+                .map(|(k, v)| Edn::Vector(Vector::new(vec![Edn::read_key(&k), v])))
+                .collect(),
+        ),
+        Edn::Tagged(t) => {
+            let (tag, value) = t.into_parts();
+            (Kind::Tagged(tag), vec![value])
+        }
+        edn => (Kind::Vector, vec![edn]),
+    }
+}
+
+fn entry(edn: Edn) -> Option<(String, Edn)> {
+    match edn {
+        Edn::Vector(v) => match <[Edn; 2]>::try_from(v.to_vec()) {
+            Ok([k, v]) => Some((k.to_string(), v)),
+            Err(_) => None,
+        },
+        _ => None,
+    }
+}
+
+// Children are checked with `check` before they are built, so map children are all entries and
+// nothing collapses into an equal key or element.
+fn build(kind: Kind, children: Vec<Edn>) -> Edn {
+    match kind {
+        Kind::Vector => Edn::Vector(Vector::new(children)),
+        Kind::List => Edn::List(List::new(children)),
+        Kind::Set => Edn::Set(Set::new(children.into_iter().collect())),
+        Kind::Map => Edn::Map(Map::new(
+            children
+                .into_iter()
+                .filter_map(entry)
+                .collect::<BTreeMap<_, _>>(),
+        )),
+        Kind::Tagged(tag) => Edn::Tagged(Tagged::new(
+            tag,
+            children.into_iter().next().unwrap_or(Edn::Nil),
+        )),
+    }
+}
+
+fn check<'a>(kind: &Kind, mut children: impl Iterator<Item = &'a Edn>) -> Result<(), Error> {
+    let mut seen = BTreeSet::new();
+    match kind {
+        Kind::Set => children.try_for_each(|e| {
+            if seen.insert(e) {
+                Ok(())
+            } else {
+                Err(Error::Path(format!("duplicate element {e} in set")))
+            }
+        }),
+        Kind::Map => children.try_for_each(|e| match e {
+            Edn::Vector(v) if v.0.len() == 2 => {
+                if seen.insert(&v.0[0]) {
+                    Ok(())
+                } else {
+                    Err(Error::Path(format!("duplicate key {} in map", v.0[0])))
+                }
+            }
+            _ => Err(Error::Type {
+                expected: "map entry",
+                found: e.type_name(),
+            }),
+        }),
+        _ => Ok(()),
+    }
+}
+
+fn is_entry(edn: &Edn) -> bool {
+    matches!(edn, Edn::Vector(v) if v.0.len() == 2)
+}
+
+impl Zipper {
+    #[must_use]
+    pub const fn new(edn: Edn) -> Self {
+        Self {
+            focus: edn,
+            path: Vec::new(),
+        }
+    }
+
+    /// The value at the focus.
+    #[must_use]
+    pub const fn node(&self) -> &Edn {
+        &self.focus
+    }
+
+    /// Whether the focus has children to move `down` into.
+    #[must_use]
+    pub fn is_branch(&self) -> bool {
+        match &self.focus {
+            Edn::Vector(v) => !v.0.is_empty(),
+            Edn::List(l) => !l.0.is_empty(),
+            Edn::Set(s) => !s.0.is_empty(),
+            Edn::Map(m) => !m.0.is_empty(),
+            Edn::Tagged(_) => true,
+            _ => false,
+        }
+    }
+
+    /// Moves to the first child of the focus.
+    pub fn down(&mut self) -> Option<&mut Self> {
+        if !self.is_branch() {
+            return None;
+        }
+        let (kind, mut right) = children(core::mem::replace(&mut self.focus, Edn::Nil));
+        right.reverse();
+        self.focus = right.pop()?;
+        self.path.push(Frame {
+            kind,
+            left: Vec::new(),
+            right,
+        });
+        Some(self)
+    }
+
+    /// Moves to the parent of the focus, rebuilding it with any edits made below.
+    ///
+    /// Returns `None` and stays put at the root, or when edits below left the parent impossible to
+    /// rebuild, which `root` reports.
+    pub fn up(&mut self) -> Option<&mut Self> {
+        self.try_up().ok()?.then_some(self)
+    }
+
+    fn try_up(&mut self) -> Result<bool, Error> {
+        let Some(frame) = self.path.last() else {
+            return Ok(false);
+        };
+        check(
+            &frame.kind,
+            frame
+                .left
+                .iter()
+                .chain(Some(&self.focus))
+                .chain(frame.right.iter().rev()),
+        )?;
+        let Some(Frame {
+            kind,
+            mut left,
+            right,
+        }) = self.path.pop()
+        else {
+            return Ok(false);
+        };
+        left.push(core::mem::replace(&mut self.focus, Edn::Nil));
+        left.extend(right.into_iter().rev());
+        self.focus = build(kind, left);
+        Ok(true)
+    }
+
+    /// Moves to the next sibling of the focus.
+    pub fn right(&mut self) -> Option<&mut Self> {
+        let frame = self.path.last_mut()?;
+        let next = frame.right.pop()?;
+        frame.left.push(core::mem::replace(&mut self.focus, next));
+        Some(self)
+    }
+
+    /// Moves to the previous sibling of the focus.
+    pub fn left(&mut self) -> Option<&mut Self> {
+        let frame = self.path.last_mut()?;
+        let previous = frame.left.pop()?;
+        frame
+            .right
+            .push(core::mem::replace(&mut self.focus, previous));
+        Some(self)
+    }
+
+    /// Moves to the next value in a depth-first walk of the tree. Returns `None` once the walk is
+    /// over, leaving the zipper at the root, or early if `up` can't rebuild a parent.
+    /// ```rust
+    /// use edn_rs::edn::{Edn, Zipper};
+    /// use std::str::FromStr;
+    ///
+    /// let mut zipper = Zipper::new(Edn::from_str("[1 [2 :drop] :drop]").unwrap());
+    /// while zipper.next().is_some() {
+    ///     if zipper.node() == &Edn::Key(":drop".into()) {
+    ///         zipper.remove().unwrap();
+    ///     }
+    /// }
+    /// assert_eq!(zipper.root(), Edn::from_str("[1 [2]]"));
+    /// ```
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Option<&mut Self> {
+        if self.down().is_some() {
+            return Some(self);
+        }
+        loop {
+            if self.right().is_some() {
+                return Some(self);
+            }
+            self.up()?;
+        }
+    }
+
+    fn check_child(&self, edn: &Edn) -> Result<(), Error> {
+        match self.path.last() {
+            Some(Frame {
+                kind: Kind::Map, ..
+            }) if !is_entry(edn) => Err(Error::Type {
+                expected: "map entry",
+                found: edn.type_name(),
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    fn siblings(&mut self, edit: &str) -> Result<&mut Frame, Error> {
+        match self.path.last_mut() {
+            None => Err(Error::Path(format!("cannot {edit} at the root"))),
+            Some(Frame {
+                kind: Kind::Tagged(_),
+                ..
+            }) => Err(Error::Path(format!("cannot {edit} in a tagged element"))),
+            Some(frame) => Ok(frame),
+        }
+    }
+
+    /// Replaces the focus with `edn`.
+    ///
+    /// # Errors
+    ///
+    /// `Error::Type` if the focus is a map entry and `edn` isn't a `[key value]` vector.
+    pub fn replace(&mut self, edn: Edn) -> Result<&mut Self, Error> {
+        self.check_child(&edn)?;
+        self.focus = edn;
+        Ok(self)
+    }
+
+    /// Inserts `edn` as the previous sibling of the focus, without moving.
+    ///
+    /// # Errors
+    ///
+    /// `Error::Path` at the root or inside a tagged element, and `Error::Type` if the focus is a
+    /// map entry and `edn` isn't a `[key value]` vector.
+    pub fn insert_left(&mut self, edn: Edn) -> Result<&mut Self, Error> {
+        self.check_child(&edn)?;
+        self.siblings("insert")?.left.push(edn);
+        Ok(self)
+    }
+
+    /// Inserts `edn` as the next sibling of the focus, without moving.
+    ///
+    /// # Errors
+    ///
+    /// `Error::Path` at the root or inside a tagged element, and `Error::Type` if the focus is a
+    /// map entry and `edn` isn't a `[key value]` vector.
+    pub fn insert_right(&mut self, edn: Edn) -> Result<&mut Self, Error> {
+        self.check_child(&edn)?;
+        self.siblings("insert")?.right.push(edn);
+        Ok(self)
+    }
+
+    /// Removes the focus and moves to the value before it in a depth-first walk, so `next`
+    /// carries on from where the removed value was.
+    ///
+    /// # Errors
+    ///
+    /// `Error::Path` at the root or inside a tagged element.
+    pub fn remove(&mut self) -> Result<&mut Self, Error> {
+        let frame = self.siblings("remove")?;
+        if let Some(previous) = frame.left.pop() {
+            self.focus = previous;
+            while self.down().is_some() {
+                while self.right().is_some() {}
+            }
+        } else {
+            check(&frame.kind, frame.right.iter())?;
+            if let Some(Frame { kind, right, .. }) = self.path.pop() {
+                self.focus = build(kind, right.into_iter().rev().collect());
+            }
+        }
+        Ok(self)
+    }
+
+    /// Moves all the way up and returns the edited tree.
+    ///
+    /// # Errors
+    ///
+    /// `Error::Type` if a map entry was edited into something other than a `[key value]` vector,
+    /// and `Error::Path` if edits left a map with two equal keys or a set with two equal elements.
+    pub fn root(mut self) -> Result<Edn, Error> {
+        while self.try_up()? {}
+        Ok(self.focus)
+    }
+}
+
+impl From<Edn> for Zipper {
+    fn from(edn: Edn) -> Self {
+        Self::new(edn)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use core::str::FromStr;
+
+    use super::*;
+
+    fn edn(s: &str) -> Edn {
+        Edn::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn moves_stay_put_when_there_is_nowhere_to_go() {
+        let mut zipper = Zipper::new(edn("[1 ()]"));
+
+        assert!(zipper.up().is_none());
+        assert!(zipper.right().is_none());
+        zipper.down().unwrap();
+        assert!(zipper.left().is_none());
+        assert!(zipper.down().is_none());
+        assert_eq!(zipper.node(), &Edn::UInt(1));
+        zipper.right().unwrap();
+        assert!(zipper.down().is_none());
+        assert!(zipper.right().is_none());
+        assert_eq!(zipper.node(), &edn("()"));
+    }
+
+    #[test]
+    fn maps_are_walked_entry_by_entry() {
+        let mut zipper = Zipper::new(edn("{:a 1 :b #{2}}"));
+        zipper.down().unwrap();
+        assert_eq!(zipper.node(), &edn("[:a 1]"));
+
+        assert_eq!(
+            zipper.replace(Edn::UInt(1)).err(),
+            Some(Error::Type {
+                expected: "map entry",
+                found: "integer"
+            })
+        );
+        zipper.replace(edn("[:a 10]")).unwrap();
+        zipper.insert_left(edn("[\"c\" nil]")).unwrap();
+        zipper
+            .right()
+            .unwrap()
+            .down()
+            .unwrap()
+            .right()
+            .unwrap()
+            .down()
+            .unwrap();
+        assert_eq!(zipper.node(), &Edn::UInt(2));
+        zipper.insert_right(Edn::UInt(3)).unwrap();
+
+        assert_eq!(zipper.root(), Ok(edn("{:a 10 :b #{2 3} \"c\" nil}")));
+    }
+
+    #[test]
+    fn tagged_values_have_one_child() {
+        let mut zipper = Zipper::new(edn("[#point [1 2]]"));
+        zipper.down().unwrap().down().unwrap();
+        assert_eq!(zipper.node(), &edn("[1 2]"));
+        assert!(zipper.remove().is_err());
+        assert!(zipper.insert_left(Edn::Nil).is_err());
+
+        zipper.down().unwrap().replace(Edn::UInt(0)).unwrap();
+        assert_eq!(zipper.root(), Ok(edn("[#point [0 2]]")));
+    }
+
+    #[test]
+    fn remove_moves_to_the_previous_value() {
+        let mut zipper = Zipper::new(edn("[[1 [2]] 3 (4)]"));
+        zipper.down().unwrap().right().unwrap().remove().unwrap();
+        assert_eq!(zipper.node(), &Edn::UInt(2));
+
+        zipper.next().unwrap().down().unwrap().remove().unwrap();
+        assert_eq!(zipper.node(), &edn("()"));
+        assert!(zipper.next().is_none());
+        assert_eq!(zipper.node(), &edn("[[1 [2]] ()]"));
+        assert!(Zipper::new(Edn::Nil).remove().is_err());
+    }
+
+    #[test]
+    fn root_rejects_edits_that_would_lose_data() {
+        let mut zipper = Zipper::new(edn("{:a 1 :b 2}"));
+        zipper.down().unwrap().down().unwrap().right().unwrap();
+        zipper.remove().unwrap();
+        assert!(zipper.up().unwrap().up().is_none());
+        assert_eq!(
+            zipper.root(),
+            Err(Error::Type {
+                expected: "map entry",
+                found: "vector"
+            })
+        );
+
+        let mut zipper = Zipper::new(edn("{:a 1 :b 2}"));
+        zipper.down().unwrap().right().unwrap().down().unwrap();
+        zipper.replace(edn(":a")).unwrap();
+        assert_eq!(
+            zipper.root(),
+            Err(Error::Path("duplicate key :a in map".to_string()))
+        );
+
+        let mut zipper = Zipper::new(edn("#{1 2}"));
+        zipper.down().unwrap().replace(Edn::UInt(2)).unwrap();
+        assert_eq!(
+            zipper.root(),
+            Err(Error::Path("duplicate element 2 in set".to_string()))
+        );
+    }
+}
@@ -0,0 +1,193 @@
+// `#inst` timestamps, in the RFC 3339 form `1985-04-12T23:20:50.52Z`, to and from the seconds
+// and nanoseconds since the Unix epoch that `SystemTime` is built from. Years outside 0000 to
+// 9999 don't fit RFC 3339, so they take a sign and as many digits as they need, like ISO 8601
+// expanded years: `+10000-01-01T00:00:00Z` or `-0001-12-31T00:00:00Z`.
+
+use alloc::format;
+use alloc::string::String;
+use core::time::Duration;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub const TAG: &str = "inst";
+
+const SECS_PER_DAY: i64 = 86_400;
+
+/// Writes `time` in UTC, with as many fractional digits as its nanoseconds need.
+pub fn format(time: SystemTime) -> String {
+    let (secs, nanos) = match time.duration_since(UNIX_EPOCH) {
+        Ok(after) => (
+            i64::try_from(after.as_secs()).unwrap_or(i64::MAX),
+            after.subsec_nanos(),
+        ),
+        Err(before) => {
+            let before = before.duration();
+            let secs = i64::try_from(before.as_secs()).map_or(i64::MIN, |secs| -secs);
+            match before.subsec_nanos() {
+                0 => (secs, 0),
+                nanos => (secs.saturating_sub(1), 1_000_000_000 - nanos),
+            }
+        }
+    };
+    let (year, month, day) = civil_from_days(secs.div_euclid(SECS_PER_DAY));
+    let secs_of_day = secs.rem_euclid(SECS_PER_DAY);
+    let year = if (0..=9999).contains(&year) {
+        format!("{year:04}")
+    } else {
+        format!("{year:+05}")
+    };
+    let mut inst = format!(
+        "{year}-{month:02}-{day:02}T{:02}:{:02}:{:02}",
+        secs_of_day / 3600,
+        secs_of_day / 60 % 60,
+        secs_of_day % 60
+    );
+    if nanos > 0 {
+        let fraction = format!("{nanos:09}");
+        inst.push('.');
+        inst.push_str(fraction.trim_end_matches('0'));
+    }
+    inst.push('Z');
+    inst
+}
+
+/// Reads `YYYY-MM-DDTHH:MM:SS`, with optional fractional seconds, followed by `Z` or an offset
+/// like `-03:00`. The year can also be signed, with four or more digits, as `format` writes the
+/// years outside 0000 to 9999.
+pub fn parse(inst: &str) -> Option<SystemTime> {
+    let (year, inst) = split_year(inst)?;
+    let b = inst.as_bytes();
+    if b.len() < 16
+        || b[0] != b'-'
+        || b[3] != b'-'
+        || !matches!(b[6], b'T' | b't')
+        || b[9] != b':'
+        || b[12] != b':'
+    {
+        return None;
+    }
+    let (month, day) = (number(inst, 1..3)?, number(inst, 4..6)?);
+    let (hour, minute, second) = (
+        number(inst, 7..9)?,
+        number(inst, 10..12)?,
+        number(inst, 13..15)?,
+    );
+    if !(1..=12).contains(&month)
+        || day < 1
+        || day > days_in_month(year, month)
+        || hour > 23
+        || minute > 59
+        || second > 59
+    {
+        return None;
+    }
+
+    let mut rest = &inst[15..];
+    let mut nanos = 0;
+    if let Some(fraction) = rest.strip_prefix('.') {
+        let digits = fraction.bytes().take_while(u8::is_ascii_digit).count();
+        if digits == 0 {
+            return None;
+        }
+        // Digits past nanoseconds are dropped.
+        for (i, c) in fraction
+            .bytes()
+            .take(9)
+            .take_while(u8::is_ascii_digit)
+            .enumerate()
+        {
+            nanos += u32::from(c - b'0') * 10u32.pow(8 - u32::try_from(i).ok()?);
+        }
+        rest = &fraction[digits..];
+    }
+    let offset = match rest.as_bytes() {
+        [b'Z' | b'z'] => 0,
+        [sign @ (b'+' | b'-'), _, _, b':', _, _] => {
+            let (hours, minutes) = (number(rest, 1..3)?, number(rest, 4..6)?);
+            if hours > 23 || minutes > 59 {
+                return None;
+            }
+            let offset = (hours * 60 + minutes) * 60;
+            if *sign == b'-' {
+                -offset
+            } else {
+                offset
+            }
+        }
+        _ => return None,
+    };
+
+    let secs = days_from_civil(year, month, day)
+        .checked_mul(SECS_PER_DAY)?
+        .checked_add((hour * 60 + minute) * 60 + second - offset)?;
+    let nanos = Duration::from_nanos(nanos.into());
+    if secs >= 0 {
+        UNIX_EPOCH.checked_add(Duration::from_secs(secs.unsigned_abs()))?
+    } else {
+        UNIX_EPOCH.checked_sub(Duration::from_secs(secs.unsigned_abs()))?
+    }
+    .checked_add(nanos)
+}
+
+// Splits the year off the front of `inst`: four digits, or a sign and four or more digits. Years
+// past nine digits are out of `SystemTime`'s range anyway, and would overflow the day count.
+fn split_year(inst: &str) -> Option<(i64, &str)> {
+    let digits = |s: &str| s.bytes().take_while(u8::is_ascii_digit).count();
+    match inst.as_bytes().first()? {
+        b'+' | b'-' => {
+            let len = 1 + digits(&inst[1..]);
+            if !(5..=10).contains(&len) {
+                return None;
+            }
+            Some((inst[..len].parse().ok()?, &inst[len..]))
+        }
+        _ if digits(inst) == 4 => Some((number(inst, 0..4)?, &inst[4..])),
+        _ => None,
+    }
+}
+
+fn number(s: &str, range: core::ops::Range<usize>) -> Option<i64> {
+    let digits = s.get(range)?;
+    if digits.bytes().all(|c| c.is_ascii_digit()) {
+        digits.parse().ok()
+    } else {
+        None
+    }
+}
+
+const fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+// Days since 1970-01-01 in the proleptic Gregorian calendar, from Howard Hinnant's
+// `days_from_civil`, which works in 400 year eras starting on March 1st.
+const fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let day_of_year = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+const fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400;
+    (if month <= 2 { year + 1 } else { year }, month, day)
+}
@@ -1,28 +1,26 @@
-use alloc::collections::BTreeMap;
-#[cfg(feature = "sets")]
-use alloc::collections::BTreeSet;
+use alloc::collections::{BTreeMap, BTreeSet};
 use alloc::format;
 use alloc::string::{String, ToString};
 use alloc::vec::Vec;
 
-use crate::edn::{rational_to_double, Edn};
+use crate::edn::{rational_to_double, Atom, Edn};
 
 #[allow(clippy::module_name_repetitions)]
 pub fn display_as_json(edn: &Edn) -> String {
     match edn {
-        Edn::Vector(v) => vec_to_json(&v.clone().to_vec()),
-        #[cfg(feature = "sets")]
-        Edn::Set(s) => set_to_json_vec(&s.clone().to_set()),
-        Edn::Map(map) => map_to_json(&map.clone().to_map()),
-        Edn::List(l) => vec_to_json(&l.clone().to_vec()),
+        Edn::Vector(v) => vec_to_json(&v.0),
+        Edn::Set(s) => set_to_json_vec(&s.0),
+        Edn::Map(map) => map_to_json(&map.0),
+        Edn::List(l) => vec_to_json(&l.0),
         Edn::Key(key) => format!("{:?}", kebab_to_camel(key)),
-        Edn::Symbol(s) | Edn::Str(s) => format!("{s:?}"),
+        Edn::Symbol(s) => format!("{s:?}"),
+        Edn::Str(s) => format!("{s:?}"),
         Edn::Int(n) => format!("{n}"),
         Edn::UInt(n) => format!("{n}"),
         Edn::Double(n) => {
             // Rust formats an f64 with a value of 2^5 as "32".
             // We do this to ensure all precision is printed if available, but still adds a decimal point for json.
-            let mut s = format!("{n}");
+            let mut s = format!("{}", n.to_float());
             if !s.contains('.') {
                 s.push_str(".0");
             }
@@ -33,7 +31,7 @@ pub fn display_as_json(edn: &Edn) -> String {
         Edn::Bool(b) => format!("{b}"),
         Edn::Nil => String::from("null"),
         Edn::Empty => String::new(),
-        Edn::Tagged(tag, content) => format!("{{ \"{}\": {}}}", tag, display_as_json(content)),
+        Edn::Tagged(t) => format!("{{ \"{}\": {}}}", t.tag(), display_as_json(t.value())),
     }
 }
 
@@ -76,7 +74,6 @@ fn vec_to_json(vec: &[Edn]) -> String {
     s
 }
 
-#[cfg(feature = "sets")]
 fn set_to_json_vec(set: &BTreeSet<Edn>) -> String {
     let set_str = set
         .iter()
@@ -89,7 +86,7 @@ fn set_to_json_vec(set: &BTreeSet<Edn>) -> String {
     s
 }
 
-fn map_to_json(map: &BTreeMap<String, Edn>) -> String {
+fn map_to_json(map: &BTreeMap<Atom, Edn>) -> String {
     let map_str = map
         .iter()
         .map(|(k, e)| {
@@ -116,7 +113,7 @@ mod test {
     use alloc::vec;
 
     use super::*;
-    use crate::edn::{Edn, List, Map, Set, Vector};
+    use crate::edn::{Edn, List, Map, Set, Tagged, Vector};
     use crate::{map, set};
 
     #[test]
@@ -142,11 +139,11 @@ mod test {
     #[test]
     fn rational_numbers() {
         assert_eq!(
-            display_as_json(&Edn::Rational("3/4".to_string())),
+            display_as_json(&Edn::Rational("3/4".into())),
             String::from("0.75")
         );
         assert_eq!(
-            display_as_json(&Edn::Rational("-3/9".to_string())),
+            display_as_json(&Edn::Rational("-3/9".into())),
             String::from("-0.3333333333333333")
         );
     }
@@ -165,20 +162,20 @@ mod test {
 
     #[test]
     fn strings() {
-        let edn = Edn::Str("Hello World".to_string());
+        let edn = Edn::Str("Hello World".into());
         assert_eq!(display_as_json(&edn), "\"Hello World\"".to_string());
     }
 
     #[test]
     fn symbols() {
-        let edn = Edn::Symbol("Hello World".to_string());
+        let edn = Edn::Symbol("Hello World".into());
         assert_eq!(display_as_json(&edn), "\"Hello World\"".to_string());
     }
 
     #[test]
     fn keyword() {
         // Don't know what to do with '/'. maybe whitespace?
-        let edn = Edn::Key(":hellow-world/again.id".to_string());
+        let edn = Edn::Key(":hellow-world/again.id".into());
         assert_eq!(display_as_json(&edn), "\"hellowWorld/againId\"".to_string());
     }
 
@@ -186,10 +183,10 @@ mod test {
     fn vector() {
         let edn = Edn::Vector(Vector::new(vec![
             Edn::Bool(true),
-            Edn::Key(":b".to_string()),
-            Edn::Str("test".to_string()),
+            Edn::Key(":b".into()),
+            Edn::Str("test".into()),
             Edn::Char('4'),
-            Edn::Rational("-3/4".to_string()),
+            Edn::Rational("-3/4".into()),
             Edn::Double(4.5f64.into()),
             Edn::UInt(4),
         ]));
@@ -203,10 +200,10 @@ mod test {
     fn list() {
         let edn = Edn::List(List::new(vec![
             Edn::Bool(true),
-            Edn::Key(":b".to_string()),
-            Edn::Str("test".to_string()),
+            Edn::Key(":b".into()),
+            Edn::Str("test".into()),
             Edn::Char('4'),
-            Edn::Rational("-3/4".to_string()),
+            Edn::Rational("-3/4".into()),
             Edn::Double(4.5f64.into()),
             Edn::UInt(4),
         ]));
@@ -220,10 +217,10 @@ mod test {
     fn set_test() {
         let edn = Edn::Set(Set::new(set![
             Edn::Bool(true),
-            Edn::Key(":my-bestie".to_string()),
-            Edn::Str("test".to_string()),
+            Edn::Key(":my-bestie".into()),
+            Edn::Str("test".into()),
             Edn::Char('4'),
-            Edn::Rational("-3/4".to_string()),
+            Edn::Rational("-3/4".into()),
             Edn::Double(4.5f64.into()),
             Edn::UInt(4),
         ]));
@@ -238,7 +235,7 @@ mod test {
     fn simple_map() {
         let map = Edn::Map(Map::new(map! {
             String::from("1.2") => Edn::Bool(false),
-            String::from(":belo-monte") => Edn::Rational(String::from("3/4")),
+            String::from(":belo-monte") => Edn::Rational("3/4".into()),
             String::from("true") => Edn::Char('d')
         }));
 
@@ -256,24 +253,24 @@ mod test {
             Edn::UInt(3),
             Edn::List(List::new(vec![
                 Edn::Bool(false),
-                Edn::Key(":f".to_string()),
+                Edn::Key(":f".into()),
                 Edn::Nil,
-                Edn::Rational("3/4".to_string()),
+                Edn::Rational("3/4".into()),
                 Edn::Set(Set::new(set! {
-                    Edn::Rational("3/4".to_string())
+                    Edn::Rational("3/4".into())
                 })),
             ])),
             Edn::Map(Map::new(map![
-                    String::from("false") => Edn::Key(":f".to_string()),
-                    String::from("nil") => Edn::Rational("3/4".to_string()),
+                    String::from("false") => Edn::Key(":f".into()),
+                    String::from("nil") => Edn::Rational("3/4".into()),
                     String::from(":my-crazy-map") => Edn::Map(Map::new(map![
                         String::from("false") => Edn::Map(
                             Map::new( map![
-                                String::from(":f") => Edn::Key(String::from(":b"))
+                                String::from(":f") => Edn::Key(":b".into())
                             ])),
                         String::from("nil") => Edn::Vector(
                             Vector::new( vec![
-                                Edn::Rational("3/4".to_string()),
+                                Edn::Rational("3/4".into()),
                                 Edn::Int(1i64)
                             ]))
                 ]))
@@ -286,18 +283,18 @@ mod test {
 
     #[test]
     fn tagged_vector() {
-        let edn = Edn::Tagged(
-            String::from("random/tag"),
-            Box::new(Edn::Vector(Vector::new(vec![
+        let edn = Edn::Tagged(Tagged::new(
+            "random/tag",
+            Edn::Vector(Vector::new(vec![
                 Edn::Bool(true),
-                Edn::Key(":b".to_string()),
-                Edn::Str("test".to_string()),
+                Edn::Key(":b".into()),
+                Edn::Str("test".into()),
                 Edn::Char('4'),
-                Edn::Rational("-3/4".to_string()),
+                Edn::Rational("-3/4".into()),
                 Edn::Double(4.5f64.into()),
                 Edn::UInt(4),
-            ]))),
-        );
+            ])),
+        ));
         assert_eq!(
             display_as_json(&edn),
             "{ \"random/tag\": [true, \"b\", \"test\", \'4\', -0.75, 4.5, 4]}".to_string()
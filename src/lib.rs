@@ -13,15 +13,29 @@ use alloc::string::String;
 /// Edn type implementation
 pub mod edn;
 
+/// Width aware pretty printing of `Edn`, laid out over several lines
+pub mod pretty;
+/// `serde` support, with `Serializer` and `Deserializer` implementations for EDN. Needs feature `serde`.
+#[cfg(feature = "serde")]
+pub mod serde;
+
+/// Specter style selection and transformation of nested `Edn`
+pub mod query;
+
+/// `#base64` tagged bytes, for `Vec<u8>` fields with `#[edn(with = "edn_rs::base64")]`.
+pub mod base64;
+#[cfg(feature = "std")]
+mod inst;
+
 /// Serialization module for most possible types.
-/// Tuples are limited between `(A, B)` and `(A, B, C, D, E, F)`, any other tuple needs to be implemented by the `trait Serialize`.
-/// This module requires `#[macro_use]` for `structs`.
+/// Tuples are limited between `(A,)` and 16 elements, any other tuple needs to be implemented by the `trait Serialize`.
+/// Structs and enums can derive `Serialize` with feature `derive`.
 ///
 /// Example:
-/// ```rust
+#[cfg_attr(feature = "derive", doc = "```rust")]
+#[cfg_attr(not(feature = "derive"), doc = "```ignore")]
 /// use std::collections::{BTreeMap, BTreeSet};
-/// use edn_derive::Serialize;
-/// use edn_rs::{set, map, edn::Edn};
+/// use edn_rs::{set, map, edn::Edn, Serialize};
 ///
 /// #[derive(Serialize)]
 /// struct ExampleEdn {
@@ -45,6 +59,11 @@ pub mod serialize;
 #[cfg(feature = "json")]
 pub(crate) mod json;
 
+#[cfg(feature = "derive")]
+#[doc(hidden)]
+#[path = "private.rs"]
+pub mod __private;
+
 #[cfg(feature = "json")]
 use alloc::borrow::Cow;
 #[cfg(feature = "json")]
@@ -99,22 +118,134 @@ pub fn json_to_edn<'a>(json: impl AsRef<str>) -> Cow<'a, str> {
     json.replace("null", "nil").into()
 }
 
+pub use base64::Bytes;
 pub use deserialize::{from_edn, from_str, Deserialize};
 pub use edn::Error as EdnError;
-#[cfg(feature = "sets")]
-pub use edn::Set;
-pub use edn::{Edn, List, Map, Vector};
-pub use serialize::Serialize;
+pub use edn::{Edn, List, Map, Set, Tagged, Vector};
+pub use serialize::{Keyword, Serialize, ToEdn, ToEdnMap};
+
+/// Derives `Serialize` for structs and enums, and `ToEdn` to build the same value as an `Edn`.
+/// Needs feature `derive`.
+///
+/// Structs are written as maps with keyword keys, tuple structs as lists, newtype structs as
+/// their value and unit structs as `nil`. Field names have their underscores turned into
+/// dashes, so `max_retries` becomes `:max-retries`.
+///
+/// Enums write unit variants as keywords and, by default, the other variants tagged with their
+/// name: `#Circle 1.5`, `#Point [1, 2]` or `#Rect { :w 1, :h 2, }`.
+///
+/// Attributes on the struct or enum:
+/// - `#[edn(rename_all = "kebab-case")]` spells field and variant names in `"lowercase"`,
+///   `"UPPERCASE"`, `"PascalCase"`, `"camelCase"`, `"snake_case"`, `"SCREAMING_SNAKE_CASE"`,
+///   `"kebab-case"` or `"SCREAMING-KEBAB-CASE"`.
+/// - `#[edn(namespace = "person")]` namespaces the keys of a struct, like `:person/name`, or the
+///   variants of an enum, like `:status/active`.
+/// - `#[edn(repr = "...")]` picks how enum variants with data are written: `"tagged"`, the
+///   default, `"map"` for `{:Circle 1.5}`, or `"keyword"` for enums with only unit variants.
+///
+/// Attributes on fields:
+/// - `#[edn(rename = "name")]` uses another key. A name with a `/`, like `"person/name"`, keeps
+///   its own namespace.
+/// - `#[edn(skip)]` leaves the field out. `Deserialize` fills it with `Default::default()`, or
+///   with the function given by `default`.
+/// - `#[edn(default)]` or `#[edn(default = "path::to::function")]` is used by `Deserialize`
+///   when the key is missing. Without it a missing key reads as `nil`, so `Option` fields can
+///   always be left out.
+/// - `#[edn(flatten)]` writes the entries of a field that implements `ToEdnMap`, like a map or
+///   a derived struct, into the surrounding map, and `Deserialize` reads that field from the
+///   whole surrounding map. Other types, including `Edn`, don't compile. Keys that the struct's
+///   own fields, or an earlier flattened field, already have are left out.
+/// - `#[edn(with = "module")]` uses `module::serialize_into(&T, &mut dyn fmt::Write) ->
+///   fmt::Result` and `module::deserialize(&Edn) -> Result<T, EdnError>` for the field.
+///   `#[edn(with = "edn_rs::base64")]` writes a `Vec<u8>` as `#base64 "..."`.
+///
+/// Enum variants take `#[edn(rename = "name")]`, and the fields of struct variants take the
+/// same attributes as struct fields.
+///
+/// ```rust
+/// use edn_rs::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize, Debug, PartialEq)]
+/// #[edn(rename_all = "kebab-case", repr = "map")]
+/// enum Shape {
+///     Empty,
+///     Circle { radius: f64 },
+/// }
+///
+/// #[derive(Serialize, Deserialize, Debug, PartialEq)]
+/// #[edn(namespace = "drawing")]
+/// struct Drawing {
+///     name: String,
+///     #[edn(rename = "shapes")]
+///     all_shapes: Vec<Shape>,
+///     #[edn(skip)]
+///     dirty: bool,
+/// }
+///
+/// let drawing = Drawing {
+///     name: "logo".to_string(),
+///     all_shapes: vec![Shape::Empty, Shape::Circle { radius: 1.5 }],
+///     dirty: true,
+/// };
+/// let edn = edn_rs::to_string(&drawing);
+/// assert_eq!(
+///     edn,
+///     "{ :drawing/name \"logo\", :drawing/shapes [:empty, {:circle { :radius 1.5, }}], }"
+/// );
+/// assert_eq!(
+///     edn_rs::from_str::<Drawing>(&edn),
+///     Ok(Drawing { dirty: false, ..drawing })
+/// );
+/// ```
+///
+/// An `Edn` can hold any value, so it can't be flattened:
+/// ```compile_fail
+/// #[derive(edn_rs::Serialize)]
+/// struct Extended {
+///     a: u8,
+///     #[edn(flatten)]
+///     extra: edn_rs::Edn,
+/// }
+/// ```
+#[cfg(feature = "derive")]
+pub use edn_rs_derive::Serialize;
+
+/// Derives `Deserialize` for structs and enums, reading what the derived `Serialize` writes.
+/// Needs feature `derive`, and takes the same attributes as the `Serialize` derive.
+///
+/// Struct keys that are missing read as `nil` unless the field has a `default`, and unknown
+/// keys are ignored. Tuple structs are read from vectors or lists of the right length, and
+/// enum variants from a keyword, or from a tagged value or single entry map depending on
+/// `repr`.
+/// ```rust
+/// use edn_rs::Deserialize;
+///
+/// #[derive(Deserialize, Debug, PartialEq)]
+/// struct Config {
+///     #[edn(default = "three")]
+///     max_retries: u8,
+///     proxy: Option<String>,
+/// }
+///
+/// fn three() -> u8 {
+///     3
+/// }
+///
+/// let config: Config = edn_rs::from_str("{:timeout 5}").unwrap();
+/// assert_eq!(config, Config { max_retries: 3, proxy: None });
+/// ```
+#[cfg(feature = "derive")]
+pub use edn_rs_derive::Deserialize;
 
 /// Function for converting Rust types into EDN Strings.
 /// For it to work, the type must implement the Serialize trait.
-/// Use `#[derive(Serialize)]` from `edn-derive` crate.
+/// Use `#[derive(Serialize)]` with feature `derive`.
 ///
 /// Example:
-/// ```rust
+#[cfg_attr(feature = "derive", doc = "```rust")]
+#[cfg_attr(not(feature = "derive"), doc = "```ignore")]
 /// use std::collections::{BTreeMap, BTreeSet};
-/// use edn_derive::Serialize;
-/// use edn_rs::{set, map, edn::Edn};
+/// use edn_rs::{set, map, edn::Edn, Serialize};
 ///
 /// #[derive(Debug, Serialize)]
 /// struct ExampleEdn {
@@ -133,7 +264,47 @@ pub use serialize::Serialize;
 ///     // { :map {:this-is-a-key ["with", "many", "keys"]}, :set #{3, 4, 5}, :tuples (3, true, \d), }
 /// }
 ///```
+///
+/// # Panics
+///
+/// If `t` fails to serialize, like `Serialize::serialize`. Use `try_to_string` to get the error
+/// instead.
 #[allow(clippy::needless_doctest_main)]
-pub fn to_string<T: Serialize>(t: &T) -> String {
+pub fn to_string<T: Serialize + ?Sized>(t: &T) -> String {
     t.serialize()
 }
+
+/// Like `to_string`, but returns the error when `t` fails to serialize instead of panicking.
+/// ```rust
+/// assert_eq!(edn_rs::try_to_string(&vec![1, 2]), Ok("[1, 2]".to_string()));
+/// ```
+///
+/// # Errors
+///
+/// The `fmt::Error` that `Serialize::serialize_into` returned.
+pub fn try_to_string<T: Serialize + ?Sized>(t: &T) -> Result<String, core::fmt::Error> {
+    t.try_serialize()
+}
+
+/// Serializes `t` straight into `writer`, without building the whole EDN string first.
+///
+/// Each piece is written as it is produced, so wrap unbuffered writers like files and sockets in
+/// a `std::io::BufWriter`.
+/// ```rust
+/// let mut out = Vec::new();
+/// edn_rs::to_writer(&mut out, &vec![(1, "a")]).unwrap();
+/// assert_eq!(out, b"[(1, \"a\")]");
+/// ```
+///
+/// # Errors
+///
+/// The first error returned by `writer`.
+#[cfg(feature = "std")]
+pub fn to_writer<W: std::io::Write, T: Serialize + ?Sized>(
+    writer: W,
+    t: &T,
+) -> std::io::Result<()> {
+    let mut writer = serialize::IoWriter::new(writer);
+    let result = t.serialize_into(&mut writer);
+    writer.finish(result)
+}
@@ -1,7 +1,6 @@
 #[macro_export(local_inner_macros)]
 ///
 /// Macro to parse `EDN` into Rust Spec
-#[cfg_attr(not(feature = "sets"), doc = "```ignore")]
 /// ```rust
 /// #![recursion_limit="512"] // recommended recursion size
 ///
@@ -19,9 +18,9 @@
 ///                     Edn::Double(1.2.into()),
 ///                     Edn::Int(3),
 ///                     Edn::Bool(false),
-///                     Edn::Key(":f".to_string()),
+///                     Edn::Key(":f".into()),
 ///                     Edn::Nil,
-///                     Edn::Rational("3/4".to_string())
+///                     Edn::Rational("3/4".into())
 ///                 ]
 ///             )
 ///         );
@@ -36,9 +35,9 @@
 ///             Edn::Double(1.2.into()),
 ///             Edn::Int(3),
 ///             Edn::Bool(false),
-///             Edn::Key(":f".to_string()),
+///             Edn::Key(":f".into()),
 ///             Edn::Nil,
-///             Edn::Rational("3/4".to_string())
+///             Edn::Rational("3/4".into())
 ///             }
 ///         )
 ///     );
@@ -50,7 +49,7 @@
 ///             map!{
 ///                 String::from("1.2") => Edn::Bool(false),
 ///                 // Note `:b` becomes `b`
-///                 String::from(":b") => Edn::Rational(String::from("3/4"))
+///                 String::from(":b") => Edn::Rational("3/4".into())
 ///             }
 ///         )
 ///     );
@@ -60,7 +59,6 @@
 /// ```
 ///
 /// A more complete example:
-#[cfg_attr(not(feature = "sets"), doc = "```ignore")]
 /// ```rust
 /// #[macro_use]
 /// extern crate edn_rs;
@@ -77,11 +75,11 @@
 ///                 Map::new( map![
 ///                     String::from("false") => Edn::Map(
 ///                         Map::new( map![
-///                             String::from(":f") => Edn::Key(String::from(":b"))
+///                             String::from(":f") => Edn::Key(":b".into())
 ///                         ])),
 ///                     String::from("nil") => Edn::Vector(
 ///                         Vector::new( vec![
-///                             Edn::Rational("3/4".to_string()),
+///                             Edn::Rational("3/4".into()),
 ///                             Edn::Int(1i64)
 ///                         ]))
 ///             ]))
@@ -106,7 +104,7 @@
 ///     assert_eq!(edn[1], edn!(1.2));
 ///     assert_eq!(edn[1], Edn::Double(1.2f64.into()));
 ///     assert_eq!(edn[3]["false"], edn!(:f));
-///     assert_eq!(edn[3]["false"], Edn::Key(":f".to_string()));
+///     assert_eq!(edn[3]["false"], Edn::Key(":f".into()));
 /// }
 /// ```
 ///
@@ -201,12 +199,12 @@ macro_rules! edn_internal {
 
     ($num:tt/$den:tt) => {{
         let q = std::format!("{:?}/{:?}", $num, $den);
-        Edn::Rational(q)
+        Edn::Rational(q.into())
     }};
 
     (:$key:tt) => {{
         let k = std::format!(":{}", std::stringify!($key));
-        Edn::Key(k)
+        Edn::Key(k.into())
     }};
 
     (#{ }) => {
@@ -244,7 +242,7 @@ macro_rules! edn_internal {
     ($($sy:ident)-+) => {{
         let s: String = std::stringify!($($sy)+).into();
         let symbol = s.replace(" ","-");
-        Edn::Symbol(symbol)
+        Edn::Symbol(symbol.into())
     }};
 
     ($e:expr) => {
@@ -256,7 +254,7 @@ macro_rules! edn_internal {
             el if el.parse::<f32>().is_ok() => Edn::Double(el.parse::<f64>().unwrap().into()),
             el if el.parse::<f64>().is_ok() => Edn::Double(el.parse::<f64>().unwrap().into()),
             el if el.parse::<bool>().is_ok() => Edn::Bool(el.parse::<bool>().unwrap()),
-            el => Edn::Str(el)
+            el => Edn::Str(el.into())
         }
     };
 }
@@ -0,0 +1,274 @@
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::edn::Edn;
+
+/// `Options` configures the pretty printer. The default targets 80 columns, aligns map values and
+/// leaves out commas and the trailing newline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+    /// The column layouts try to stay within. A value too wide for it still goes on one line.
+    pub width: usize,
+    /// Pads the keys of a map laid out over several lines so its values line up.
+    pub align_map_values: bool,
+    /// Separates map entries with commas.
+    pub commas: bool,
+    /// Ends the output with a newline.
+    pub trailing_newline: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            width: 80,
+            align_map_values: true,
+            commas: false,
+            trailing_newline: false,
+        }
+    }
+}
+
+// A document in the sense of Wadler's "A prettier printer". Every `Line` in a `Group` is either a
+// space, when the whole group fits in the remaining width, or a newline.
+enum Doc {
+    Text(String),
+    Line,
+    // Spaces that are only written when the enclosing group is broken over several lines.
+    Pad(usize),
+    Concat(Vec<Self>),
+    // Indents the lines inside to the column where it starts.
+    Align(Box<Self>),
+    Group(Box<Self>),
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Flat,
+    Break,
+}
+
+fn width(s: &str) -> usize {
+    s.chars().count()
+}
+
+fn join(docs: Vec<Doc>, separator: &dyn Fn() -> Doc) -> Vec<Doc> {
+    let mut joined = Vec::with_capacity(docs.len() * 2);
+    for (i, doc) in docs.into_iter().enumerate() {
+        if i > 0 {
+            joined.push(separator());
+        }
+        joined.push(doc);
+    }
+    joined
+}
+
+fn collection(open: &str, docs: Vec<Doc>, close: &str, separator: &dyn Fn() -> Doc) -> Doc {
+    Doc::Group(Box::new(Doc::Concat(vec![
+        Doc::Text(open.to_string()),
+        Doc::Align(Box::new(Doc::Concat(join(docs, separator)))),
+        Doc::Text(close.to_string()),
+    ])))
+}
+
+fn layout(edn: &Edn, options: &Options) -> Doc {
+    match edn {
+        Edn::Vector(v) => collection("[", docs(v.iter(), options), "]", &|| Doc::Line),
+        Edn::List(l) => collection("(", docs(l.iter(), options), ")", &|| Doc::Line),
+        Edn::Set(s) => collection("#{", docs(s.iter(), options), "}", &|| Doc::Line),
+        Edn::Map(m) => {
+            let key_width = if options.align_map_values {
+                m.keys().map(|k| width(k)).max().unwrap_or(0)
+            } else {
+                0
+            };
+            let entries = m
+                .iter()
+                .map(|(k, v)| {
+                    Doc::Concat(vec![
+                        Doc::Text(k.to_string()),
+                        Doc::Pad(key_width.saturating_sub(width(k))),
+                        Doc::Text(" ".to_string()),
+                        layout(v, options),
+                    ])
+                })
+                .collect();
+            let separator: &dyn Fn() -> Doc = if options.commas {
+                &|| Doc::Concat(vec![Doc::Text(",".to_string()), Doc::Line])
+            } else {
+                &|| Doc::Line
+            };
+            collection("{", entries, "}", separator)
+        }
+        Edn::Tagged(t) => Doc::Concat(vec![
+            Doc::Text(alloc::format!("#{} ", t.tag())),
+            layout(t.value(), options),
+        ]),
+        edn => Doc::Text(edn.to_string()),
+    }
+}
+
+fn docs<'a>(edns: impl Iterator<Item = &'a Edn>, options: &Options) -> Vec<Doc> {
+    edns.map(|edn| layout(edn, options)).collect()
+}
+
+// Whether `doc` fits flat in `remaining` columns, along with whatever follows it up to the next
+// line break.
+fn fits(doc: &Doc, rest: &[(usize, Mode, &Doc)], mut remaining: usize) -> bool {
+    let mut todo = vec![(Mode::Flat, doc)];
+    let mut rest = rest.iter().rev();
+    loop {
+        let (mode, doc) = match todo.pop() {
+            Some(next) => next,
+            None => match rest.next() {
+                Some(&(_, mode, doc)) => (mode, doc),
+                None => return true,
+            },
+        };
+        let used = match doc {
+            Doc::Text(s) => width(s),
+            Doc::Line if mode == Mode::Break => return true,
+            Doc::Line => 1,
+            Doc::Pad(_) => 0,
+            Doc::Concat(docs) => {
+                todo.extend(docs.iter().rev().map(|doc| (mode, doc)));
+                0
+            }
+            Doc::Align(doc) | Doc::Group(doc) => {
+                todo.push((mode, doc));
+                0
+            }
+        };
+        remaining = match remaining.checked_sub(used) {
+            Some(remaining) => remaining,
+            None => return false,
+        };
+    }
+}
+
+fn render(doc: &Doc, width: usize) -> String {
+    let mut out = String::new();
+    let mut column = 0;
+    let mut stack = vec![(0, Mode::Break, doc)];
+    while let Some((indent, mode, doc)) = stack.pop() {
+        match doc {
+            Doc::Text(s) => {
+                out.push_str(s);
+                column += self::width(s);
+            }
+            Doc::Line if mode == Mode::Flat => {
+                out.push(' ');
+                column += 1;
+            }
+            Doc::Line => {
+                out.push('\n');
+                out.extend(core::iter::repeat_n(' ', indent));
+                column = indent;
+            }
+            Doc::Pad(n) if mode == Mode::Break => {
+                out.extend(core::iter::repeat_n(' ', *n));
+                column += n;
+            }
+            Doc::Pad(_) => {}
+            Doc::Concat(docs) => stack.extend(docs.iter().rev().map(|doc| (indent, mode, doc))),
+            Doc::Align(doc) => stack.push((column, mode, doc)),
+            Doc::Group(doc) => {
+                let flat = mode == Mode::Flat || fits(doc, &stack, width.saturating_sub(column));
+                stack.push((indent, if flat { Mode::Flat } else { Mode::Break }, doc));
+            }
+        }
+    }
+    out
+}
+
+/// Prints `edn` over several lines, with the default `Options`.
+///
+/// Collections that fit in the remaining width stay on one line. Otherwise each element goes on
+/// its own line, aligned after the opening delimiter, and map values line up after their keys.
+/// ```rust
+/// use edn_rs::{pretty, Edn};
+/// use std::str::FromStr;
+///
+/// let edn = Edn::from_str("{:name \"edn-rs\" :tags [:parser :emitter] :version 18}").unwrap();
+/// let options = pretty::Options { width: 30, ..pretty::Options::default() };
+/// assert_eq!(
+///     pretty::to_string_with(&edn, &options),
+///     "{:name    \"edn-rs\"\n :tags    [:parser :emitter]\n :version 18}"
+/// );
+/// ```
+#[must_use]
+pub fn to_string(edn: &Edn) -> String {
+    to_string_with(edn, &Options::default())
+}
+
+/// Prints `edn` over several lines, laid out according to `options`.
+#[must_use]
+pub fn to_string_with(edn: &Edn, options: &Options) -> String {
+    let mut out = render(&layout(edn, options), options.width);
+    if options.trailing_newline {
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use core::str::FromStr;
+
+    use super::*;
+
+    fn edn(s: &str) -> Edn {
+        Edn::from_str(s).unwrap()
+    }
+
+    fn pretty(s: &str, width: usize) -> String {
+        to_string_with(
+            &edn(s),
+            &Options {
+                width,
+                ..Options::default()
+            },
+        )
+    }
+
+    #[test]
+    fn values_that_fit_stay_on_one_line() {
+        assert_eq!(
+            pretty("{:a [1 2 (3)] :b #{:x} :c #tag {:d nil}}", 80),
+            "{:a [1 2 (3)] :b #{:x} :c #tag {:d nil}}"
+        );
+        assert_eq!(pretty("[1 2 3]", 7), "[1 2 3]");
+        assert_eq!(pretty("[1 2 3]", 6), "[1\n 2\n 3]");
+    }
+
+    #[test]
+    fn nested_collections_break_from_the_outside_in() {
+        assert_eq!(
+            pretty(
+                "{:servers [{:host \"alpha\" :port 8080} {:host \"beta\" :port 8081}] :debug false}",
+                40
+            ),
+            "{:debug   false\n :servers [{:host \"alpha\" :port 8080}\n           {:host \"beta\" :port 8081}]}"
+        );
+        assert_eq!(
+            pretty("[#point {:x 100 :y 200} (:a :b)]", 20),
+            "[#point {:x 100\n         :y 200}\n (:a :b)]"
+        );
+    }
+
+    #[test]
+    fn options() {
+        let options = Options {
+            width: 10,
+            align_map_values: false,
+            commas: true,
+            trailing_newline: true,
+        };
+        assert_eq!(
+            to_string_with(&edn("{:a 1 :bbb 2}"), &options),
+            "{:a 1,\n :bbb 2}\n"
+        );
+        assert_eq!(to_string(&edn("{:a 1 :bbb 2}")), "{:a 1 :bbb 2}");
+    }
+}
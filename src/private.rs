@@ -0,0 +1,93 @@
+// Helpers for the code `edn-rs-derive` generates. Not public API.
+
+use alloc::format;
+use core::fmt::{self, Write};
+use core::str::FromStr;
+
+pub use alloc::string::String;
+pub use alloc::vec::Vec;
+
+use crate::edn::{Edn, Error, Map};
+
+pub const fn expect_map(edn: &Edn) -> Result<&Map, Error> {
+    match edn {
+        Edn::Map(map) => Ok(map),
+        _ => Err(Error::Type {
+            expected: "map",
+            found: edn.type_name(),
+        }),
+    }
+}
+
+pub fn expect_seq<'a>(edn: &'a Edn, len: usize, name: &str) -> Result<&'a [Edn], Error> {
+    let items: &[Edn] = match edn {
+        Edn::Vector(v) => v,
+        Edn::List(l) => l,
+        _ => {
+            return Err(Error::Type {
+                expected: "vector",
+                found: edn.type_name(),
+            })
+        }
+    };
+    if items.len() == len {
+        Ok(items)
+    } else {
+        Err(unknown_variant(edn, name))
+    }
+}
+
+pub enum Repr {
+    Keyword,
+    Tagged,
+    Map,
+}
+
+/// The name of the variant `edn` holds, without its `:`, and its value unless it is a keyword.
+#[must_use]
+pub fn variant(edn: &Edn, repr: Repr) -> Option<(&str, Option<&Edn>)> {
+    match (edn, repr) {
+        (Edn::Key(k), _) => Some((k.strip_prefix(':').unwrap_or(k), None)),
+        (Edn::Tagged(t), Repr::Tagged) => Some((t.tag(), Some(t.value()))),
+        (Edn::Map(m), Repr::Map) if m.len() == 1 => m
+            .iter()
+            .next()
+            .and_then(|(k, v)| Some((k.strip_prefix(':')?, Some(v)))),
+        _ => None,
+    }
+}
+
+#[must_use]
+pub fn unknown_variant(edn: &Edn, name: &str) -> Error {
+    Error::Deserialize(format!("couldn't convert `{edn}` into `{name}`"))
+}
+
+/// Writes the entries of `flattened` into the surrounding map, the same way derived structs write
+/// their fields, leaving out the `keys` the struct's own fields already wrote.
+///
+/// # Errors
+///
+/// Any error from `w`.
+pub fn write_flattened(w: &mut dyn Write, flattened: &Map, keys: &[&str]) -> fmt::Result {
+    flattened
+        .iter()
+        .filter(|(k, _)| !keys.contains(&k.as_str()))
+        .try_for_each(|(k, v)| write!(w, "{k} {v}, "))
+}
+
+/// The `Edn` that `serialize_into` writes for a field with `#[edn(with = "module")]`, read back
+/// from its text as `module` can only write the field. `nil` if it doesn't write valid EDN.
+pub fn to_edn_with(serialize_into: impl FnOnce(&mut String) -> fmt::Result) -> Edn {
+    let mut text = String::new();
+    serialize_into(&mut text)
+        .ok()
+        .and_then(|()| Edn::from_str(&text).ok())
+        .unwrap_or(Edn::Nil)
+}
+
+/// Adds the entries of `flattened` to `map`, keeping the entries `map` already has.
+pub fn flatten_into(map: &mut Map, flattened: Map) {
+    for (k, v) in flattened {
+        map.entry(k).or_insert(v);
+    }
+}
@@ -0,0 +1,442 @@
+//! Specter style navigation to select or transform many values of an `Edn` at once.
+//!
+//! A `Query` is a list of navigators applied in order, each one moving from every value reached so
+//! far to zero or more values inside it:
+//!
+//! * `ALL` navigates to every element of a vector, list or set, and every value of a map.
+//! * `keypath(k)` navigates to the value under key `k` of a map, index `k` of a vector or list, or
+//!   the element `k` of a set. Missing keys are skipped.
+//! * `nthpath(n)` navigates to index `n` of a vector or list.
+//! * `filterer(pred)` navigates to a vector of the elements of a vector, list or set that match
+//!   `pred`. Transforming it writes the new elements back in place of the matching ones.
+//! * `walker(pred)` navigates to every value in the tree that matches `pred`, without looking
+//!   inside matching values.
+//!
+//! Queries can also be written as EDN, so they can be kept in configuration files. Keywords,
+//! strings and numbers are keypaths, `ALL` is `ALL`, and the other navigators are lists like
+//! `(nthpath 0)`, `(filterer :tag :sale)` or `(walker number?)`. See `Query::from_str` for the
+//! predicates they accept.
+//!
+//! `pull` projects maps through a Datomic style pull pattern instead, to trim them down to the keys
+//! a caller asked for.
+//! ```rust
+//! use edn_rs::query::{self, filterer, keypath, Query, ALL};
+//! use edn_rs::Edn;
+//! use std::str::FromStr;
+//!
+//! let edn = Edn::from_str("{:items [{:tag :sale :price 10} {:tag :new :price 20} {:tag :sale :price 30}]}").unwrap();
+//!
+//! let on_sale = Query::from(vec![
+//!     keypath(Edn::Key(":items".into())),
+//!     filterer(|item| item[":tag"] == Edn::Key(":sale".into())),
+//!     ALL,
+//!     keypath(Edn::Key(":price".into())),
+//! ]);
+//! assert_eq!(query::select(&on_sale, &edn), vec![Edn::UInt(10), Edn::UInt(30)]);
+//!
+//! let on_sale = Query::from_str("[:items (filterer :tag :sale) ALL :price]").unwrap();
+//! let mut edn = edn;
+//! query::transform(&on_sale, &mut edn, |price| Edn::from(price.to_uint().unwrap() / 2)).unwrap();
+//! assert_eq!(edn, Edn::from_str("{:items [{:tag :sale :price 5} {:tag :new :price 20} {:tag :sale :price 15}]}").unwrap());
+//! ```
+
+use alloc::boxed::Box;
+use alloc::collections::BTreeSet;
+use alloc::format;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::edn::{Edn, Error, Vector};
+
+mod parse;
+mod pull;
+
+pub use pull::pull;
+
+/// `Pred` is a test applied to values by `filterer` and `walker`.
+pub struct Pred(Box<dyn Fn(&Edn) -> bool>);
+
+impl Pred {
+    pub fn new(pred: impl Fn(&Edn) -> bool + 'static) -> Self {
+        Self(Box::new(pred))
+    }
+
+    #[must_use]
+    pub fn test(&self, edn: &Edn) -> bool {
+        (self.0)(edn)
+    }
+}
+
+impl fmt::Debug for Pred {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Pred")
+    }
+}
+
+/// A single step of a `Query`. See the module documentation for what each one navigates to.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Navigator {
+    All,
+    Keypath(Edn),
+    Nthpath(usize),
+    Filterer(Pred),
+    Walker(Pred),
+}
+
+pub const ALL: Navigator = Navigator::All;
+
+pub fn keypath(key: impl Into<Edn>) -> Navigator {
+    Navigator::Keypath(key.into())
+}
+
+#[must_use]
+pub const fn nthpath(n: usize) -> Navigator {
+    Navigator::Nthpath(n)
+}
+
+pub fn filterer(pred: impl Fn(&Edn) -> bool + 'static) -> Navigator {
+    Navigator::Filterer(Pred::new(pred))
+}
+
+pub fn walker(pred: impl Fn(&Edn) -> bool + 'static) -> Navigator {
+    Navigator::Walker(Pred::new(pred))
+}
+
+/// `Query` is a sequence of navigators, built from a `Vec<Navigator>` or parsed from EDN text.
+#[derive(Debug, Default)]
+pub struct Query(Vec<Navigator>);
+
+impl Query {
+    #[must_use]
+    pub const fn new(navigators: Vec<Navigator>) -> Self {
+        Self(navigators)
+    }
+
+    #[must_use]
+    pub fn navigators(&self) -> &[Navigator] {
+        &self.0
+    }
+}
+
+impl From<Vec<Navigator>> for Query {
+    fn from(navigators: Vec<Navigator>) -> Self {
+        Self(navigators)
+    }
+}
+
+fn index(key: &Edn) -> Option<usize> {
+    match key {
+        Edn::UInt(u) => usize::try_from(*u).ok(),
+        Edn::Int(i) => usize::try_from(*i).ok(),
+        _ => None,
+    }
+}
+
+fn lookup<'e>(edn: &'e Edn, key: &Edn) -> Option<&'e Edn> {
+    match edn {
+        Edn::Map(m) => m.0.get(key.to_string().as_str()),
+        Edn::Vector(v) => index(key).and_then(|i| v.0.get(i)),
+        Edn::List(l) => index(key).and_then(|i| l.0.get(i)),
+        Edn::Set(s) => s.0.get(key),
+        _ => None,
+    }
+}
+
+fn lookup_mut<'e>(edn: &'e mut Edn, key: &Edn) -> Option<&'e mut Edn> {
+    match edn {
+        Edn::Map(m) => m.0.get_mut(key.to_string().as_str()),
+        Edn::Vector(v) => index(key).and_then(|i| v.0.get_mut(i)),
+        Edn::List(l) => index(key).and_then(|i| l.0.get_mut(i)),
+        _ => None,
+    }
+}
+
+fn children(edn: &Edn) -> Vec<&Edn> {
+    match edn {
+        Edn::Vector(v) => v.0.iter().collect(),
+        Edn::List(l) => l.0.iter().collect(),
+        Edn::Set(s) => s.0.iter().collect(),
+        Edn::Map(m) => m.0.values().collect(),
+        _ => Vec::new(),
+    }
+}
+
+// Sets are rebuilt after their elements change, since a changed element may sort elsewhere.
+fn each_child_mut(
+    edn: &mut Edn,
+    f: &mut dyn FnMut(&mut Edn) -> Result<(), Error>,
+) -> Result<(), Error> {
+    match edn {
+        Edn::Vector(v) => v.0.iter_mut().try_for_each(f),
+        Edn::List(l) => l.0.iter_mut().try_for_each(f),
+        Edn::Map(m) => m.0.values_mut().try_for_each(f),
+        Edn::Set(s) => {
+            let mut elements = core::mem::take(&mut *s.0).into_iter().collect::<Vec<_>>();
+            let result = elements.iter_mut().try_for_each(f);
+            *s.0 = elements.into_iter().collect();
+            result
+        }
+        _ => Ok(()),
+    }
+}
+
+fn select_into(navigators: &[Navigator], edn: &Edn, out: &mut Vec<Edn>) {
+    let Some((navigator, rest)) = navigators.split_first() else {
+        out.push(edn.clone());
+        return;
+    };
+    match navigator {
+        Navigator::All => {
+            for child in children(edn) {
+                select_into(rest, child, out);
+            }
+        }
+        Navigator::Keypath(key) => {
+            if let Some(child) = lookup(edn, key) {
+                select_into(rest, child, out);
+            }
+        }
+        Navigator::Nthpath(n) => {
+            if let Edn::Vector(_) | Edn::List(_) = edn {
+                if let Some(child) = lookup(edn, &Edn::from(*n)) {
+                    select_into(rest, child, out);
+                }
+            }
+        }
+        Navigator::Filterer(pred) => {
+            if let Edn::Vector(_) | Edn::List(_) | Edn::Set(_) = edn {
+                let matches = children(edn)
+                    .into_iter()
+                    .filter(|e| pred.test(e))
+                    .cloned()
+                    .collect();
+                select_into(rest, &Edn::Vector(Vector::new(matches)), out);
+            }
+        }
+        Navigator::Walker(pred) => walk(edn, pred, &mut |e| select_into(rest, e, out)),
+    }
+}
+
+fn walk(edn: &Edn, pred: &Pred, f: &mut dyn FnMut(&Edn)) {
+    if pred.test(edn) {
+        f(edn);
+    } else if let Edn::Tagged(t) = edn {
+        walk(t.value(), pred, f);
+    } else {
+        for child in children(edn) {
+            walk(child, pred, f);
+        }
+    }
+}
+
+fn walk_mut(
+    edn: &mut Edn,
+    pred: &Pred,
+    f: &mut dyn FnMut(&mut Edn) -> Result<(), Error>,
+) -> Result<(), Error> {
+    if pred.test(edn) {
+        f(edn)
+    } else if let Edn::Tagged(t) = edn {
+        walk_mut(t.value_mut(), pred, f)
+    } else {
+        each_child_mut(edn, &mut |child| walk_mut(child, pred, f))
+    }
+}
+
+fn filterer_mut(
+    edn: &mut Edn,
+    pred: &Pred,
+    f: &mut dyn FnMut(&mut Edn) -> Result<(), Error>,
+) -> Result<(), Error> {
+    let elements = match edn {
+        Edn::Vector(v) => &mut *v.0,
+        Edn::List(l) => &mut *l.0,
+        Edn::Set(s) => {
+            let (matches, rest): (BTreeSet<_>, _) = core::mem::take(&mut *s.0)
+                .into_iter()
+                .partition(|e| pred.test(e));
+            let mut selected = Edn::Vector(Vector::new(matches.iter().cloned().collect()));
+            let result = f(&mut selected);
+            *s.0 = rest;
+            return match (result, selected) {
+                (Ok(()), Edn::Vector(v)) if v.0.len() == matches.len() => {
+                    s.0.extend(v.to_vec());
+                    Ok(())
+                }
+                (result, other) => {
+                    let error = filterer_mismatch(matches.len(), &other);
+                    s.0.extend(matches);
+                    result.and(Err(error))
+                }
+            };
+        }
+        _ => return Ok(()),
+    };
+    let positions = (0..elements.len())
+        .filter(|i| pred.test(&elements[*i]))
+        .collect::<Vec<_>>();
+    let mut selected = Edn::Vector(Vector::new(
+        positions.iter().map(|i| elements[*i].clone()).collect(),
+    ));
+    f(&mut selected)?;
+    match selected {
+        Edn::Vector(v) if v.0.len() == positions.len() => {
+            for (i, e) in positions.into_iter().zip(v.to_vec()) {
+                elements[i] = e;
+            }
+            Ok(())
+        }
+        other => Err(filterer_mismatch(positions.len(), &other)),
+    }
+}
+
+fn filterer_mismatch(selected: usize, found: &Edn) -> Error {
+    Error::Query(format!(
+        "filterer expected a vector of {selected} elements back, found {found}"
+    ))
+}
+
+fn transform_in(
+    navigators: &[Navigator],
+    edn: &mut Edn,
+    f: &mut dyn FnMut(Edn) -> Edn,
+) -> Result<(), Error> {
+    let Some((navigator, rest)) = navigators.split_first() else {
+        *edn = f(core::mem::replace(edn, Edn::Nil));
+        return Ok(());
+    };
+    match navigator {
+        Navigator::All => each_child_mut(edn, &mut |child| transform_in(rest, child, f)),
+        Navigator::Keypath(key) => {
+            lookup_mut(edn, key).map_or(Ok(()), |child| transform_in(rest, child, f))
+        }
+        Navigator::Nthpath(n) => match edn {
+            Edn::Vector(_) | Edn::List(_) => {
+                lookup_mut(edn, &Edn::from(*n)).map_or(Ok(()), |child| transform_in(rest, child, f))
+            }
+            _ => Ok(()),
+        },
+        Navigator::Filterer(pred) => filterer_mut(edn, pred, &mut |e| transform_in(rest, e, f)),
+        Navigator::Walker(pred) => walk_mut(edn, pred, &mut |e| transform_in(rest, e, f)),
+    }
+}
+
+/// Returns a copy of every value `query` navigates to in `edn`, in order.
+#[must_use]
+pub fn select(query: &Query, edn: &Edn) -> Vec<Edn> {
+    let mut out = Vec::new();
+    select_into(&query.0, edn, &mut out);
+    out
+}
+
+/// Replaces every value `query` navigates to in `edn` with `f` applied to it.
+///
+/// # Errors
+///
+/// `Error::Query` if a transform through `filterer` doesn't give back a vector with as many
+/// elements as were selected. Values transformed before the error keep their new value.
+pub fn transform(query: &Query, edn: &mut Edn, mut f: impl FnMut(Edn) -> Edn) -> Result<(), Error> {
+    transform_in(&query.0, edn, &mut f)
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::vec;
+    use core::str::FromStr;
+
+    use super::*;
+
+    fn edn(s: &str) -> Edn {
+        Edn::from_str(s).unwrap()
+    }
+
+    fn key(k: &str) -> Edn {
+        Edn::Key(k.into())
+    }
+
+    #[test]
+    fn all_visits_sequences_sets_and_map_values() {
+        let query = Query::from(vec![ALL, ALL]);
+
+        assert_eq!(
+            select(&query, &edn("[[1 2] #{3} {:a 4} 5]")),
+            vec![Edn::UInt(1), Edn::UInt(2), Edn::UInt(3), Edn::UInt(4)]
+        );
+    }
+
+    #[test]
+    fn keypath_and_nthpath_skip_missing_values() {
+        let data = edn("{:a [{:b 1} {:c 2}]}");
+
+        assert_eq!(
+            select(
+                &Query::from(vec![keypath(key(":a")), ALL, keypath(key(":b"))]),
+                &data
+            ),
+            vec![Edn::UInt(1)]
+        );
+        assert_eq!(
+            select(&Query::from(vec![keypath(key(":a")), nthpath(1)]), &data),
+            vec![edn("{:c 2}")]
+        );
+        assert!(select(&Query::from(vec![nthpath(0)]), &data).is_empty());
+    }
+
+    #[test]
+    fn filterer_transforms_write_back_in_place() {
+        let mut data = edn("[1 2 3 4 5]");
+        let odd = Query::from(vec![filterer(|e| e.to_uint().is_some_and(|n| n % 2 == 1))]);
+
+        assert_eq!(select(&odd, &data), vec![edn("[1 3 5]")]);
+        transform(&odd, &mut data, |odds| {
+            let Edn::Vector(odds) = odds else {
+                unreachable!()
+            };
+            Edn::Vector(Vector::new(odds.to_vec().into_iter().rev().collect()))
+        })
+        .unwrap();
+        assert_eq!(data, edn("[5 2 3 4 1]"));
+
+        assert!(transform(&odd, &mut data, |_| Edn::Nil).is_err());
+    }
+
+    #[test]
+    fn filterer_transforms_sets_only_with_vectors() {
+        let mut data = edn("#{1 2 3}");
+        let odd = Query::from(vec![filterer(|e| e.to_uint().is_some_and(|n| n % 2 == 1))]);
+
+        transform(&odd, &mut data, |_| edn("[5 7]")).unwrap();
+        assert_eq!(data, edn("#{2 5 7}"));
+
+        assert!(transform(&odd, &mut data, |_| Edn::UInt(9)).is_err());
+        assert!(transform(&odd, &mut data, |_| edn("[9]")).is_err());
+        assert_eq!(data, edn("#{2 5 7}"));
+    }
+
+    #[test]
+    fn walker_stops_at_matches() {
+        let mut data = edn("{:a 1 :b [2 #{3}] :c #inst \"x\" :d {:e 4}}");
+        let numbers = Query::from(vec![walker(|e| matches!(e, Edn::UInt(_)))]);
+
+        let mut found = select(&numbers, &data);
+        found.sort();
+        assert_eq!(
+            found,
+            vec![Edn::UInt(1), Edn::UInt(2), Edn::UInt(3), Edn::UInt(4)]
+        );
+
+        let maps = Query::from(vec![
+            walker(|e| matches!(e, Edn::Map(_))),
+            keypath(key(":e")),
+        ]);
+        assert!(select(&maps, &data).is_empty());
+
+        transform(&numbers, &mut data, |n| {
+            Edn::from(n.to_uint().unwrap() * 10)
+        })
+        .unwrap();
+        assert_eq!(data, edn("{:a 10 :b [20 #{30}] :c #inst \"x\" :d {:e 40}}"));
+    }
+}
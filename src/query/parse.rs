@@ -0,0 +1,166 @@
+use alloc::format;
+use alloc::vec::Vec;
+use core::str::FromStr;
+
+use crate::edn::{Edn, Error, List, Vector};
+
+use super::{lookup, Navigator, Pred, Query};
+
+impl FromStr for Query {
+    type Err = Error;
+
+    /// Parses an EDN vector or list of navigators, like `[:items (filterer :tag :sale) ALL :price]`.
+    ///
+    /// * `ALL` is `ALL`, and keywords, strings and numbers are keypaths.
+    /// * `(keypath k)` and `(nthpath n)` are `keypath(k)` and `nthpath(n)`.
+    /// * `(filterer ...)` and `(walker ...)` take a predicate written as a type test like `number?`
+    ///   or `map?`, a key like `:active?` that must hold a value other than `nil` or `false`, or a
+    ///   key and a value like `:tag :sale` that must be equal.
+    ///
+    /// # Errors
+    ///
+    /// `Error::ParseEdn` if `s` isn't valid EDN, `Error::Type` if it isn't a vector or list, and
+    /// `Error::Query` for an unknown navigator or predicate.
+    fn from_str(s: &str) -> Result<Self, Error> {
+        let navigators = match Edn::from_str(s)? {
+            Edn::Vector(v) => v.to_vec(),
+            Edn::List(l) => l.to_vec(),
+            edn => {
+                return Err(Error::Type {
+                    expected: "vector",
+                    found: edn.type_name(),
+                })
+            }
+        };
+        navigators
+            .into_iter()
+            .map(navigator)
+            .collect::<Result<Vec<_>, _>>()
+            .map(Self)
+    }
+}
+
+fn navigator(edn: Edn) -> Result<Navigator, Error> {
+    match edn {
+        Edn::Symbol(s) if s.as_str() == "ALL" => Ok(Navigator::All),
+        Edn::List(l) => {
+            let form = l.to_vec();
+            let Some((Edn::Symbol(name), args)) = form.split_first() else {
+                return Err(unknown("navigator", &Edn::List(List::new(form))));
+            };
+            match (name.as_str(), args) {
+                ("keypath", [key]) => Ok(Navigator::Keypath(key.clone())),
+                ("nthpath", [n]) => usize::try_from(n).map(Navigator::Nthpath),
+                ("filterer", args) => pred(args).map(Navigator::Filterer),
+                ("walker", args) => pred(args).map(Navigator::Walker),
+                _ => Err(unknown("navigator", &Edn::List(List::new(form.clone())))),
+            }
+        }
+        Edn::Symbol(_) | Edn::Vector(_) | Edn::Set(_) | Edn::Map(_) | Edn::Tagged(_) => {
+            Err(unknown("navigator", &edn))
+        }
+        key => Ok(Navigator::Keypath(key)),
+    }
+}
+
+fn pred(args: &[Edn]) -> Result<Pred, Error> {
+    match args {
+        [Edn::Symbol(name)] => {
+            let test: fn(&Edn) -> bool = match name.as_str() {
+                "nil?" => |e| matches!(e, Edn::Nil),
+                "boolean?" => |e| matches!(e, Edn::Bool(_)),
+                "number?" => |e| {
+                    matches!(
+                        e,
+                        Edn::Int(_) | Edn::UInt(_) | Edn::Double(_) | Edn::Rational(_)
+                    )
+                },
+                "integer?" => |e| matches!(e, Edn::Int(_) | Edn::UInt(_)),
+                "string?" => |e| matches!(e, Edn::Str(_)),
+                "keyword?" => |e| matches!(e, Edn::Key(_)),
+                "symbol?" => |e| matches!(e, Edn::Symbol(_)),
+                "char?" => |e| matches!(e, Edn::Char(_)),
+                "vector?" => |e| matches!(e, Edn::Vector(_)),
+                "list?" => |e| matches!(e, Edn::List(_)),
+                "set?" => |e| matches!(e, Edn::Set(_)),
+                "map?" => |e| matches!(e, Edn::Map(_)),
+                "tagged?" => |e| matches!(e, Edn::Tagged(_)),
+                _ => return Err(unknown("predicate", &Edn::Symbol(name.clone()))),
+            };
+            Ok(Pred::new(test))
+        }
+        [key] => {
+            let key = key.clone();
+            Ok(Pred::new(move |e| {
+                !matches!(lookup(e, &key), None | Some(Edn::Nil | Edn::Bool(false)))
+            }))
+        }
+        [key, value] => {
+            let (key, value) = (key.clone(), value.clone());
+            Ok(Pred::new(move |e| lookup(e, &key) == Some(&value)))
+        }
+        _ => Err(Error::Query(format!(
+            "expected a predicate, found {}",
+            Vector::new(args.to_vec())
+        ))),
+    }
+}
+
+fn unknown(what: &str, edn: &Edn) -> Error {
+    Error::Query(format!("unknown {what} {edn}"))
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::string::ToString;
+    use alloc::vec;
+
+    use super::*;
+    use crate::query::select;
+
+    fn edn(s: &str) -> Edn {
+        Edn::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn navigators_read_from_edn() {
+        let data = edn("{:users [{:name \"Ana\" :admin? true :id 1} {:name \"Bo\" :id 2.5}]}");
+        let select_str = |q: &str| select(&Query::from_str(q).unwrap(), &data);
+
+        assert_eq!(
+            select_str("[:users ALL :name]"),
+            vec![edn("\"Ana\""), edn("\"Bo\"")]
+        );
+        assert_eq!(
+            select_str("[:users (nthpath 1) (keypath :name)]"),
+            vec![edn("\"Bo\"")]
+        );
+        assert_eq!(select_str("[:users 0 :id]"), vec![Edn::UInt(1)]);
+        assert_eq!(
+            select_str("[:users (filterer :admin?) ALL :name]"),
+            vec![edn("\"Ana\"")]
+        );
+        assert_eq!(
+            select_str("[:users (filterer :name \"Bo\") ALL :id]"),
+            vec![Edn::from(2.5)]
+        );
+        assert_eq!(select_str("((walker integer?))"), vec![Edn::UInt(1)]);
+    }
+
+    #[test]
+    fn unknown_forms_are_errors() {
+        assert_eq!(
+            Query::from_str("[(selected? :a)]").unwrap_err(),
+            Error::Query("unknown navigator (selected? :a)".to_string())
+        );
+        assert_eq!(
+            Query::from_str("[(walker odd?)]").unwrap_err(),
+            Error::Query("unknown predicate odd?".to_string())
+        );
+        assert!(matches!(
+            Query::from_str("[ALL {:a 1}]"),
+            Err(Error::Query(_))
+        ));
+        assert!(matches!(Query::from_str(":a"), Err(Error::Type { .. })));
+    }
+}
@@ -0,0 +1,241 @@
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::str::FromStr;
+
+use crate::edn::{Atom, Edn, Error, List, Set, Vector};
+
+// A compiled pattern entry. Joins keep their own sub-pattern.
+enum Spec {
+    Wildcard,
+    Attr {
+        key: Edn,
+        name: String,
+        default: Option<Edn>,
+        limit: Option<usize>,
+        join: Option<Vec<Self>>,
+    },
+}
+
+fn invalid(what: &str, edn: &Edn) -> Error {
+    Error::Query(format!("invalid {what} {edn}"))
+}
+
+fn pattern(edn: &Edn) -> Result<Vec<Spec>, Error> {
+    let specs = match edn {
+        Edn::Vector(v) => v.0.iter(),
+        Edn::List(l) => l.0.iter(),
+        _ => return Err(invalid("pattern", edn)),
+    };
+    let mut compiled = Vec::new();
+    for spec in specs {
+        match spec {
+            Edn::Symbol(s) if s.as_str() == "*" => compiled.push(Spec::Wildcard),
+            Edn::Map(joins) => {
+                for (attr, sub) in joins.0.iter() {
+                    compiled.push(attribute(&Edn::from_str(attr)?, Some(pattern(sub)?))?);
+                }
+            }
+            attr => compiled.push(attribute(attr, None)?),
+        }
+    }
+    Ok(compiled)
+}
+
+// Reads `:key` or an attribute expression like `(:key :as :alias :default 0 :limit 10)`.
+fn attribute(edn: &Edn, join: Option<Vec<Spec>>) -> Result<Spec, Error> {
+    let (key, options) = match edn {
+        Edn::List(List(v)) | Edn::Vector(Vector(v)) => match v.split_first() {
+            Some((key, options)) if options.len() % 2 == 0 => (key, options),
+            _ => return Err(invalid("attribute", edn)),
+        },
+        Edn::Symbol(_) | Edn::Map(_) | Edn::Set(_) | Edn::Tagged(_) | Edn::Nil | Edn::Empty => {
+            return Err(invalid("attribute", edn))
+        }
+        key => (key, &[][..]),
+    };
+    let mut name = key.to_string();
+    let (mut default, mut limit) = (None, None);
+    for option in options.chunks(2) {
+        match (option[0].to_string().as_str(), &option[1]) {
+            (":as", alias) => name = alias.to_string(),
+            (":default", value) => default = Some(value.clone()),
+            (":limit", Edn::Nil) => limit = None,
+            (":limit", n) => limit = Some(usize::try_from(n).map_err(|_| invalid("limit", n))?),
+            _ => return Err(invalid("attribute option", &option[0])),
+        }
+    }
+    Ok(Spec::Attr {
+        key: key.clone(),
+        name,
+        default,
+        limit,
+        join,
+    })
+}
+
+fn limited(value: &Edn, limit: Option<usize>) -> Edn {
+    let Some(n) = limit else {
+        return value.clone();
+    };
+    match value {
+        Edn::Vector(v) => Edn::Vector(Vector::new(v.0.iter().take(n).cloned().collect())),
+        Edn::List(l) => Edn::List(List::new(l.0.iter().take(n).cloned().collect())),
+        Edn::Set(s) => Edn::Set(Set::new(s.0.iter().take(n).cloned().collect())),
+        value => value.clone(),
+    }
+}
+
+// Maps are projected, collections are projected element by element and anything else is kept.
+fn project(edn: &Edn, specs: &[Spec]) -> Edn {
+    match edn {
+        Edn::Map(m) => Edn::Map(project_map(&m.0, specs).into()),
+        Edn::Vector(v) => Edn::Vector(Vector::new(v.0.iter().map(|e| project(e, specs)).collect())),
+        Edn::List(l) => Edn::List(List::new(l.0.iter().map(|e| project(e, specs)).collect())),
+        Edn::Set(s) => Edn::Set(Set::new(s.0.iter().map(|e| project(e, specs)).collect())),
+        edn => edn.clone(),
+    }
+}
+
+fn project_map(map: &BTreeMap<Atom, Edn>, specs: &[Spec]) -> BTreeMap<Atom, Edn> {
+    let mut pulled = BTreeMap::new();
+    for spec in specs {
+        match spec {
+            // Keys already pulled by a join or an attribute expression keep what it pulled.
+            Spec::Wildcard => {
+                for (k, v) in map {
+                    pulled.entry(k.clone()).or_insert_with(|| v.clone());
+                }
+            }
+            Spec::Attr {
+                key,
+                name,
+                default,
+                limit,
+                join,
+            } => {
+                let Some(value) = map.get(key.to_string().as_str()).or(default.as_ref()) else {
+                    continue;
+                };
+                let value = limited(value, *limit);
+                let value = match join {
+                    Some(join) => project(&value, join),
+                    None => value,
+                };
+                pulled.insert(name.as_str().into(), value);
+            }
+        }
+    }
+    pulled
+}
+
+/// Projects `edn` through a Datomic style pull `pattern`, keeping only the attributes it names.
+///
+/// A pattern is a vector of:
+/// * keys, like `:name`, kept when present;
+/// * `*`, which keeps every key, except that keys pulled by a join keep what the join pulled
+///   wherever `*` is in the pattern;
+/// * attribute expressions like `(:email :default "n/a")`, `(:tags :limit 2)` or
+///   `(:name :as :full-name)`, which can be combined;
+/// * joins like `{:friends [:name]}`, which pull the value under a key with a nested pattern.
+///   Their key can be an attribute expression too, like `{(:friends :limit 2) [:name]}`.
+///
+/// Missing keys without a default are left out. Vectors, lists and sets are pulled element by
+/// element, and values that aren't maps or collections are kept as they are.
+/// ```rust
+/// use edn_rs::query::pull;
+/// use edn_rs::Edn;
+/// use std::str::FromStr;
+///
+/// let person = Edn::from_str("{:name \"Ana\" :age 30 :friends [{:name \"Bo\" :age 25 :email \"bo@x\"}]}").unwrap();
+/// let pattern = Edn::from_str("[:name {:friends [:name :age]} (:email :default \"n/a\")]").unwrap();
+///
+/// assert_eq!(
+///     pull(&person, &pattern).unwrap(),
+///     Edn::from_str("{:name \"Ana\" :friends [{:name \"Bo\" :age 25}] :email \"n/a\"}").unwrap()
+/// );
+/// ```
+///
+/// # Errors
+///
+/// `Error::Query` if `pattern` isn't a valid pull pattern, and `Error::Type` if `edn` isn't a map,
+/// vector, list or set.
+pub fn pull(edn: &Edn, pattern: &Edn) -> Result<Edn, Error> {
+    let specs = self::pattern(pattern)?;
+    match edn {
+        Edn::Map(_) | Edn::Vector(_) | Edn::List(_) | Edn::Set(_) => Ok(project(edn, &specs)),
+        _ => Err(Error::Type {
+            expected: "map",
+            found: edn.type_name(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn edn(s: &str) -> Edn {
+        Edn::from_str(s).unwrap()
+    }
+
+    fn users() -> Edn {
+        edn("[{:id 1 :name \"Ana\" :tags [:a :b :c] :friends [{:id 2 :name \"Bo\"} {:id 3 :name \"Cy\"}]}
+              {:id 2 :name \"Bo\"}]")
+    }
+
+    #[test]
+    fn wildcard_keeps_everything_and_joins_refine_it() {
+        assert_eq!(
+            pull(&edn("{:a 1 :b {:c 2 :d 3}}"), &edn("[*]")),
+            Ok(edn("{:a 1 :b {:c 2 :d 3}}"))
+        );
+        assert_eq!(
+            pull(&edn("{:a 1 :b {:c 2 :d 3}}"), &edn("[* {:b [:c]}]")),
+            Ok(edn("{:a 1 :b {:c 2}}"))
+        );
+        assert_eq!(
+            pull(&edn("{:a 1 :b {:c 2 :d 3}}"), &edn("[{:b [:c]} *]")),
+            Ok(edn("{:a 1 :b {:c 2}}"))
+        );
+    }
+
+    #[test]
+    fn limits_renames_and_defaults() {
+        assert_eq!(
+            pull(
+                &users(),
+                &edn("[(:name :as :user) (:tags :limit 2) {(:friends :limit 1 :as :pals) [:name]} (:age :default 0)]")
+            ),
+            Ok(edn("[{:user \"Ana\" :tags [:a :b] :pals [{:name \"Bo\"}] :age 0} {:user \"Bo\" :age 0}]"))
+        );
+        assert_eq!(
+            pull(&users(), &edn("[[:tags :limit nil] :missing]")),
+            Ok(edn("[{:tags [:a :b :c]} {}]"))
+        );
+    }
+
+    #[test]
+    fn invalid_patterns_and_values_are_errors() {
+        assert_eq!(
+            pull(&users(), &edn("[(:name :as)]")),
+            Err(Error::Query("invalid attribute (:name :as)".to_string()))
+        );
+        assert_eq!(
+            pull(&users(), &edn("[(:name :limit -1)]")),
+            Err(Error::Query("invalid limit -1".to_string()))
+        );
+        assert_eq!(
+            pull(&users(), &edn(":name")),
+            Err(Error::Query("invalid pattern :name".to_string()))
+        );
+        assert_eq!(
+            pull(&Edn::UInt(1), &edn("[:name]")),
+            Err(Error::Type {
+                expected: "map",
+                found: "integer"
+            })
+        );
+    }
+}
@@ -0,0 +1,228 @@
+use alloc::collections::btree_map;
+use alloc::string::{String, ToString};
+
+use ::serde::de::{self, IntoDeserializer, Visitor};
+use ::serde::forward_to_deserialize_any;
+
+use crate::edn::{Atom, Edn, Error};
+
+/// `Deserializer` reads any `serde::Deserialize` type out of an `Edn`. See
+/// `edn_rs::serde::from_edn` for how EDN values are mapped.
+#[derive(Debug, Clone, Copy)]
+pub struct Deserializer<'a> {
+    edn: &'a Edn,
+}
+
+impl<'a> Deserializer<'a> {
+    #[must_use]
+    pub const fn new(edn: &'a Edn) -> Self {
+        Self { edn }
+    }
+}
+
+struct SeqAccess<I> {
+    items: I,
+}
+
+impl<'de, 'a, I: ExactSizeIterator<Item = &'a Edn>> de::SeqAccess<'de> for SeqAccess<I> {
+    type Error = Error;
+
+    fn next_element_seed<T: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Error> {
+        self.items
+            .next()
+            .map(|edn| seed.deserialize(Deserializer::new(edn)))
+            .transpose()
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.items.len())
+    }
+}
+
+struct MapAccess<'a> {
+    entries: btree_map::Iter<'a, Atom, Edn>,
+    value: Option<&'a Edn>,
+}
+
+impl<'de> de::MapAccess<'de> for MapAccess<'_> {
+    type Error = Error;
+
+    fn next_key_seed<K: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Error> {
+        let Some((key, value)) = self.entries.next() else {
+            return Ok(None);
+        };
+        self.value = Some(value);
+        seed.deserialize(Deserializer::new(&Edn::read_key(key)))
+            .map(Some)
+    }
+
+    fn next_value_seed<V: de::DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Error> {
+        let value = self
+            .value
+            .take()
+            .ok_or_else(|| Error::Deserialize("map value without a key".to_string()))?;
+        seed.deserialize(Deserializer::new(value))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.entries.len())
+    }
+}
+
+const fn not_a_variant(edn: &Edn) -> Error {
+    Error::Type {
+        expected: "enum variant",
+        found: edn.type_name(),
+    }
+}
+
+struct EnumAccess<'a> {
+    variant: String,
+    value: Option<&'a Edn>,
+}
+
+impl<'de, 'a> de::EnumAccess<'de> for EnumAccess<'a> {
+    type Error = Error;
+    type Variant = VariantAccess<'a>;
+
+    fn variant_seed<V: de::DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, VariantAccess<'a>), Error> {
+        let variant =
+            seed.deserialize(IntoDeserializer::<Error>::into_deserializer(self.variant))?;
+        Ok((variant, VariantAccess { value: self.value }))
+    }
+}
+
+struct VariantAccess<'a> {
+    value: Option<&'a Edn>,
+}
+
+impl VariantAccess<'_> {
+    fn value(&self, expected: &'static str) -> Result<&Edn, Error> {
+        self.value.ok_or(Error::Type {
+            expected,
+            found: "keyword",
+        })
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for VariantAccess<'_> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Error> {
+        match self.value {
+            None | Some(Edn::Nil) => Ok(()),
+            Some(edn) => Err(Error::Type {
+                expected: "unit variant",
+                found: edn.type_name(),
+            }),
+        }
+    }
+
+    fn newtype_variant_seed<T: de::DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, Error> {
+        seed.deserialize(Deserializer::new(self.value("newtype variant")?))
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value, Error> {
+        de::Deserializer::deserialize_any(Deserializer::new(self.value("tuple variant")?), visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        de::Deserializer::deserialize_any(Deserializer::new(self.value("struct variant")?), visitor)
+    }
+}
+
+impl<'de> de::Deserializer<'de> for Deserializer<'_> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.edn {
+            Edn::Bool(b) => visitor.visit_bool(*b),
+            Edn::Int(i) => visitor.visit_i64(*i),
+            Edn::UInt(u) => visitor.visit_u64(*u),
+            Edn::Double(_) | Edn::Rational(_) => {
+                visitor.visit_f64(self.edn.to_float().unwrap_or(f64::NAN))
+            }
+            Edn::Char(c) => visitor.visit_char(*c),
+            Edn::Str(s) => visitor.visit_str(s),
+            Edn::Key(k) => visitor.visit_str(k.strip_prefix(':').unwrap_or(k)),
+            Edn::Symbol(s) => visitor.visit_str(s),
+            Edn::Vector(v) => visitor.visit_seq(SeqAccess { items: v.iter() }),
+            Edn::List(l) => visitor.visit_seq(SeqAccess { items: l.iter() }),
+            Edn::Set(s) => visitor.visit_seq(SeqAccess { items: s.iter() }),
+            Edn::Map(m) => visitor.visit_map(MapAccess {
+                entries: m.iter(),
+                value: None,
+            }),
+            Edn::Tagged(t) => {
+                de::Deserializer::deserialize_any(Deserializer::new(t.value()), visitor)
+            }
+            _ => visitor.visit_unit(),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.edn {
+            Edn::Nil => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        let (variant, value) = match self.edn {
+            Edn::Key(k) => (k.strip_prefix(':').unwrap_or(k).to_string(), None),
+            Edn::Symbol(s) => (s.to_string(), None),
+            Edn::Str(s) => (s.to_string(), None),
+            Edn::Tagged(t) => (t.tag().to_string(), Some(t.value())),
+            Edn::Map(m) => match (m.len(), m.iter().next()) {
+                (1, Some((key, value))) => (
+                    de::Deserialize::deserialize(Deserializer::new(&Edn::read_key(key)))?,
+                    Some(value),
+                ),
+                _ => return Err(not_a_variant(self.edn)),
+            },
+            edn => return Err(not_a_variant(edn)),
+        };
+        visitor.visit_enum(EnumAccess { variant, value })
+    }
+
+    // Integers out of the 64 bit range are read from the strings `to_edn` writes for them.
+    fn deserialize_i128<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_i128(crate::from_edn(self.edn)?)
+    }
+
+    fn deserialize_u128<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_u128(crate::from_edn(self.edn)?)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
@@ -0,0 +1,286 @@
+use alloc::string::{String, ToString};
+use core::fmt::Display;
+use core::str::FromStr;
+
+use ::serde::de::DeserializeOwned;
+use ::serde::Serialize;
+
+use crate::edn::{Edn, Error};
+
+mod de;
+mod ser;
+
+pub use de::Deserializer;
+pub use ser::Serializer;
+
+impl ::serde::ser::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        Self::Serialize(msg.to_string())
+    }
+}
+
+impl ::serde::de::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        Self::Deserialize(msg.to_string())
+    }
+}
+
+/// `to_edn` converts any `serde::Serialize` value into `Edn`.
+///
+/// - Structs become maps with keyword keys named after the fields, like `{:max_retries 3}`. Use
+///   `#[serde(rename_all = "kebab-case")]` for Clojure style keys.
+/// - Maps keep their keys as values, so `String` keys become EDN strings, not the keywords
+///   `edn_rs::Serialize` writes for them.
+/// - Sequences and sets become vectors, and tuples and tuple structs become lists.
+/// - `None`, `()` and unit structs become `nil`, and newtype structs become their value.
+/// - Unit enum variants become keywords, like `:Active`. Other variants become their value tagged
+///   with the variant name, like `#Circle 1.5`, `#Point [1 2]` or `#Rect {:w 1 :h 2}`.
+/// - Names that can't be keywords or tags, like `#[serde(rename = "a b")]`, are kept as strings:
+///   `{"a b" 1}` for a field, `"a b"` for a unit variant and `{"a b" 1.5}` for a variant with
+///   data.
+/// - `char` becomes an EDN char, and `i128`/`u128` out of the 64 bit range become strings, the
+///   same as `Serialize` writes them.
+/// ```rust
+/// use edn_rs::Edn;
+/// use serde::Serialize;
+/// use std::str::FromStr;
+///
+/// #[derive(Serialize)]
+/// enum Shape {
+///     Empty,
+///     Circle { radius: f64 },
+/// }
+///
+/// #[derive(Serialize)]
+/// struct Drawing {
+///     name: String,
+///     shapes: Vec<Shape>,
+///     origin: (i32, i32),
+/// }
+///
+/// let drawing = Drawing {
+///     name: "logo".to_string(),
+///     shapes: vec![Shape::Empty, Shape::Circle { radius: 1.5 }],
+///     origin: (0, -1),
+/// };
+/// assert_eq!(
+///     edn_rs::serde::to_edn(&drawing).unwrap(),
+///     Edn::from_str("{:name \"logo\" :shapes [:Empty #Circle {:radius 1.5}] :origin (0 -1)}").unwrap()
+/// );
+/// ```
+///
+/// # Errors
+///
+/// `Error::Serialize` if the `Serialize` implementation of `value` fails.
+pub fn to_edn<T: ?Sized + Serialize>(value: &T) -> Result<Edn, Error> {
+    value.serialize(Serializer)
+}
+
+/// `to_string` serializes any `serde::Serialize` value into an EDN string, mapped as `to_edn`
+/// describes.
+///
+/// # Errors
+///
+/// The same as `to_edn`.
+pub fn to_string<T: ?Sized + Serialize>(value: &T) -> Result<String, Error> {
+    to_edn(value).map(|edn| edn.to_string())
+}
+
+/// `from_edn` reads any `serde::Deserialize` type out of an `Edn`, accepting what `to_edn`
+/// produces and more.
+///
+/// - Struct fields are read from keyword or string keys.
+/// - Sequences and tuples are read from vectors, lists and sets.
+/// - Keywords are read as strings without their leading `:`.
+/// - Enums are read from a keyword, symbol or string for unit variants, or from a value tagged
+///   with the variant name, or from a map with the variant name as its only key.
+/// - Any other tagged value is read as its value, so `#inst "2020-01-01"` reads as a string.
+/// ```rust
+/// use serde::Deserialize;
+/// use std::collections::HashSet;
+///
+/// #[derive(Deserialize, Debug, PartialEq)]
+/// enum Level {
+///     Debug,
+///     Custom(u8),
+/// }
+///
+/// #[derive(Deserialize, Debug, PartialEq)]
+/// struct Config {
+///     levels: Vec<Level>,
+///     tags: HashSet<String>,
+///     created: String,
+/// }
+///
+/// let config: Config = edn_rs::serde::from_str(
+///     "{:levels [:Debug #Custom 3] :tags #{:a :b} :created #inst \"2020-01-01\"}",
+/// )
+/// .unwrap();
+/// assert_eq!(config.levels, vec![Level::Debug, Level::Custom(3)]);
+/// assert!(config.tags.contains("a"));
+/// assert_eq!(config.created, "2020-01-01");
+/// ```
+///
+/// # Errors
+///
+/// `Error::Deserialize` or `Error::Type` if `edn` doesn't have the shape `T` needs.
+pub fn from_edn<T: DeserializeOwned>(edn: &Edn) -> Result<T, Error> {
+    T::deserialize(Deserializer::new(edn))
+}
+
+/// `from_str` parses an EDN string and reads any `serde::Deserialize` type out of it, mapped as
+/// `from_edn` describes.
+///
+/// # Errors
+///
+/// `Error::ParseEdn` if `s` isn't valid EDN, and the same errors as `from_edn`.
+pub fn from_str<T: DeserializeOwned>(s: &str) -> Result<T, Error> {
+    from_edn(&Edn::from_str(s)?)
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::collections::{BTreeMap, BTreeSet};
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    use ::serde::Deserialize;
+
+    use super::*;
+
+    fn edn(s: &str) -> Edn {
+        Edn::from_str(s).unwrap()
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    enum Event {
+        Started,
+        Moved(i64, i64),
+        Renamed(String),
+        Resized { width: u32, height: u32 },
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Id(u64);
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Log {
+        id: Id,
+        events: Vec<Event>,
+        tags: BTreeSet<char>,
+        counts: BTreeMap<u8, Option<f32>>,
+        pair: (bool, String),
+    }
+
+    fn log() -> Log {
+        Log {
+            id: Id(7),
+            events: vec![
+                Event::Started,
+                Event::Moved(1, -2),
+                Event::Renamed("b".to_string()),
+                Event::Resized {
+                    width: 3,
+                    height: 4,
+                },
+            ],
+            tags: ['x', 'y'].into_iter().collect(),
+            counts: [(1, Some(0.1)), (2, None)].into_iter().collect(),
+            pair: (true, "p".to_string()),
+        }
+    }
+
+    #[test]
+    fn structs_and_enums_map_to_edn() {
+        assert_eq!(
+            to_edn(&log()).unwrap(),
+            edn("{:id 7
+                  :events [:Started #Moved [1 -2] #Renamed \"b\" #Resized {:width 3 :height 4}]
+                  :tags [\\x \\y]
+                  :counts {1 0.1 2 nil}
+                  :pair (true \"p\")}")
+        );
+    }
+
+    #[test]
+    fn round_trips() {
+        assert_eq!(from_str::<Log>(&to_string(&log()).unwrap()), Ok(log()));
+        assert_eq!(from_edn::<Log>(&to_edn(&log()).unwrap()), Ok(log()));
+    }
+
+    #[test]
+    fn reads_other_edn_shapes() {
+        let events: Vec<Event> =
+            from_str("(Started \"Started\" {:Renamed \"c\"} #Moved (0 0))").unwrap();
+        assert_eq!(
+            events,
+            vec![
+                Event::Started,
+                Event::Started,
+                Event::Renamed("c".to_string()),
+                Event::Moved(0, 0)
+            ]
+        );
+        let tags: BTreeSet<String> = from_str("#{:a \"b\" c}").unwrap();
+        assert_eq!(tags.into_iter().collect::<Vec<_>>(), ["a", "b", "c"]);
+        assert_eq!(from_str::<(u8, f64)>("[1 3/4]"), Ok((1, 0.75)));
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    enum Odd {
+        #[serde(rename = "a b")]
+        Spaced,
+        #[serde(rename = "_hidden")]
+        Hidden(u8),
+        #[serde(rename = "")]
+        Empty { n: u8 },
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Names {
+        #[serde(rename = "a b")]
+        spaced: Vec<Odd>,
+        #[serde(rename = ":colon")]
+        colon: u8,
+        plain: u8,
+    }
+
+    #[test]
+    fn names_that_arent_keywords_are_strings() {
+        let names = Names {
+            spaced: vec![Odd::Spaced, Odd::Hidden(1), Odd::Empty { n: 2 }],
+            colon: 3,
+            plain: 4,
+        };
+        assert_eq!(
+            to_edn(&names).unwrap(),
+            edn("{\"a b\" [\"a b\" {:_hidden 1} {\"\" {:n 2}}] \":colon\" 3 :plain 4}")
+        );
+        assert_eq!(from_str::<Names>(&to_string(&names).unwrap()), Ok(names));
+    }
+
+    #[test]
+    fn wide_integers_out_of_range_are_strings() {
+        let wide = (u128::MAX, i128::MIN, 5i128);
+        assert_eq!(
+            to_edn(&wide).unwrap(),
+            edn("(\"340282366920938463463374607431768211455\"
+                  \"-170141183460469231731687303715884105728\" 5)")
+        );
+        assert_eq!(to_edn(&wide).unwrap(), edn(&crate::to_string(&wide)));
+        assert_eq!(from_edn(&to_edn(&wide).unwrap()), Ok(wide));
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            from_str::<Event>("[1]"),
+            Err(Error::Type {
+                expected: "enum variant",
+                found: "vector"
+            })
+        );
+        assert!(from_str::<Log>("{:id \"x\"}").is_err());
+        assert!(from_str::<u8>("300").is_err());
+    }
+}
@@ -0,0 +1,376 @@
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use ::serde::ser::{self, Serialize};
+
+use crate::edn::{Edn, Error, List, Map, Tagged, Vector};
+use crate::serialize::{is_name, ToEdn};
+
+/// `Serializer` turns any `serde::Serialize` value into `Edn`. See `edn_rs::serde::to_edn` for how
+/// Rust values are mapped.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Serializer;
+
+// Names that can't be keywords or tags, like `"a b"`, are kept as strings instead: `"a b"` for
+// keys and unit variants, and `{"a b" value}` for variants with data. `from_edn` reads both.
+fn keyword(name: &str) -> Edn {
+    if is_name(name) {
+        Edn::Key(format!(":{name}").into())
+    } else {
+        Edn::Str(name.into())
+    }
+}
+
+// `#_` starts a discard, so tags can't start with `_`.
+fn tagged(name: &str, value: Edn) -> Edn {
+    if is_name(name) && !name.starts_with('_') {
+        Edn::Tagged(Tagged::new(name, value))
+    } else {
+        let mut map = Map::empty();
+        map.insert(keyword(name).to_string(), value);
+        Edn::Map(map)
+    }
+}
+
+enum Seq {
+    Vector,
+    List,
+    Variant(&'static str),
+}
+
+pub struct SerializeSeq {
+    seq: Seq,
+    items: Vec<Edn>,
+}
+
+impl SerializeSeq {
+    fn new(seq: Seq, len: Option<usize>) -> Self {
+        Self {
+            seq,
+            items: Vec::with_capacity(len.unwrap_or(0)),
+        }
+    }
+
+    fn push<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        self.items.push(value.serialize(Serializer)?);
+        Ok(())
+    }
+
+    fn finish(self) -> Edn {
+        match self.seq {
+            Seq::Vector => Edn::Vector(Vector::new(self.items)),
+            Seq::List => Edn::List(List::new(self.items)),
+            Seq::Variant(name) => tagged(name, Edn::Vector(Vector::new(self.items))),
+        }
+    }
+}
+
+pub struct SerializeMap {
+    variant: Option<&'static str>,
+    entries: BTreeMap<String, Edn>,
+    key: Option<String>,
+}
+
+impl SerializeMap {
+    const fn new(variant: Option<&'static str>) -> Self {
+        Self {
+            variant,
+            entries: BTreeMap::new(),
+            key: None,
+        }
+    }
+
+    fn field<T: ?Sized + Serialize>(&mut self, key: &str, value: &T) -> Result<(), Error> {
+        self.entries
+            .insert(keyword(key).to_string(), value.serialize(Serializer)?);
+        Ok(())
+    }
+
+    fn finish(self) -> Edn {
+        let map = Edn::Map(Map::new(self.entries));
+        match self.variant {
+            Some(name) => tagged(name, map),
+            None => map,
+        }
+    }
+}
+
+impl ser::Serializer for Serializer {
+    type Ok = Edn;
+    type Error = Error;
+
+    type SerializeSeq = SerializeSeq;
+    type SerializeTuple = SerializeSeq;
+    type SerializeTupleStruct = SerializeSeq;
+    type SerializeTupleVariant = SerializeSeq;
+    type SerializeMap = SerializeMap;
+    type SerializeStruct = SerializeMap;
+    type SerializeStructVariant = SerializeMap;
+
+    fn serialize_bool(self, v: bool) -> Result<Edn, Error> {
+        Ok(v.to_edn())
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Edn, Error> {
+        Ok(v.to_edn())
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Edn, Error> {
+        Ok(v.to_edn())
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Edn, Error> {
+        Ok(v.to_edn())
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Edn, Error> {
+        Ok(v.to_edn())
+    }
+
+    // Like `Serialize` does, integers out of the 64 bit range are written as strings.
+    fn serialize_i128(self, v: i128) -> Result<Edn, Error> {
+        Ok(v.to_edn())
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Edn, Error> {
+        Ok(v.to_edn())
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Edn, Error> {
+        Ok(v.to_edn())
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Edn, Error> {
+        Ok(v.to_edn())
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Edn, Error> {
+        Ok(v.to_edn())
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<Edn, Error> {
+        Ok(v.to_edn())
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Edn, Error> {
+        Ok(v.to_edn())
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Edn, Error> {
+        Ok(v.to_edn())
+    }
+
+    fn serialize_char(self, v: char) -> Result<Edn, Error> {
+        Ok(v.to_edn())
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Edn, Error> {
+        Ok(v.to_edn())
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Edn, Error> {
+        Ok(Edn::Vector(v.iter().map(ToEdn::to_edn).collect()))
+    }
+
+    fn serialize_none(self) -> Result<Edn, Error> {
+        Ok(Edn::Nil)
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<Edn, Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Edn, Error> {
+        Ok(Edn::Nil)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Edn, Error> {
+        Ok(Edn::Nil)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+    ) -> Result<Edn, Error> {
+        Ok(keyword(variant))
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Edn, Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Edn, Error> {
+        Ok(tagged(variant, value.serialize(self)?))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<SerializeSeq, Error> {
+        Ok(SerializeSeq::new(Seq::Vector, len))
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SerializeSeq, Error> {
+        Ok(SerializeSeq::new(Seq::List, Some(len)))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<SerializeSeq, Error> {
+        Ok(SerializeSeq::new(Seq::List, Some(len)))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerializeSeq, Error> {
+        Ok(SerializeSeq::new(Seq::Variant(variant), Some(len)))
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<SerializeMap, Error> {
+        Ok(SerializeMap::new(None))
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<SerializeMap, Error> {
+        Ok(SerializeMap::new(None))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<SerializeMap, Error> {
+        Ok(SerializeMap::new(Some(variant)))
+    }
+
+    fn collect_str<T: ?Sized + core::fmt::Display>(self, value: &T) -> Result<Edn, Error> {
+        Ok(Edn::Str(value.to_string().into()))
+    }
+}
+
+impl ser::SerializeSeq for SerializeSeq {
+    type Ok = Edn;
+    type Error = Error;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Edn, Error> {
+        Ok(self.finish())
+    }
+}
+
+impl ser::SerializeTuple for SerializeSeq {
+    type Ok = Edn;
+    type Error = Error;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Edn, Error> {
+        Ok(self.finish())
+    }
+}
+
+impl ser::SerializeTupleStruct for SerializeSeq {
+    type Ok = Edn;
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Edn, Error> {
+        Ok(self.finish())
+    }
+}
+
+impl ser::SerializeTupleVariant for SerializeSeq {
+    type Ok = Edn;
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Edn, Error> {
+        Ok(self.finish())
+    }
+}
+
+impl ser::SerializeMap for SerializeMap {
+    type Ok = Edn;
+    type Error = Error;
+
+    fn serialize_key<T: ?Sized + Serialize>(&mut self, key: &T) -> Result<(), Error> {
+        self.key = Some(key.serialize(Serializer)?.to_string());
+        Ok(())
+    }
+
+    fn serialize_value<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        let key = self
+            .key
+            .take()
+            .ok_or_else(|| Error::Serialize("map value without a key".to_string()))?;
+        self.entries.insert(key, value.serialize(Serializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Edn, Error> {
+        Ok(self.finish())
+    }
+}
+
+impl ser::SerializeStruct for SerializeMap {
+    type Ok = Edn;
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.field(key, value)
+    }
+
+    fn end(self) -> Result<Edn, Error> {
+        Ok(self.finish())
+    }
+}
+
+impl ser::SerializeStructVariant for SerializeMap {
+    type Ok = Edn;
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.field(key, value)
+    }
+
+    fn end(self) -> Result<Edn, Error> {
+        Ok(self.finish())
+    }
+}
@@ -0,0 +1,74 @@
+use alloc::borrow::Borrow;
+use alloc::string::String;
+use core::fmt::{self, Write};
+
+/// A keyword name, for map keys that should be written as exactly `:name`.
+///
+/// `String` map keys are written as keywords too, but with spaces and underscores turned into
+/// dashes, so `"max_retries"` and `"max-retries"` both become `:max-retries`. They are read back
+/// without the `:`, as `"max-retries"`, so a `String` key with spaces or underscores doesn't
+/// survive a round trip. A `Keyword` key is kept as it is, so it should already be a valid
+/// keyword like `max_retries` or `db/id`.
+/// ```rust
+/// use std::collections::BTreeMap;
+/// use edn_rs::{map, Keyword};
+///
+/// let by_string: BTreeMap<String, u8> = map! {"max_retries".to_string() => 3};
+/// let by_keyword: BTreeMap<Keyword, u8> = map! {Keyword::new("max_retries") => 3};
+///
+/// assert_eq!(edn_rs::to_string(&by_string), "{:max-retries 3}");
+/// assert_eq!(edn_rs::to_string(&by_keyword), "{:max_retries 3}");
+///
+/// let back: BTreeMap<String, u8> = edn_rs::from_str("{:max-retries 3}").unwrap();
+/// assert_eq!(back.get("max-retries"), Some(&3));
+///
+/// let back: BTreeMap<Keyword, u8> = edn_rs::from_str("{:max_retries 3}").unwrap();
+/// assert_eq!(back.get("max_retries"), Some(&3));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Keyword(String);
+
+impl Keyword {
+    #[must_use]
+    pub fn new(name: impl Into<String>) -> Self {
+        Self(name.into())
+    }
+
+    /// The name without its leading `:`.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<&str> for Keyword {
+    fn from(name: &str) -> Self {
+        Self::new(name)
+    }
+}
+
+impl From<String> for Keyword {
+    fn from(name: String) -> Self {
+        Self(name)
+    }
+}
+
+impl Borrow<str> for Keyword {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for Keyword {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_char(':')?;
+        f.write_str(&self.0)
+    }
+}
+
+// Names go after the `:` of a keyword or the `#` of a tag, so they can't be empty, start with
+// `:`, or have whitespace or delimiters in them.
+pub fn is_name(name: &str) -> bool {
+    let invalid = |c: char| c.is_whitespace() || "()[]{}\",;#\\".contains(c);
+    !name.is_empty() && !name.starts_with(':') && !name.contains(invalid)
+}
@@ -1,26 +1,186 @@
-use alloc::collections::{BTreeMap, BTreeSet, LinkedList};
-use alloc::format;
-use alloc::string::{String, ToString};
+use alloc::borrow::{Cow, ToOwned};
+use alloc::boxed::Box;
+use alloc::collections::{BTreeMap, BTreeSet, BinaryHeap, LinkedList, VecDeque};
+use alloc::rc::Rc;
+use alloc::string::String;
+use alloc::sync::Arc;
 use alloc::vec::Vec;
+use core::cmp::{Ordering, Reverse};
+use core::fmt::{self, Write};
+use core::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+use core::num::{
+    NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+    NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize, Wrapping,
+};
+use core::time::Duration;
+
+use crate::base64::{self, Bytes};
+use crate::edn::{write_float, Edn};
+#[cfg(feature = "std")]
+use crate::inst;
+
+mod keyword;
+mod to_edn;
+pub(crate) use keyword::is_name;
+pub use keyword::Keyword;
+pub use to_edn::{ToEdn, ToEdnMap};
 
 /// Trait that allows you to implement Serialization for each type of your choice.
+///
+/// Implement `serialize_into`, which writes into any `fmt::Write`, so nested values write into the
+/// same buffer instead of allocating a `String` each. `serialize` collects it into a `String`.
 /// Example:
 /// ```rust
+/// use core::fmt::{self, Write};
 /// use edn_rs::serialize::Serialize;
 ///
 /// #[derive(Debug)]
 /// struct YourType;
 ///
 /// impl Serialize for YourType {
-///     fn serialize(&self) -> String {
-///         format!("{:?}", self)
+///     fn serialize_into(&self, w: &mut dyn Write) -> fmt::Result {
+///         write!(w, "{:?}", self)
+///     }
+/// }
+///
+/// struct Pair(YourType, Vec<u8>);
+///
+/// impl Serialize for Pair {
+///     fn serialize_into(&self, w: &mut dyn Write) -> fmt::Result {
+///         w.write_str("{:first ")?;
+///         self.0.serialize_into(w)?;
+///         w.write_str(", :second ")?;
+///         self.1.serialize_into(w)?;
+///         w.write_char('}')
 ///     }
 /// }
+///
+/// assert_eq!(Pair(YourType, vec![1]).serialize(), "{:first YourType, :second [1]}");
 /// ```
 ///
 /// Implemented for all generic types.
 pub trait Serialize {
-    fn serialize(&self) -> String;
+    /// The EDN for `self` as a `String`.
+    ///
+    /// # Panics
+    ///
+    /// If `serialize_into` fails, as half written EDN is never returned. Use `try_serialize` for
+    /// values that can fail, like a field with a `with` module that returns an error.
+    fn serialize(&self) -> String {
+        self.try_serialize()
+            .expect("a Serialize implementation returned an error unexpectedly")
+    }
+
+    /// The EDN for `self` as a `String`, or the error `serialize_into` failed with.
+    ///
+    /// # Errors
+    ///
+    /// Any error from `serialize_into`. Writing into a `String` never fails by itself.
+    fn try_serialize(&self) -> Result<String, fmt::Error> {
+        let mut s = String::new();
+        self.serialize_into(&mut s)?;
+        Ok(s)
+    }
+
+    /// Writes the EDN for `self` into `w`.
+    ///
+    /// # Errors
+    ///
+    /// Any error from `w`, or `fmt::Error` if `self` can't be written as EDN. What was written
+    /// before the error is left in `w`.
+    fn serialize_into(&self, w: &mut dyn Write) -> fmt::Result;
+
+    /// Writes `self` as a map key into `w`, the same as `serialize_into` unless overridden.
+    ///
+    /// `String` and `&str` keys are written as keywords, with spaces and underscores turned into
+    /// dashes, so `"max_retries"` becomes `:max-retries`, and `":max-retries"`, the key
+    /// `Deserialize` reads out of `{:max-retries 3}`, does too. Strings that can't be a keyword
+    /// even then, like `"a(b"`, stay strings. Key a map by `Keyword` for keywords written exactly as
+    /// given, or by `Edn` values like `Edn::from("max_retries")` for string keys.
+    ///
+    /// # Errors
+    ///
+    /// Any error from `w`.
+    fn serialize_key_into(&self, w: &mut dyn Write) -> fmt::Result {
+        self.serialize_into(w)
+    }
+}
+
+fn write_seq<'a, T, I>(w: &mut dyn Write, open: &str, items: I, close: char) -> fmt::Result
+where
+    T: Serialize + 'a,
+    I: IntoIterator<Item = &'a T>,
+{
+    w.write_str(open)?;
+    for (i, item) in items.into_iter().enumerate() {
+        if i > 0 {
+            w.write_str(", ")?;
+        }
+        item.serialize_into(w)?;
+    }
+    w.write_char(close)
+}
+
+/// The keyword a `String` map key is written as, with spaces and underscores turned into dashes,
+/// or `None` if it can't be one. A leading `:` is dropped, so `":a"` is written as `:a` rather
+/// than `::a`.
+fn string_key(key: &str) -> Option<String> {
+    let name = key
+        .strip_prefix(':')
+        .unwrap_or(key)
+        .replace([' ', '_'], "-");
+    is_name(&name).then(|| alloc::format!(":{name}"))
+}
+
+fn write_map<'a, K, T, I>(w: &mut dyn Write, entries: I) -> fmt::Result
+where
+    K: Serialize + 'a,
+    T: Serialize + 'a,
+    I: IntoIterator<Item = (&'a K, &'a T)>,
+{
+    w.write_char('{')?;
+    for (i, (k, v)) in entries.into_iter().enumerate() {
+        if i > 0 {
+            w.write_str(", ")?;
+        }
+        k.serialize_key_into(w)?;
+        w.write_char(' ')?;
+        v.serialize_into(w)?;
+    }
+    w.write_char('}')
+}
+
+/// Adapts an `io::Write` to the `fmt::Write` that `serialize_into` takes, keeping the first IO
+/// error so it can be returned instead of a bare `fmt::Error`.
+#[cfg(feature = "std")]
+pub(crate) struct IoWriter<W> {
+    inner: W,
+    error: Option<std::io::Error>,
+}
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write> IoWriter<W> {
+    pub(crate) const fn new(inner: W) -> Self {
+        Self { inner, error: None }
+    }
+
+    pub(crate) fn finish(self, result: fmt::Result) -> std::io::Result<()> {
+        match (result, self.error) {
+            (_, Some(error)) => Err(error),
+            (Ok(()), None) => Ok(()),
+            (Err(_), None) => Err(std::io::Error::other("formatter error")),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write> Write for IoWriter<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.inner.write_all(s.as_bytes()).map_err(|e| {
+            self.error = Some(e);
+            fmt::Error
+        })
+    }
 }
 
 macro_rules! ser_primitives {
@@ -28,8 +188,8 @@ macro_rules! ser_primitives {
         $(
             impl Serialize for $name
             {
-                fn serialize(&self) -> String {
-                    format!("{:?}", self)
+                fn serialize_into(&self, w: &mut dyn Write) -> fmt::Result {
+                    write!(w, "{:?}", self)
                 }
             }
         )+
@@ -40,16 +200,8 @@ impl<T> Serialize for Vec<T>
 where
     T: Serialize,
 {
-    fn serialize(&self) -> String {
-        let aux_vec = self
-            .iter()
-            .map(Serialize::serialize)
-            .collect::<Vec<String>>();
-        let mut s = String::new();
-        s.push('[');
-        s.push_str(&aux_vec.join(", "));
-        s.push(']');
-        s
+    fn serialize_into(&self, w: &mut dyn Write) -> fmt::Result {
+        write_seq(w, "[", self, ']')
     }
 }
 
@@ -58,17 +210,8 @@ impl<T, H: std::hash::BuildHasher> Serialize for std::collections::HashSet<T, H>
 where
     T: Serialize,
 {
-    fn serialize(&self) -> String {
-        let aux_vec = self
-            .iter()
-            .map(Serialize::serialize)
-            .collect::<Vec<String>>();
-        let mut s = String::new();
-        s.push('#');
-        s.push('{');
-        s.push_str(&aux_vec.join(", "));
-        s.push('}');
-        s
+    fn serialize_into(&self, w: &mut dyn Write) -> fmt::Result {
+        write_seq(w, "#{", self, '}')
     }
 }
 
@@ -76,17 +219,8 @@ impl<T> Serialize for BTreeSet<T>
 where
     T: Serialize,
 {
-    fn serialize(&self) -> String {
-        let aux_vec = self
-            .iter()
-            .map(Serialize::serialize)
-            .collect::<Vec<String>>();
-        let mut s = String::new();
-        s.push('#');
-        s.push('{');
-        s.push_str(&aux_vec.join(", "));
-        s.push('}');
-        s
+    fn serialize_into(&self, w: &mut dyn Write) -> fmt::Result {
+        write_seq(w, "#{", self, '}')
     }
 }
 
@@ -94,113 +228,129 @@ impl<T> Serialize for LinkedList<T>
 where
     T: Serialize,
 {
-    fn serialize(&self) -> String {
-        let aux_vec = self
-            .iter()
-            .map(Serialize::serialize)
-            .collect::<Vec<String>>();
-        let mut s = String::new();
-        s.push('(');
-        s.push_str(&aux_vec.join(", "));
-        s.push(')');
-        s
+    fn serialize_into(&self, w: &mut dyn Write) -> fmt::Result {
+        write_seq(w, "(", self, ')')
     }
 }
 
-#[cfg(feature = "std")]
-impl<T, H: std::hash::BuildHasher> Serialize for std::collections::HashMap<String, T, H>
+impl<T> Serialize for VecDeque<T>
 where
     T: Serialize,
 {
-    fn serialize(&self) -> String {
-        let aux_vec = self
-            .iter()
-            .map(|(k, v)| format!(":{} {}", k.replace([' ', '_'], "-"), v.serialize()))
-            .collect::<Vec<String>>();
-        let mut s = String::new();
-        s.push('{');
-        s.push_str(&aux_vec.join(", "));
-        s.push('}');
-        s
+    fn serialize_into(&self, w: &mut dyn Write) -> fmt::Result {
+        write_seq(w, "[", self, ']')
     }
 }
 
-#[cfg(feature = "std")]
-impl<T, H: ::std::hash::BuildHasher> Serialize for std::collections::HashMap<&str, T, H>
+// In the heap's internal order, which `Deserialize` doesn't depend on.
+impl<T> Serialize for BinaryHeap<T>
 where
     T: Serialize,
 {
-    fn serialize(&self) -> String {
-        let aux_vec = self
-            .iter()
-            .map(|(k, v)| format!(":{} {}", k.replace([' ', '_'], "-"), v.serialize()))
-            .collect::<Vec<String>>();
-        let mut s = String::new();
-        s.push('{');
-        s.push_str(&aux_vec.join(", "));
-        s.push('}');
-        s
+    fn serialize_into(&self, w: &mut dyn Write) -> fmt::Result {
+        write_seq(w, "[", self, ']')
     }
 }
 
-impl<T> Serialize for BTreeMap<String, T>
+impl<T> Serialize for [T]
 where
     T: Serialize,
 {
-    fn serialize(&self) -> String {
-        let aux_vec = self
-            .iter()
-            .map(|(k, v)| format!(":{} {}", k.replace([' ', '_'], "-"), v.serialize()))
-            .collect::<Vec<String>>();
-        let mut s = String::new();
-        s.push('{');
-        s.push_str(&aux_vec.join(", "));
-        s.push('}');
-        s
+    fn serialize_into(&self, w: &mut dyn Write) -> fmt::Result {
+        write_seq(w, "[", self, ']')
     }
 }
 
-impl<T> Serialize for BTreeMap<&str, T>
+impl<T, const N: usize> Serialize for [T; N]
 where
     T: Serialize,
 {
-    fn serialize(&self) -> String {
-        let aux_vec = self
-            .iter()
-            .map(|(k, v)| format!(":{} {}", k.replace([' ', '_'], "-"), v.serialize()))
-            .collect::<Vec<String>>();
-        let mut s = String::new();
-        s.push('{');
-        s.push_str(&aux_vec.join(", "));
-        s.push('}');
-        s
+    fn serialize_into(&self, w: &mut dyn Write) -> fmt::Result {
+        write_seq(w, "[", self, ']')
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K, T, H> Serialize for std::collections::HashMap<K, T, H>
+where
+    K: Serialize,
+    T: Serialize,
+    H: std::hash::BuildHasher,
+{
+    fn serialize_into(&self, w: &mut dyn Write) -> fmt::Result {
+        write_map(w, self)
+    }
+}
+
+impl<K, T> Serialize for BTreeMap<K, T>
+where
+    K: Serialize,
+    T: Serialize,
+{
+    fn serialize_into(&self, w: &mut dyn Write) -> fmt::Result {
+        write_map(w, self)
+    }
+}
+
+impl Serialize for Keyword {
+    fn serialize_into(&self, w: &mut dyn Write) -> fmt::Result {
+        write!(w, "{self}")
+    }
+}
+
+impl Serialize for Edn {
+    fn serialize_into(&self, w: &mut dyn Write) -> fmt::Result {
+        write!(w, "{self}")
     }
 }
 
 // Primitive Types
-ser_primitives![i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, f32, f64, bool];
+ser_primitives![i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, bool];
+
+impl Serialize for f64 {
+    fn serialize_into(&self, w: &mut dyn Write) -> fmt::Result {
+        write_float(w, *self, self)
+    }
+}
+
+impl Serialize for f32 {
+    fn serialize_into(&self, w: &mut dyn Write) -> fmt::Result {
+        write_float(w, f64::from(*self), self)
+    }
+}
 
 impl Serialize for () {
-    fn serialize(&self) -> String {
-        "nil".to_string()
+    fn serialize_into(&self, w: &mut dyn Write) -> fmt::Result {
+        w.write_str("nil")
     }
 }
 
 impl Serialize for String {
-    fn serialize(&self) -> String {
-        format!("{self:?}")
+    fn serialize_into(&self, w: &mut dyn Write) -> fmt::Result {
+        write!(w, "{self:?}")
+    }
+
+    fn serialize_key_into(&self, w: &mut dyn Write) -> fmt::Result {
+        self.as_str().serialize_key_into(w)
     }
 }
 
-impl Serialize for &str {
-    fn serialize(&self) -> String {
-        format!("{self:?}")
+impl Serialize for str {
+    fn serialize_into(&self, w: &mut dyn Write) -> fmt::Result {
+        write!(w, "{self:?}")
+    }
+
+    fn serialize_key_into(&self, w: &mut dyn Write) -> fmt::Result {
+        match string_key(self) {
+            Some(key) => w.write_str(&key),
+            None => self.serialize_into(w),
+        }
     }
 }
 
 impl Serialize for char {
-    fn serialize(&self) -> String {
-        format!("\\{self}")
+    fn serialize_into(&self, w: &mut dyn Write) -> fmt::Result {
+        write!(w, "\\{self}")
     }
 }
 
@@ -208,84 +358,239 @@ impl<T> Serialize for Option<T>
 where
     T: Serialize,
 {
-    fn serialize(&self) -> String {
-        self.as_ref().map_or_else(
-            || String::from("nil"),
-            crate::serialize::Serialize::serialize,
-        )
+    fn serialize_into(&self, w: &mut dyn Write) -> fmt::Result {
+        match self {
+            Some(t) => t.serialize_into(w),
+            None => w.write_str("nil"),
+        }
     }
 }
 
-// Complex types
-impl<A: Serialize> Serialize for (A,) {
-    fn serialize(&self) -> String {
-        format!("({})", self.0.serialize())
+// 128 bit integers that don't fit in an EDN integer are written as strings, which `Deserialize`
+// reads back.
+macro_rules! ser_wide_ints {
+    ( $( $name:ty ),+ ) => {
+        $(
+            impl Serialize for $name {
+                fn serialize_into(&self, w: &mut dyn Write) -> fmt::Result {
+                    if i64::try_from(*self).is_ok() || u64::try_from(*self).is_ok() {
+                        write!(w, "{self}")
+                    } else {
+                        write!(w, "\"{self}\"")
+                    }
+                }
+            }
+        )+
+    };
+}
+
+ser_wide_ints![i128, u128];
+
+macro_rules! ser_non_zero {
+    ( $( $name:ty ),+ ) => {
+        $(
+            impl Serialize for $name {
+                fn serialize_into(&self, w: &mut dyn Write) -> fmt::Result {
+                    self.get().serialize_into(w)
+                }
+            }
+        )+
+    };
+}
+
+ser_non_zero![
+    NonZeroI8,
+    NonZeroI16,
+    NonZeroI32,
+    NonZeroI64,
+    NonZeroI128,
+    NonZeroIsize,
+    NonZeroU8,
+    NonZeroU16,
+    NonZeroU32,
+    NonZeroU64,
+    NonZeroU128,
+    NonZeroUsize
+];
+
+// Pointers and wrappers are written as the value they hold.
+macro_rules! ser_transparent {
+    ( $( $name:ident ),+ ) => {
+        $(
+            impl<T: Serialize + ?Sized> Serialize for $name<T> {
+                fn serialize_into(&self, w: &mut dyn Write) -> fmt::Result {
+                    (**self).serialize_into(w)
+                }
+                fn serialize_key_into(&self, w: &mut dyn Write) -> fmt::Result {
+                    (**self).serialize_key_into(w)
+                }
+            }
+        )+
+    };
+}
+
+ser_transparent![Box, Rc, Arc];
+
+impl<T: Serialize + ?Sized> Serialize for &T {
+    fn serialize_into(&self, w: &mut dyn Write) -> fmt::Result {
+        (**self).serialize_into(w)
+    }
+
+    fn serialize_key_into(&self, w: &mut dyn Write) -> fmt::Result {
+        (**self).serialize_key_into(w)
     }
 }
 
-impl<A: Serialize, B: Serialize> Serialize for (A, B) {
-    fn serialize(&self) -> String {
-        format!("({}, {})", self.0.serialize(), self.1.serialize())
+impl<T: Serialize + ToOwned + ?Sized> Serialize for Cow<'_, T> {
+    fn serialize_into(&self, w: &mut dyn Write) -> fmt::Result {
+        (**self).serialize_into(w)
+    }
+
+    fn serialize_key_into(&self, w: &mut dyn Write) -> fmt::Result {
+        (**self).serialize_key_into(w)
     }
 }
 
-impl<A: Serialize, B: Serialize, C: Serialize> Serialize for (A, B, C) {
-    fn serialize(&self) -> String {
-        format!(
-            "({}, {}, {})",
-            self.0.serialize(),
-            self.1.serialize(),
-            self.2.serialize()
-        )
+impl<T: Serialize> Serialize for Wrapping<T> {
+    fn serialize_into(&self, w: &mut dyn Write) -> fmt::Result {
+        self.0.serialize_into(w)
     }
 }
 
-impl<A: Serialize, B: Serialize, C: Serialize, D: Serialize> Serialize for (A, B, C, D) {
-    fn serialize(&self) -> String {
-        format!(
-            "({}, {}, {}, {})",
-            self.0.serialize(),
-            self.1.serialize(),
-            self.2.serialize(),
-            self.3.serialize()
-        )
+impl<T: Serialize> Serialize for Reverse<T> {
+    fn serialize_into(&self, w: &mut dyn Write) -> fmt::Result {
+        self.0.serialize_into(w)
     }
 }
 
-impl<A: Serialize, B: Serialize, C: Serialize, D: Serialize, E: Serialize> Serialize
-    for (A, B, C, D, E)
-{
-    fn serialize(&self) -> String {
-        format!(
-            "({}, {}, {}, {}, {})",
-            self.0.serialize(),
-            self.1.serialize(),
-            self.2.serialize(),
-            self.3.serialize(),
-            self.4.serialize()
-        )
+// `Ok` and `Err` are tagged, like `#Ok 1` or `#Err "not found"`.
+impl<T: Serialize, E: Serialize> Serialize for Result<T, E> {
+    fn serialize_into(&self, w: &mut dyn Write) -> fmt::Result {
+        match self {
+            Ok(t) => {
+                w.write_str("#Ok ")?;
+                t.serialize_into(w)
+            }
+            Err(e) => {
+                w.write_str("#Err ")?;
+                e.serialize_into(w)
+            }
+        }
     }
 }
 
-impl<A: Serialize, B: Serialize, C: Serialize, D: Serialize, E: Serialize, F: Serialize> Serialize
-    for (A, B, C, D, E, F)
-{
-    fn serialize(&self) -> String {
-        format!(
-            "({}, {}, {}, {}, {}, {})",
-            self.0.serialize(),
-            self.1.serialize(),
-            self.2.serialize(),
-            self.3.serialize(),
-            self.4.serialize(),
-            self.5.serialize()
+// `-1`, `0` or `1`, like Clojure's `compare`.
+impl Serialize for Ordering {
+    fn serialize_into(&self, w: &mut dyn Write) -> fmt::Result {
+        (*self as i8).serialize_into(w)
+    }
+}
+
+pub(crate) const DURATION_TAG: &str = "duration";
+pub(crate) const IP_TAG: &str = "ip";
+pub(crate) const SOCKET_TAG: &str = "socket";
+
+// Like `#duration {:secs 90, :nanos 500000000}`.
+impl Serialize for Duration {
+    fn serialize_into(&self, w: &mut dyn Write) -> fmt::Result {
+        write!(
+            w,
+            "#{DURATION_TAG} {{:secs {}, :nanos {}}}",
+            self.as_secs(),
+            self.subsec_nanos()
         )
     }
 }
 
+macro_rules! ser_tagged_display {
+    ( $tag:ident: $( $name:ty ),+ ) => {
+        $(
+            impl Serialize for $name {
+                fn serialize_into(&self, w: &mut dyn Write) -> fmt::Result {
+                    write!(w, "#{} \"{}\"", $tag, self)
+                }
+            }
+        )+
+    };
+}
+
+ser_tagged_display![IP_TAG: IpAddr, Ipv4Addr, Ipv6Addr];
+ser_tagged_display![SOCKET_TAG: SocketAddr, SocketAddrV4, SocketAddrV6];
+
+impl Serialize for Bytes {
+    fn serialize_into(&self, w: &mut dyn Write) -> fmt::Result {
+        base64::serialize_into(self, w)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Serialize for std::time::SystemTime {
+    fn serialize_into(&self, w: &mut dyn Write) -> fmt::Result {
+        write!(w, "#{} ", inst::TAG)?;
+        inst::format(*self).serialize_into(w)
+    }
+}
+
+// A path that isn't UTF-8 has its invalid parts replaced, like `ToEdn` does, so the string it is
+// written as may point somewhere else.
+#[cfg(feature = "std")]
+impl Serialize for std::path::Path {
+    fn serialize_into(&self, w: &mut dyn Write) -> fmt::Result {
+        self.to_string_lossy().serialize_into(w)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Serialize for std::path::PathBuf {
+    fn serialize_into(&self, w: &mut dyn Write) -> fmt::Result {
+        self.as_path().serialize_into(w)
+    }
+}
+
+// Complex types
+macro_rules! ser_tuples {
+    ( $( ( $first:ident $(, $name:ident )* ) ),+ ) => {
+        $(
+            impl<$first: Serialize $(, $name: Serialize )*> Serialize for ($first, $( $name, )*) {
+                #[allow(non_snake_case)]
+                fn serialize_into(&self, w: &mut dyn Write) -> fmt::Result {
+                    let ($first, $( $name, )*) = self;
+                    w.write_char('(')?;
+                    $first.serialize_into(w)?;
+                    $(
+                        w.write_str(", ")?;
+                        $name.serialize_into(w)?;
+                    )*
+                    w.write_char(')')
+                }
+            }
+        )+
+    };
+}
+
+ser_tuples![
+    (A),
+    (A, B),
+    (A, B, C),
+    (A, B, C, D),
+    (A, B, C, D, E),
+    (A, B, C, D, E, F),
+    (A, B, C, D, E, F, G),
+    (A, B, C, D, E, F, G, H),
+    (A, B, C, D, E, F, G, H, I),
+    (A, B, C, D, E, F, G, H, I, J),
+    (A, B, C, D, E, F, G, H, I, J, K),
+    (A, B, C, D, E, F, G, H, I, J, K, L),
+    (A, B, C, D, E, F, G, H, I, J, K, L, M),
+    (A, B, C, D, E, F, G, H, I, J, K, L, M, N),
+    (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O),
+    (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P)
+];
+
 #[cfg(test)]
 mod test {
     use alloc::collections::BTreeSet;
+    use alloc::string::ToString;
     use alloc::vec;
 
     use super::*;
@@ -550,6 +855,77 @@ mod test {
         );
     }
 
+    #[test]
+    fn maps_with_other_keys() {
+        assert_eq!(
+            map! {Keyword::new("db/id") => 1u8, Keyword::from("bye_bye") => 2}.serialize(),
+            "{:bye_bye 2, :db/id 1}"
+        );
+        assert_eq!(map! {-1i64 => 'a', 2 => 'b'}.serialize(), "{-1 \\a, 2 \\b}");
+        assert_eq!(
+            map! {(1u8, true) => vec![()]}.serialize(),
+            "{(1, true) [nil]}"
+        );
+        assert_eq!(
+            map! {Some(1u8) => Keyword::new("x"), None => Keyword::new("y")}.serialize(),
+            "{nil :y, 1 :x}"
+        );
+    }
+
+    #[test]
+    fn serialize_into_appends_to_the_writer() {
+        struct Legacy;
+        impl Serialize for Legacy {
+            fn serialize_into(&self, w: &mut dyn Write) -> fmt::Result {
+                w.write_str("#legacy 1")
+            }
+        }
+
+        let mut s = String::from("=> ");
+        vec![(Some(Legacy), 'a')].serialize_into(&mut s).unwrap();
+        assert_eq!(s, "=> [(#legacy 1, \\a)]");
+    }
+
+    #[test]
+    fn try_serialize_reports_errors_instead_of_half_written_edn() {
+        struct Broken;
+        impl Serialize for Broken {
+            fn serialize_into(&self, _: &mut dyn Write) -> fmt::Result {
+                Err(fmt::Error)
+            }
+        }
+
+        assert_eq!(vec![None, Some(Broken)].try_serialize(), Err(fmt::Error));
+        assert_eq!(crate::try_to_string(&vec![Some(Broken)]), Err(fmt::Error));
+        assert_eq!(
+            vec![None::<Broken>].try_serialize(),
+            Ok("[nil]".to_string())
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn to_writer_reports_io_errors() {
+        struct Full;
+        impl std::io::Write for Full {
+            fn write(&mut self, _: &[u8]) -> std::io::Result<usize> {
+                Err(std::io::ErrorKind::WriteZero.into())
+            }
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let map = map! {"a b".to_string() => vec![1u8]};
+        let mut out = Vec::new();
+        crate::to_writer(&mut out, &map).unwrap();
+        assert_eq!(out, b"{:a-b [1]}");
+        assert_eq!(
+            crate::to_writer(Full, &map).unwrap_err().kind(),
+            std::io::ErrorKind::WriteZero
+        );
+    }
+
     #[test]
     fn multi_sized_tuples() {
         assert_eq!((1,).serialize(), "(1)");
@@ -0,0 +1,486 @@
+use alloc::borrow::{Cow, ToOwned};
+use alloc::boxed::Box;
+use alloc::collections::{BTreeMap, BTreeSet, BinaryHeap, LinkedList, VecDeque};
+use alloc::rc::Rc;
+use alloc::string::{String, ToString};
+use alloc::sync::Arc;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::{Ordering, Reverse};
+use core::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+use core::num::{
+    NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+    NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize, Wrapping,
+};
+use core::time::Duration;
+
+use super::{string_key, Keyword, DURATION_TAG, IP_TAG, SOCKET_TAG};
+use crate::base64::{self, Bytes};
+use crate::edn::{Edn, List, Map, Set, Tagged, Vector};
+#[cfg(feature = "std")]
+use crate::inst;
+
+/// `ToEdn` converts a value straight into the `Edn` its `Serialize` output would parse into,
+/// without printing and parsing it.
+/// ```rust
+/// use edn_rs::{map, Edn, Serialize, ToEdn};
+/// use std::collections::BTreeMap;
+/// use std::str::FromStr;
+///
+/// let config: BTreeMap<String, Vec<(u8, Option<char>)>> = map! {"max_retries".to_string() => vec![(3, None)]};
+///
+/// assert_eq!(config.to_edn(), Edn::from_str("{:max-retries [(3 nil)]}").unwrap());
+/// assert_eq!(config.to_edn(), Edn::from_str(&config.serialize()).unwrap());
+/// ```
+///
+/// Implemented for the same types as `Serialize`, for `Edn` itself, and by `#[derive(Serialize)]`.
+pub trait ToEdn {
+    fn to_edn(&self) -> Edn;
+
+    /// The `Edn` for `self` as a map key, what `Serialize::serialize_key_into` writes.
+    fn to_edn_key(&self) -> Edn {
+        self.to_edn()
+    }
+}
+
+/// `ToEdnMap` is implemented by the types that always convert into an `Edn::Map`, which are the
+/// types a derived struct can `#[edn(flatten)]`.
+/// ```rust
+/// use edn_rs::{map, Edn, ToEdnMap};
+/// use std::collections::BTreeMap;
+/// use std::str::FromStr;
+///
+/// let extra: Option<BTreeMap<String, u8>> = Some(map! {"max_retries".to_string() => 3});
+///
+/// assert_eq!(Edn::Map(extra.to_edn_map()), Edn::from_str("{:max-retries 3}").unwrap());
+/// assert_eq!(None::<BTreeMap<String, u8>>.to_edn_map().len(), 0);
+/// ```
+///
+/// Implemented for `BTreeMap`, `HashMap`, `Option` of a map, which is empty for `None`, and by
+/// `#[derive(Serialize)]` for structs with named fields.
+pub trait ToEdnMap: ToEdn {
+    fn to_edn_map(&self) -> Map;
+}
+
+impl ToEdn for Edn {
+    fn to_edn(&self) -> Edn {
+        self.clone()
+    }
+}
+
+macro_rules! to_edn_primitives {
+    ( $( $name:ty ),+ ) => {
+        $(
+            impl ToEdn for $name {
+                fn to_edn(&self) -> Edn {
+                    Edn::from(*self)
+                }
+            }
+        )+
+    };
+}
+
+to_edn_primitives![i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, f64, bool, char];
+
+// Widening an `f32` would show its binary error, like `12.100000381469727` for `12.1f32`, so it
+// goes through the shortest decimal that `Serialize` prints instead.
+impl ToEdn for f32 {
+    fn to_edn(&self) -> Edn {
+        Edn::from(
+            self.to_string()
+                .parse::<f64>()
+                .unwrap_or_else(|_| f64::from(*self)),
+        )
+    }
+}
+
+impl ToEdn for () {
+    fn to_edn(&self) -> Edn {
+        Edn::Nil
+    }
+}
+
+impl ToEdn for String {
+    fn to_edn(&self) -> Edn {
+        Edn::Str(self.as_str().into())
+    }
+
+    fn to_edn_key(&self) -> Edn {
+        self.as_str().to_edn_key()
+    }
+}
+
+impl ToEdn for str {
+    fn to_edn(&self) -> Edn {
+        Edn::Str(self.into())
+    }
+
+    fn to_edn_key(&self) -> Edn {
+        string_key(self).map_or_else(|| self.to_edn(), |key| Edn::Key(key.into()))
+    }
+}
+
+macro_rules! to_edn_wide_ints {
+    ( $( $name:ty ),+ ) => {
+        $(
+            impl ToEdn for $name {
+                fn to_edn(&self) -> Edn {
+                    u64::try_from(*self)
+                        .map(Edn::UInt)
+                        .or_else(|_| i64::try_from(*self).map(Edn::Int))
+                        .unwrap_or_else(|_| Edn::Str(self.to_string().into()))
+                }
+            }
+        )+
+    };
+}
+
+to_edn_wide_ints![i128, u128];
+
+macro_rules! to_edn_non_zero {
+    ( $( $name:ty ),+ ) => {
+        $(
+            impl ToEdn for $name {
+                fn to_edn(&self) -> Edn {
+                    self.get().to_edn()
+                }
+            }
+        )+
+    };
+}
+
+to_edn_non_zero![
+    NonZeroI8,
+    NonZeroI16,
+    NonZeroI32,
+    NonZeroI64,
+    NonZeroI128,
+    NonZeroIsize,
+    NonZeroU8,
+    NonZeroU16,
+    NonZeroU32,
+    NonZeroU64,
+    NonZeroU128,
+    NonZeroUsize
+];
+
+macro_rules! to_edn_transparent {
+    ( $( $name:ident ),+ ) => {
+        $(
+            impl<T: ToEdn + ?Sized> ToEdn for $name<T> {
+                fn to_edn(&self) -> Edn {
+                    (**self).to_edn()
+                }
+
+                fn to_edn_key(&self) -> Edn {
+                    (**self).to_edn_key()
+                }
+            }
+        )+
+    };
+}
+
+to_edn_transparent![Box, Rc, Arc];
+
+macro_rules! to_edn_map_transparent {
+    ( $( $name:ident ),+ ) => {
+        $(
+            impl<T: ToEdnMap + ?Sized> ToEdnMap for $name<T> {
+                fn to_edn_map(&self) -> Map {
+                    (**self).to_edn_map()
+                }
+            }
+        )+
+    };
+}
+
+to_edn_map_transparent![Box, Rc, Arc];
+
+impl<T: ToEdnMap + ?Sized> ToEdnMap for &T {
+    fn to_edn_map(&self) -> Map {
+        (**self).to_edn_map()
+    }
+}
+
+impl<T: ToEdn + ?Sized> ToEdn for &T {
+    fn to_edn(&self) -> Edn {
+        (**self).to_edn()
+    }
+
+    fn to_edn_key(&self) -> Edn {
+        (**self).to_edn_key()
+    }
+}
+
+impl<T: ToEdn + ToOwned + ?Sized> ToEdn for Cow<'_, T> {
+    fn to_edn(&self) -> Edn {
+        (**self).to_edn()
+    }
+
+    fn to_edn_key(&self) -> Edn {
+        (**self).to_edn_key()
+    }
+}
+
+impl<T: ToEdn> ToEdn for Wrapping<T> {
+    fn to_edn(&self) -> Edn {
+        self.0.to_edn()
+    }
+}
+
+impl<T: ToEdn> ToEdn for Reverse<T> {
+    fn to_edn(&self) -> Edn {
+        self.0.to_edn()
+    }
+}
+
+impl<T: ToEdn, E: ToEdn> ToEdn for Result<T, E> {
+    fn to_edn(&self) -> Edn {
+        match self {
+            Ok(t) => Edn::Tagged(Tagged::new("Ok", t.to_edn())),
+            Err(e) => Edn::Tagged(Tagged::new("Err", e.to_edn())),
+        }
+    }
+}
+
+impl ToEdn for Ordering {
+    fn to_edn(&self) -> Edn {
+        Edn::from(*self as i8)
+    }
+}
+
+impl<T: ToEdn> ToEdn for Option<T> {
+    fn to_edn(&self) -> Edn {
+        self.as_ref().map_or(Edn::Nil, ToEdn::to_edn)
+    }
+}
+
+impl<T: ToEdnMap> ToEdnMap for Option<T> {
+    fn to_edn_map(&self) -> Map {
+        self.as_ref().map_or_else(Map::empty, ToEdnMap::to_edn_map)
+    }
+}
+
+impl<T: ToEdn> ToEdn for Vec<T> {
+    fn to_edn(&self) -> Edn {
+        Edn::Vector(self.iter().map(ToEdn::to_edn).collect::<Vector>())
+    }
+}
+
+impl<T: ToEdn> ToEdn for LinkedList<T> {
+    fn to_edn(&self) -> Edn {
+        Edn::List(self.iter().map(ToEdn::to_edn).collect::<List>())
+    }
+}
+
+impl<T: ToEdn> ToEdn for VecDeque<T> {
+    fn to_edn(&self) -> Edn {
+        Edn::Vector(self.iter().map(ToEdn::to_edn).collect::<Vector>())
+    }
+}
+
+impl<T: ToEdn> ToEdn for BinaryHeap<T> {
+    fn to_edn(&self) -> Edn {
+        Edn::Vector(self.iter().map(ToEdn::to_edn).collect::<Vector>())
+    }
+}
+
+impl<T: ToEdn> ToEdn for [T] {
+    fn to_edn(&self) -> Edn {
+        Edn::Vector(self.iter().map(ToEdn::to_edn).collect::<Vector>())
+    }
+}
+
+impl<T: ToEdn, const N: usize> ToEdn for [T; N] {
+    fn to_edn(&self) -> Edn {
+        Edn::Vector(self.iter().map(ToEdn::to_edn).collect::<Vector>())
+    }
+}
+
+impl<T: ToEdn> ToEdn for BTreeSet<T> {
+    fn to_edn(&self) -> Edn {
+        Edn::Set(self.iter().map(ToEdn::to_edn).collect::<Set>())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: ToEdn, H: std::hash::BuildHasher> ToEdn for std::collections::HashSet<T, H> {
+    fn to_edn(&self) -> Edn {
+        Edn::Set(self.iter().map(ToEdn::to_edn).collect::<Set>())
+    }
+}
+
+fn entries_to_edn_map<'a, K, T>(entries: impl Iterator<Item = (&'a K, &'a T)>) -> Map
+where
+    K: ToEdn + 'a,
+    T: ToEdn + 'a,
+{
+    entries
+        .map(|(k, v)| (k.to_edn_key().to_string(), v.to_edn()))
+        .collect::<Map>()
+}
+
+impl<K: ToEdn, T: ToEdn> ToEdn for BTreeMap<K, T> {
+    fn to_edn(&self) -> Edn {
+        Edn::Map(self.to_edn_map())
+    }
+}
+
+impl<K: ToEdn, T: ToEdn> ToEdnMap for BTreeMap<K, T> {
+    fn to_edn_map(&self) -> Map {
+        entries_to_edn_map(self.iter())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K: ToEdn, T: ToEdn, H: std::hash::BuildHasher> ToEdn for std::collections::HashMap<K, T, H> {
+    fn to_edn(&self) -> Edn {
+        Edn::Map(self.to_edn_map())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K: ToEdn, T: ToEdn, H: std::hash::BuildHasher> ToEdnMap
+    for std::collections::HashMap<K, T, H>
+{
+    fn to_edn_map(&self) -> Map {
+        entries_to_edn_map(self.iter())
+    }
+}
+
+impl ToEdn for Keyword {
+    fn to_edn(&self) -> Edn {
+        Edn::Key(self.to_string().into())
+    }
+}
+
+impl ToEdn for Duration {
+    fn to_edn(&self) -> Edn {
+        let map = [
+            (":secs".to_string(), Edn::UInt(self.as_secs())),
+            (":nanos".to_string(), Edn::UInt(self.subsec_nanos().into())),
+        ];
+        Edn::Tagged(Tagged::new(
+            DURATION_TAG,
+            Edn::Map(map.into_iter().collect()),
+        ))
+    }
+}
+
+macro_rules! to_edn_tagged_display {
+    ( $tag:ident: $( $name:ty ),+ ) => {
+        $(
+            impl ToEdn for $name {
+                fn to_edn(&self) -> Edn {
+                    Edn::Tagged(Tagged::new($tag, Edn::Str(self.to_string().into())))
+                }
+            }
+        )+
+    };
+}
+
+to_edn_tagged_display![IP_TAG: IpAddr, Ipv4Addr, Ipv6Addr];
+to_edn_tagged_display![SOCKET_TAG: SocketAddr, SocketAddrV4, SocketAddrV6];
+
+impl ToEdn for Bytes {
+    fn to_edn(&self) -> Edn {
+        base64::to_edn(self)
+    }
+}
+
+#[cfg(feature = "std")]
+impl ToEdn for std::time::SystemTime {
+    fn to_edn(&self) -> Edn {
+        Edn::Tagged(Tagged::new(inst::TAG, Edn::Str(inst::format(*self).into())))
+    }
+}
+
+// A path that isn't UTF-8 has its invalid parts replaced, like `Serialize` does.
+#[cfg(feature = "std")]
+impl ToEdn for std::path::Path {
+    fn to_edn(&self) -> Edn {
+        Edn::Str(self.to_string_lossy().as_ref().into())
+    }
+}
+
+#[cfg(feature = "std")]
+impl ToEdn for std::path::PathBuf {
+    fn to_edn(&self) -> Edn {
+        self.as_path().to_edn()
+    }
+}
+
+macro_rules! to_edn_tuples {
+    ( $( ( $( $name:ident ),+ ) ),+ ) => {
+        $(
+            impl<$( $name: ToEdn ),+> ToEdn for ($( $name, )+) {
+                #[allow(non_snake_case)]
+                fn to_edn(&self) -> Edn {
+                    let ($( $name, )+) = self;
+                    Edn::List(List::new(vec![$( $name.to_edn() ),+]))
+                }
+            }
+        )+
+    };
+}
+
+to_edn_tuples![
+    (A),
+    (A, B),
+    (A, B, C),
+    (A, B, C, D),
+    (A, B, C, D, E),
+    (A, B, C, D, E, F),
+    (A, B, C, D, E, F, G),
+    (A, B, C, D, E, F, G, H),
+    (A, B, C, D, E, F, G, H, I),
+    (A, B, C, D, E, F, G, H, I, J),
+    (A, B, C, D, E, F, G, H, I, J, K),
+    (A, B, C, D, E, F, G, H, I, J, K, L),
+    (A, B, C, D, E, F, G, H, I, J, K, L, M),
+    (A, B, C, D, E, F, G, H, I, J, K, L, M, N),
+    (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O),
+    (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P)
+];
+
+#[cfg(test)]
+mod test {
+    use core::str::FromStr;
+
+    use super::*;
+    use crate::Serialize;
+
+    fn same_as_serialize<T: ToEdn + Serialize>(value: &T) {
+        assert_eq!(value.to_edn(), Edn::from_str(&value.serialize()).unwrap());
+    }
+
+    #[test]
+    fn matches_what_serialize_parses_into() {
+        same_as_serialize(&vec![-1i8, 2]);
+        same_as_serialize(&vec![12.1f32, -0.5]);
+        same_as_serialize(&(1u64, "two", 'c', (), Some(false), None::<f64>));
+        same_as_serialize(&[1u8, 2].into_iter().collect::<BTreeSet<_>>());
+        same_as_serialize(&core::iter::once("a").collect::<LinkedList<_>>());
+        same_as_serialize(&map! {"a b_c" => vec![String::from("x")]});
+        same_as_serialize(&map! {"k".to_string() => Edn::from_str("#{:a [1]}").unwrap()});
+        same_as_serialize(&[1u8, 2].into_iter().collect::<VecDeque<_>>());
+        same_as_serialize(&[3u8, 1].into_iter().collect::<BinaryHeap<_>>());
+        same_as_serialize(&[[1i16, 2], [3, 4]]);
+        same_as_serialize(&Box::<[u8]>::from([5u8, 6]));
+        same_as_serialize(&(Rc::new("a"), Arc::new('b'), Cow::Borrowed("c")));
+        same_as_serialize(&(i128::MIN, u128::MAX, 7i128, NonZeroU8::new(9)));
+        same_as_serialize(&(Wrapping(1u8), Reverse(-2i8), Ordering::Less));
+        same_as_serialize(&vec![Ok::<u8, String>(1), Err("no".to_string())]);
+        same_as_serialize(&(Duration::new(3, 7), Bytes(vec![1, 2, 3, 4])));
+        same_as_serialize(&(IpAddr::from([1, 2, 3, 4]), Ipv6Addr::LOCALHOST));
+        same_as_serialize(&SocketAddr::from(([10, 0, 0, 1], 80)));
+        #[cfg(feature = "std")]
+        same_as_serialize(&(
+            std::time::UNIX_EPOCH + Duration::from_millis(1),
+            std::path::PathBuf::from("a/b"),
+        ));
+        same_as_serialize(&(
+            1u8, 2u8, 3u8, 4u8, 5u8, 6u8, 7u8, 8u8, 9u8, 10u8, 11u8, 12u8, 13u8, 14u8, 15u8, 16u8,
+        ));
+    }
+}
@@ -0,0 +1,344 @@
+#![cfg(feature = "derive")]
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use edn_rs::{Deserialize, Edn, EdnError, Serialize, ToEdn};
+
+    fn round_trip<T: Serialize + Deserialize + PartialEq + std::fmt::Debug>(value: &T) {
+        assert_eq!(
+            edn_rs::from_str::<T>(&edn_rs::to_string(value)).as_ref(),
+            Ok(value)
+        );
+    }
+
+    fn edn<T: Serialize>(value: &T) -> Edn {
+        Edn::from_str(&edn_rs::to_string(value)).unwrap()
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    #[edn(rename_all = "camelCase")]
+    struct Retry {
+        max_retries: u8,
+        #[edn(rename = "wait")]
+        backoff_ms: Option<u64>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    #[edn(namespace = "person")]
+    struct Person {
+        name: String,
+        #[edn(rename = "db/id")]
+        id: u64,
+        #[edn(skip)]
+        cache: Vec<u8>,
+        #[edn(default = "unknown")]
+        city: String,
+        #[edn(flatten)]
+        retry: Retry,
+        #[edn(with = "upper")]
+        nickname: String,
+    }
+
+    fn unknown() -> String {
+        "unknown".to_string()
+    }
+
+    mod upper {
+        use core::fmt;
+
+        use edn_rs::{Edn, EdnError, Serialize};
+
+        pub fn serialize_into(value: &str, w: &mut dyn fmt::Write) -> fmt::Result {
+            value.to_uppercase().serialize_into(w)
+        }
+
+        pub fn deserialize(edn: &Edn) -> Result<String, EdnError> {
+            edn_rs::from_edn::<String>(edn).map(|s| s.to_lowercase())
+        }
+    }
+
+    fn person() -> Person {
+        Person {
+            name: "rose".to_string(),
+            id: 7,
+            cache: Vec::new(),
+            city: "porto".to_string(),
+            retry: Retry {
+                max_retries: 3,
+                backoff_ms: None,
+            },
+            nickname: "ro".to_string(),
+        }
+    }
+
+    #[test]
+    fn structs_use_the_field_attributes() {
+        assert_eq!(
+            edn_rs::to_string(&Retry {
+                max_retries: 1,
+                backoff_ms: Some(10)
+            }),
+            "{ :maxRetries 1, :wait 10, }"
+        );
+        assert_eq!(
+            edn(&person()),
+            Edn::from_str(
+                "{:person/name \"rose\" :db/id 7 :person/city \"porto\"
+                  :maxRetries 3 :wait nil :person/nickname \"RO\"}"
+            )
+            .unwrap()
+        );
+        round_trip(&person());
+    }
+
+    #[test]
+    fn missing_keys_use_defaults_or_nil() {
+        let person: Person = edn_rs::from_str(
+            "{:person/name \"ana\" :db/id 1 :maxRetries 0 :person/nickname \"A\"}",
+        )
+        .unwrap();
+        assert_eq!(person.city, "unknown");
+        assert_eq!(person.retry.backoff_ms, None);
+        assert_eq!(person.nickname, "a");
+
+        assert!(edn_rs::from_str::<Person>("{:person/name \"ana\"}").is_err());
+        assert_eq!(
+            edn_rs::from_str::<Retry>("[1]"),
+            Err(EdnError::Type {
+                expected: "map",
+                found: "vector"
+            })
+        );
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    #[edn(repr = "keyword", rename_all = "kebab-case", namespace = "status")]
+    enum Status {
+        Active,
+        OnHold,
+        #[edn(rename = "gone")]
+        Deleted,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    enum Shape {
+        Empty,
+        Circle(f64),
+        Point(i64, i64),
+        Rect {
+            #[edn(rename = "w")]
+            width: u32,
+            height: u32,
+        },
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    #[edn(repr = "map", rename_all = "snake_case")]
+    enum Event {
+        Started,
+        MovedTo(i64, i64),
+        Renamed(String),
+        Resized { width: u32 },
+    }
+
+    #[test]
+    fn enum_representations() {
+        assert_eq!(edn_rs::to_string(&Status::OnHold), ":status/on-hold");
+        assert_eq!(edn_rs::to_string(&Status::Deleted), ":status/gone");
+        assert_eq!(edn_rs::from_str(":status/active"), Ok(Status::Active));
+
+        assert_eq!(
+            edn(&vec![
+                Shape::Empty,
+                Shape::Circle(1.5),
+                Shape::Point(1, -2),
+                Shape::Rect {
+                    width: 1,
+                    height: 2
+                }
+            ]),
+            Edn::from_str("[:Empty #Circle 1.5 #Point [1 -2] #Rect {:w 1 :height 2}]").unwrap()
+        );
+
+        assert_eq!(
+            edn(&vec![
+                Event::Started,
+                Event::MovedTo(0, 1),
+                Event::Renamed("b".to_string()),
+                Event::Resized { width: 3 }
+            ]),
+            Edn::from_str("[:started {:moved_to [0 1]} {:renamed \"b\"} {:resized {:width 3}}]")
+                .unwrap()
+        );
+
+        for status in [Status::Active, Status::OnHold, Status::Deleted] {
+            round_trip(&status);
+        }
+        for shape in [
+            Shape::Empty,
+            Shape::Circle(-0.5),
+            Shape::Point(3, 4),
+            Shape::Rect {
+                width: 5,
+                height: 6,
+            },
+        ] {
+            round_trip(&shape);
+        }
+        for event in [
+            Event::Started,
+            Event::MovedTo(-1, 2),
+            Event::Renamed("c".to_string()),
+            Event::Resized { width: 9 },
+        ] {
+            round_trip(&event);
+        }
+    }
+
+    #[test]
+    fn unknown_variants_are_errors() {
+        assert_eq!(
+            edn_rs::from_str::<Status>(":status/deleted"),
+            Err(EdnError::Deserialize(
+                "couldn't convert `:status/deleted` into `Status`".to_string()
+            ))
+        );
+        assert!(edn_rs::from_str::<Shape>("{:Circle 1.5}").is_err());
+        assert!(edn_rs::from_str::<Event>("#renamed \"b\"").is_err());
+        assert!(edn_rs::from_str::<Shape>("#Point [1]").is_err());
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Meters(f64);
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Pair<T>(T, T);
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Marker;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Labeled<T> {
+        labels: Vec<T>,
+        marker: Marker,
+    }
+
+    #[test]
+    fn tuple_unit_and_generic_structs() {
+        assert_eq!(edn_rs::to_string(&Meters(2.5)), "2.5");
+        assert_eq!(edn_rs::to_string(&Pair('a', 'b')), "(\\a, \\b)");
+        assert_eq!(edn_rs::to_string(&Marker), "nil");
+        assert_eq!(edn_rs::from_str("[1 2]"), Ok(Pair(1u8, 2)));
+
+        round_trip(&Meters(-1.0));
+        round_trip(&Pair("x".to_string(), "y".to_string()));
+        round_trip(&Marker);
+        round_trip(&Labeled {
+            labels: vec![Pair(1, 2)],
+            marker: Marker,
+        });
+    }
+
+    #[test]
+    fn to_edn_builds_what_serialize_writes() {
+        assert_eq!(person().to_edn(), edn(&person()));
+        assert_eq!(Status::OnHold.to_edn(), edn(&Status::OnHold));
+
+        let shapes = vec![
+            Shape::Empty,
+            Shape::Circle(1.5),
+            Shape::Point(1, -2),
+            Shape::Rect {
+                width: 1,
+                height: 2,
+            },
+        ];
+        assert_eq!(shapes.to_edn(), edn(&shapes));
+
+        let events = vec![
+            Event::Started,
+            Event::MovedTo(0, 1),
+            Event::Renamed("b".to_string()),
+            Event::Resized { width: 3 },
+        ];
+        assert_eq!(events.to_edn(), edn(&events));
+
+        let labeled = Labeled {
+            labels: vec![Pair(1, 2)],
+            marker: Marker,
+        };
+        assert_eq!(labeled.to_edn(), edn(&labeled));
+        assert_eq!(Meters(2.5).to_edn(), Edn::from(2.5));
+    }
+
+    #[derive(Serialize, Debug)]
+    struct Extended {
+        a: u8,
+        #[edn(flatten)]
+        extra: std::collections::BTreeMap<edn_rs::Keyword, u8>,
+        #[edn(flatten)]
+        more: Option<std::collections::BTreeMap<edn_rs::Keyword, Edn>>,
+    }
+
+    #[test]
+    fn flattened_keys_never_repeat() {
+        let extended = Extended {
+            a: 1,
+            extra: [("a", 2), ("b", 3)]
+                .into_iter()
+                .map(|(k, v)| (edn_rs::Keyword::new(k), v))
+                .collect(),
+            more: Some(
+                [("b", Edn::from(4)), ("c", Edn::from(5))]
+                    .into_iter()
+                    .map(|(k, v)| (edn_rs::Keyword::new(k), v))
+                    .collect(),
+            ),
+        };
+        assert_eq!(edn_rs::to_string(&extended), "{ :a 1, :b 3, :c 5, }");
+        assert_eq!(extended.to_edn(), edn(&extended));
+    }
+
+    #[test]
+    fn flattening_none_writes_nothing() {
+        let extended = Extended {
+            a: 1,
+            extra: std::collections::BTreeMap::new(),
+            more: None,
+        };
+        assert_eq!(edn_rs::to_string(&vec![extended]), "[{ :a 1, }]");
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Telemetry {
+        at: std::time::SystemTime,
+        took: std::time::Duration,
+        peer: std::net::SocketAddr,
+        log: std::path::PathBuf,
+        #[edn(with = "edn_rs::base64")]
+        payload: Vec<u8>,
+    }
+
+    #[test]
+    fn std_types_need_no_hand_written_impls() {
+        let telemetry = Telemetry {
+            at: std::time::UNIX_EPOCH + std::time::Duration::from_millis(1_500),
+            took: std::time::Duration::from_micros(250),
+            peer: "10.0.0.1:443".parse().unwrap(),
+            log: "/tmp/a.log".into(),
+            payload: vec![0, 255],
+        };
+        assert_eq!(
+            edn(&telemetry),
+            Edn::from_str(
+                "{:at #inst \"1970-01-01T00:00:01.5Z\" :took #duration {:secs 0 :nanos 250000}
+                  :peer #socket \"10.0.0.1:443\" :log \"/tmp/a.log\" :payload #base64 \"AP8=\"}"
+            )
+            .unwrap()
+        );
+        assert_eq!(telemetry.to_edn(), edn(&telemetry));
+        round_trip(&telemetry);
+    }
+}
@@ -6,7 +6,7 @@ mod test {
     use core::str::FromStr;
 
     use edn::Error;
-    use edn_rs::{edn, from_edn, from_str, hmap, map, Edn, List, Map, Vector};
+    use edn_rs::{edn, from_edn, from_str, hmap, map, Edn, List, Map, Tagged, Vector};
 
     #[test]
     fn unit() {
@@ -29,18 +29,6 @@ mod test {
         assert_eq!(Edn::from_str(edn).unwrap(), Edn::Empty);
     }
 
-    #[test]
-    #[cfg(not(feature = "sets"))]
-    // Special case of running into a set without the feature enabled
-    fn parse_set_without_set_feature() {
-        assert_eq!(
-            Edn::from_str("#{true, \\c, 3,four, }"),
-            Err(Error::ParseEdn(
-                "Could not parse set due to feature not being enabled".to_string()
-            ))
-        )
-    }
-
     #[test]
     fn parse_commas_are_whitespace() {
         assert_eq!(Edn::from_str(",,,,, \r\n,,,").unwrap(), Edn::Empty);
@@ -50,7 +38,7 @@ mod test {
     fn parse_keyword() {
         assert_eq!(
             Edn::from_str(":keyword").unwrap(),
-            Edn::Key(":keyword".to_string())
+            Edn::Key(":keyword".into())
         );
     }
 
@@ -58,7 +46,7 @@ mod test {
     fn parse_str() {
         assert_eq!(
             Edn::from_str("\"hello world, from      RUST\"").unwrap(),
-            Edn::Str("hello world, from      RUST".to_string())
+            Edn::Str("hello world, from      RUST".into())
         );
     }
 
@@ -68,9 +56,7 @@ mod test {
 
         assert_eq!(
             Edn::from_str(edn).unwrap(),
-            Edn::Vector(Vector::new(vec![Edn::Str(
-                "hello brave new world".to_string()
-            )]))
+            Edn::Vector(Vector::new(vec![Edn::Str("hello brave new world".into())]))
         );
     }
 
@@ -78,7 +64,7 @@ mod test {
     fn parse_str_top_level_comment() {
         assert_eq!(
             Edn::from_str(";;; hello world string example\n\n;; deserialize the following string\n\n\"hello world, from      RUST\"").unwrap(),
-            Edn::Str("hello world, from      RUST".to_string())
+            Edn::Str("hello world, from      RUST".into())
         );
     }
 
@@ -86,7 +72,7 @@ mod test {
     fn parse_str_top_level_comment_whitespace() {
         assert_eq!(
             Edn::from_str("\n;;; hello world string example\n\n;; deserialize the following string\n\n,,\"hello world, from      RUST\"").unwrap(),
-            Edn::Str("hello world, from      RUST".to_string())
+            Edn::Str("hello world, from      RUST".into())
         );
     }
 
@@ -94,7 +80,7 @@ mod test {
     fn parse_str_looks_like_comment() {
         assert_eq!(
             Edn::from_str("\";;; hello world, from      RUST\n\"").unwrap(),
-            Edn::Str(";;; hello world, from      RUST\n".to_string())
+            Edn::Str(";;; hello world, from      RUST\n".into())
         );
     }
 
@@ -102,7 +88,7 @@ mod test {
     fn parse_str_with_escaped_characters() {
         assert_eq!(
             Edn::from_str(r#""hello\n \r \t \"world\" with escaped \\ characters""#).unwrap(),
-            Edn::Str("hello\n \r \t \"world\" with escaped \\ characters".to_string())
+            Edn::Str("hello\n \r \t \"world\" with escaped \\ characters".into())
         );
     }
 
@@ -114,6 +100,29 @@ mod test {
         );
     }
 
+    #[test]
+    fn parse_unicode_escapes() {
+        assert_eq!(
+            Edn::from_str(r#""caf\u00e9 \u00A0""#),
+            Ok(Edn::Str("café \u{a0}".into()))
+        );
+        assert_eq!(
+            Edn::from_str(r"[\u00e9 \u \u12]"),
+            Ok(Edn::Vector(Vector::new(vec![
+                Edn::Char('é'),
+                Edn::Char('u'),
+                Edn::Char('u'),
+                Edn::UInt(12)
+            ])))
+        );
+        assert_eq!(
+            Edn::from_str(r#""\u12""#),
+            Err(Error::ParseEdn(
+                "Invalid escape sequence \\u12\"".to_string()
+            ))
+        );
+    }
+
     #[test]
     fn parse_unterminated_string() {
         assert_eq!(
@@ -133,7 +142,7 @@ mod test {
         );
         assert_eq!(
             Edn::from_str("43/5143").unwrap(),
-            Edn::Rational("43/5143".to_string())
+            Edn::Rational("43/5143".into())
         );
         assert_eq!(
             Edn::from_str("999999999999999999999.0").unwrap(),
@@ -151,10 +160,7 @@ mod test {
         assert_eq!(Edn::from_str("true").unwrap(), Edn::Bool(true));
         assert_eq!(Edn::from_str("false").unwrap(), Edn::Bool(false));
         assert_eq!(Edn::from_str("nil").unwrap(), Edn::Nil);
-        assert_eq!(
-            Edn::from_str("\"true\"").unwrap(),
-            Edn::Str("true".to_string())
-        );
+        assert_eq!(Edn::from_str("\"true\"").unwrap(), Edn::Str("true".into()));
     }
 
     #[test]
@@ -165,9 +171,9 @@ mod test {
             Edn::from_str(edn),
             Ok(Edn::Vector(Vector::new(vec![
                 Edn::UInt(1),
-                Edn::Str("2".to_string()),
+                Edn::Str("2".into()),
                 Edn::Double(3.3.into()),
-                Edn::Key(":b".to_string()),
+                Edn::Key(":b".into()),
                 Edn::Bool(true),
                 Edn::Char('c')
             ])))
@@ -180,9 +186,9 @@ mod test {
             Edn::from_str("[11 \"2\" 3.3 :b true \\c]").unwrap(),
             Edn::Vector(Vector::new(vec![
                 Edn::UInt(11),
-                Edn::Str("2".to_string()),
+                Edn::Str("2".into()),
                 Edn::Double(3.3.into()),
-                Edn::Key(":b".to_string()),
+                Edn::Key(":b".into()),
                 Edn::Bool(true),
                 Edn::Char('c')
             ]))
@@ -195,9 +201,9 @@ mod test {
             Edn::from_str("[11 \"2\" 3.3 ; float in simple vec\n:b true \\c]").unwrap(),
             Edn::Vector(Vector::new(vec![
                 Edn::UInt(11),
-                Edn::Str("2".to_string()),
+                Edn::Str("2".into()),
                 Edn::Double(3.3.into()),
-                Edn::Key(":b".to_string()),
+                Edn::Key(":b".into()),
                 Edn::Bool(true),
                 Edn::Char('c')
             ]))
@@ -210,9 +216,9 @@ mod test {
             Edn::from_str("[11 \"2\" 3.3 :b true \\c; char in simple vec\n]").unwrap(),
             Edn::Vector(Vector::new(vec![
                 Edn::UInt(11),
-                Edn::Str("2".to_string()),
+                Edn::Str("2".into()),
                 Edn::Double(3.3.into()),
-                Edn::Key(":b".to_string()),
+                Edn::Key(":b".into()),
                 Edn::Bool(true),
                 Edn::Char('c')
             ]))
@@ -234,9 +240,9 @@ mod test {
             Edn::from_str(edn).unwrap(),
             Edn::Vector(Vector::new(vec![
                 Edn::UInt(11),
-                Edn::Str("2".to_string()),
+                Edn::Str("2".into()),
                 Edn::Double(3.3.into()),
-                Edn::Key(":b".to_string()),
+                Edn::Key(":b".into()),
                 Edn::Bool(true),
                 Edn::Char('c')
             ]))
@@ -285,9 +291,9 @@ mod test {
             Edn::from_str("(1 \"2\" 3.3 :b )").unwrap(),
             Edn::List(List::new(vec![
                 Edn::UInt(1),
-                Edn::Str("2".to_string()),
+                Edn::Str("2".into()),
                 Edn::Double(3.3.into()),
-                Edn::Key(":b".to_string()),
+                Edn::Key(":b".into()),
             ]))
         );
     }
@@ -300,9 +306,9 @@ mod test {
             Edn::from_str(edn),
             Ok(Edn::List(List::new(vec![
                 Edn::UInt(1),
-                Edn::Str("2".to_string()),
+                Edn::Str("2".into()),
                 Edn::Double(3.3.into()),
-                Edn::Key(":b".to_string()),
+                Edn::Key(":b".into()),
                 Edn::Vector(Vector::new(vec![Edn::Bool(true), Edn::Char('c')]))
             ])))
         );
@@ -314,9 +320,9 @@ mod test {
             Edn::from_str("(1 \"2\"; string in list\n3.3 :b )").unwrap(),
             Edn::List(List::new(vec![
                 Edn::UInt(1),
-                Edn::Str("2".to_string()),
+                Edn::Str("2".into()),
                 Edn::Double(3.3.into()),
-                Edn::Key(":b".to_string()),
+                Edn::Key(":b".into()),
             ]))
         );
     }
@@ -327,9 +333,9 @@ mod test {
             Edn::from_str("(1 \"2\" 3.3 :b; keyword in list\n)").unwrap(),
             Edn::List(List::new(vec![
                 Edn::UInt(1),
-                Edn::Str("2".to_string()),
+                Edn::Str("2".into()),
                 Edn::Double(3.3.into()),
-                Edn::Key(":b".to_string()),
+                Edn::Key(":b".into()),
             ]))
         );
     }
@@ -338,10 +344,8 @@ mod test {
     fn parse_simple_map() {
         assert_eq!(
             Edn::from_str("{:a \"2\" :b false :c nil }").unwrap(),
-            Edn::Map(Map::new(
-                map! {":a".to_string() => Edn::Str("2".to_string()),
-                ":b".to_string() => Edn::Bool(false), ":c".to_string() => Edn::Nil}
-            ))
+            Edn::Map(Map::new(map! {":a".to_string() => Edn::Str("2".into()),
+            ":b".to_string() => Edn::Bool(false), ":c".to_string() => Edn::Nil}))
         );
     }
 
@@ -352,7 +356,7 @@ mod test {
         assert_eq!(
             Edn::from_str(edn),
             Ok(Edn::Map(Map::new(
-                map! {":a".to_string() => Edn::Str("2".to_string()),
+                map! {":a".to_string() => Edn::Str("2".into()),
                 ":b".to_string() => Edn::Bool(true), ":c".to_string() => Edn::Nil}
             )))
         );
@@ -361,12 +365,12 @@ mod test {
     #[test]
     fn deser_btreemap() {
         let ns_map = Edn::Map(Map::new(map! {
-            ":a".to_string() => Edn::Vector(Vector::new(vec![Edn::Key(":val".to_string())])),
-            ":b".to_string() => Edn::Vector(Vector::new(vec![Edn::Key(":value".to_string())]))
+            ":a".to_string() => Edn::Vector(Vector::new(vec![Edn::Key(":val".into())])),
+            ":b".to_string() => Edn::Vector(Vector::new(vec![Edn::Key(":value".into())]))
         }));
         let expected = map! {
-            ":a".to_string() => vec![":val".to_string()],
-            ":b".to_string() => vec![":value".to_string()]
+            "a".to_string() => vec![":val".to_string()],
+            "b".to_string() => vec![":value".to_string()]
         };
         let map: BTreeMap<String, Vec<String>> = from_edn(&ns_map).unwrap();
         assert_eq!(map, expected);
@@ -380,8 +384,8 @@ mod test {
             ":b".to_string() => Edn::Bool(false)
         }));
         let expected = hmap! {
-            ":a".to_string() => true,
-            ":b".to_string() => false
+            "a".to_string() => true,
+            "b".to_string() => false
         };
         let map: std::collections::HashMap<String, bool> = from_edn(&ns_map).unwrap();
         assert_eq!(map, expected);
@@ -393,8 +397,7 @@ mod test {
             Edn::from_str("{:date  #inst \"2020-07-16T21:53:14.628-00:00\"}").unwrap(),
             Edn::Map(Map::new(map! {
                 ":date".to_string() =>
-                    Edn::Tagged("inst".to_string(),
-                                Box::new(Edn::Str("2020-07-16T21:53:14.628-00:00".to_string())))
+                    Edn::Tagged(Tagged::new("inst", Edn::Str("2020-07-16T21:53:14.628-00:00".into())))
             }))
         );
     }
@@ -406,10 +409,10 @@ mod test {
 
         assert_eq!(
             edn,
-            Edn::Tagged(
-                "uuid".to_string(),
-                Box::new(Edn::Str("af6d8699-f442-4dfd-8b26-37d80543186b".to_string()))
-            )
+            Edn::Tagged(Tagged::new(
+                "uuid",
+                Edn::Str("af6d8699-f442-4dfd-8b26-37d80543186b".into())
+            ))
         );
     }
 
@@ -417,7 +420,7 @@ mod test {
     fn parse_tagged_int() {
         assert_eq!(
             Edn::from_str("#iasdf 234").unwrap(),
-            Edn::Tagged(String::from("iasdf"), Box::new(Edn::UInt(234)))
+            Edn::Tagged(Tagged::new("iasdf", Edn::UInt(234)))
         );
     }
 
@@ -459,7 +462,7 @@ mod test {
         assert_eq!(
             Edn::from_str("#_ ,, #_{discard again} #_ {:and :again} :okay {:a map}").unwrap(),
             Edn::Map(Map::new(
-                map! {":a".to_string() => Edn::Symbol("map".to_string())}
+                map! {":a".to_string() => Edn::Symbol("map".into())}
             ))
         );
     }
@@ -478,7 +481,7 @@ mod test {
     fn parse_discard_end_of_seq() {
         assert_eq!(
             Edn::from_str("[:foo #_ foo]").unwrap(),
-            Edn::Vector(Vector::new(vec![Edn::Key(":foo".to_string())]))
+            Edn::Vector(Vector::new(vec![Edn::Key(":foo".into())]))
         );
     }
 
@@ -497,10 +500,10 @@ mod test {
         assert_eq!(
             Edn::from_str("#_\"random comment\" [:a :b :c #_(:hello :world) :d]").unwrap(),
             Edn::Vector(Vector::new(vec![
-                Edn::Key(":a".to_string()),
-                Edn::Key(":b".to_string()),
-                Edn::Key(":c".to_string()),
-                Edn::Key(":d".to_string())
+                Edn::Key(":a".into()),
+                Edn::Key(":b".into()),
+                Edn::Key(":c".into()),
+                Edn::Key(":d".into())
             ]))
         );
     }
@@ -510,7 +513,7 @@ mod test {
         assert_eq!(
             Edn::from_str("{ :a :something, :b false, :c nil, }").unwrap(),
             Edn::Map(Map::new(
-                map! {":a".to_string() => Edn::Key(":something".to_string()),
+                map! {":a".to_string() => Edn::Key(":something".into()),
                 ":b".to_string() => Edn::Bool(false), ":c".to_string() => Edn::Nil}
             ))
         );
@@ -522,7 +525,7 @@ mod test {
             Edn::from_str("{ :a \"hello\n \r \t \\\"world\\\" with escaped \\\\ characters\" }")
                 .unwrap(),
             Edn::Map(Map::new(
-                map! {":a".to_string() => Edn::Str("hello\n \r \t \"world\" with escaped \\ characters".to_string())}
+                map! {":a".to_string() => Edn::Str("hello\n \r \t \"world\" with escaped \\ characters".into())}
             ))
         );
     }
@@ -553,7 +556,7 @@ mod test {
         assert_eq!(
             Edn::from_str(" ;; this is a map\n{ :a \"hello\n \r \t \\\"world\\\" with escaped \\\\ characters\" }").unwrap(),
             Edn::Map(Map::new(
-                map! {":a".to_string() => Edn::Str("hello\n \r \t \"world\" with escaped \\ characters".to_string())}
+                map! {":a".to_string() => Edn::Str("hello\n \r \t \"world\" with escaped \\ characters".into())}
             ))
         );
     }
@@ -563,7 +566,7 @@ mod test {
         assert_eq!(
             Edn::from_str("{ :a \"hello\n \r \t \\\"world\\\" with escaped \\\\ characters\" ; escaped chars\n }").unwrap(),
             Edn::Map(Map::new(
-                map! {":a".to_string() => Edn::Str("hello\n \r \t \"world\" with escaped \\ characters".to_string())}
+                map! {":a".to_string() => Edn::Str("hello\n \r \t \"world\" with escaped \\ characters".into())}
             ))
         );
     }
@@ -573,7 +576,7 @@ mod test {
         assert_eq!(
             Edn::from_str(";; this is a map\n{ :a \"hello\n \r \t \\\"world\\\" with escaped \\\\ characters\" }\n ;; end of file\n").unwrap(),
             Edn::Map(Map::new(
-                map! {":a".to_string() => Edn::Str("hello\n \r \t \"world\" with escaped \\ characters".to_string())}
+                map! {":a".to_string() => Edn::Str("hello\n \r \t \"world\" with escaped \\ characters".into())}
             ))
         );
     }
@@ -583,7 +586,7 @@ mod test {
         assert_eq!(
             Edn::from_str(";; this is a map\n{ :a \"hello\n \r \t \\\"world\\\" with escaped \\\\ characters\" }\n ;; end of file").unwrap(),
             Edn::Map(Map::new(
-                map! {":a".to_string() => Edn::Str("hello\n \r \t \"world\" with escaped \\ characters".to_string())}
+                map! {":a".to_string() => Edn::Str("hello\n \r \t \"world\" with escaped \\ characters".into())}
             ))
         );
     }
@@ -592,14 +595,10 @@ mod test {
     fn parse_tagged_vec() {
         assert_eq!(
             Edn::from_str("#domain/model [1 2 3]").unwrap(),
-            Edn::Tagged(
-                String::from("domain/model"),
-                Box::new(Edn::Vector(Vector::new(vec![
-                    Edn::UInt(1),
-                    Edn::UInt(2),
-                    Edn::UInt(3)
-                ])))
-            )
+            Edn::Tagged(Tagged::new(
+                "domain/model",
+                Edn::Vector(Vector::new(vec![Edn::UInt(1), Edn::UInt(2), Edn::UInt(3)]))
+            ))
         );
     }
 
@@ -607,14 +606,10 @@ mod test {
     fn parse_tagged_vec_with_comment() {
         assert_eq!(
             Edn::from_str("#domain/model ; tagging this vector\n [1 2 3]").unwrap(),
-            Edn::Tagged(
-                String::from("domain/model"),
-                Box::new(Edn::Vector(Vector::new(vec![
-                    Edn::UInt(1),
-                    Edn::UInt(2),
-                    Edn::UInt(3)
-                ])))
-            )
+            Edn::Tagged(Tagged::new(
+                "domain/model",
+                Edn::Vector(Vector::new(vec![Edn::UInt(1), Edn::UInt(2), Edn::UInt(3)]))
+            ))
         );
     }
 
@@ -623,15 +618,12 @@ mod test {
         assert_eq!(
             Edn::from_str("{ :model #domain/model [1 2 3] :int 2 }").unwrap(),
             Edn::Map(Map::new(map! {
-                ":int".to_string() => Edn::UInt(2),
-                ":model".to_string() => Edn::Tagged(
-                String::from("domain/model"),
-                Box::new(Edn::Vector(Vector::new(vec![
-                    Edn::UInt(1),
-                    Edn::UInt(2),
-                    Edn::UInt(3)
-                ])))
-            )}))
+            ":int".to_string() => Edn::UInt(2),
+            ":model".to_string() => Edn::Tagged(Tagged::new("domain/model", Edn::Vector(Vector::new(vec![
+                Edn::UInt(1),
+                Edn::UInt(2),
+                Edn::UInt(3)
+            ]))))}))
         );
     }
 
@@ -639,14 +631,10 @@ mod test {
     fn parse_tagged_list() {
         assert_eq!(
             Edn::from_str("#domain/model (1 2 3)").unwrap(),
-            Edn::Tagged(
-                String::from("domain/model"),
-                Box::new(Edn::List(List::new(vec![
-                    Edn::UInt(1),
-                    Edn::UInt(2),
-                    Edn::UInt(3)
-                ])))
-            )
+            Edn::Tagged(Tagged::new(
+                "domain/model",
+                Edn::List(List::new(vec![Edn::UInt(1), Edn::UInt(2), Edn::UInt(3)]))
+            ))
         );
     }
 
@@ -654,10 +642,7 @@ mod test {
     fn parse_tagged_str() {
         assert_eq!(
             Edn::from_str("#domain/model \"hello\"").unwrap(),
-            Edn::Tagged(
-                String::from("domain/model"),
-                Box::new(Edn::Str(String::from("hello")))
-            )
+            Edn::Tagged(Tagged::new("domain/model", Edn::Str("hello".into())))
         );
     }
 
@@ -665,15 +650,15 @@ mod test {
     fn parse_tagged_map() {
         assert_eq!(
             Edn::from_str("#domain/model {1 2 3 4}").unwrap(),
-            Edn::Tagged(
-                String::from("domain/model"),
-                Box::new(Edn::Map(Map::new(map! {
+            Edn::Tagged(Tagged::new(
+                "domain/model",
+                Edn::Map(Map::new(map! {
                     "1".to_string() =>
                     Edn::UInt(2),
                     "3".to_string() =>
                     Edn::UInt(4)
-                })))
-            )
+                }))
+            ))
         );
     }
 
@@ -681,15 +666,18 @@ mod test {
     fn test_sym() {
         let edn: Edn = Edn::from_str("(a b c your-hair!-is+_parsed?)").unwrap();
         let expected = Edn::List(List::new(vec![
-            Edn::Symbol("a".to_string()),
-            Edn::Symbol("b".to_string()),
-            Edn::Symbol("c".to_string()),
-            Edn::Symbol("your-hair!-is+_parsed?".to_string()),
+            Edn::Symbol("a".into()),
+            Edn::Symbol("b".into()),
+            Edn::Symbol("c".into()),
+            Edn::Symbol("your-hair!-is+_parsed?".into()),
         ]));
         assert_eq!(edn, expected);
 
         let lorem = "Lorem-ipsum-dolor-sit-amet-consectetur-adipiscing-elit-sed-do-eiusmod-tempor-incididunt-ut-labore-et-dolore-magna-aliqua.-Ut-enim-ad-minim-veniam-quis-nostrud-exercitation-ullamco-laboris-nisi-ut-aliquip-ex-ea-commodo-consequat.-Duis-aute-irure-dolor-in-reprehenderit-in-voluptate-velit-esse-cillum-dolore-eu-fugiat-nulla-pariatur.-Excepteur-sint-occaecat-cupidatat-non-proident-sunt-in-culpa-qui-officia-deserunt-mollit-anim-id-est-laborum".to_string();
-        assert_eq!(Edn::from_str(lorem.as_str()).unwrap(), Edn::Symbol(lorem));
+        assert_eq!(
+            Edn::from_str(lorem.as_str()).unwrap(),
+            Edn::Symbol(lorem.into())
+        );
     }
 
     #[test]
@@ -699,10 +687,10 @@ mod test {
         let n: Edn = Edn::from_str("nTEST").unwrap();
         let err: Edn = Edn::from_str("fTE").unwrap();
 
-        assert_eq!(n, Edn::Symbol("nTEST".to_string()));
-        assert_eq!(f, Edn::Symbol("fTEST".to_string()));
-        assert_eq!(t, Edn::Symbol("tTEST".to_string()));
-        assert_eq!(err, Edn::Symbol("fTE".to_string()));
+        assert_eq!(n, Edn::Symbol("nTEST".into()));
+        assert_eq!(f, Edn::Symbol("fTEST".into()));
+        assert_eq!(t, Edn::Symbol("tTEST".into()));
+        assert_eq!(err, Edn::Symbol("fTE".into()));
     }
 
     #[test]
@@ -714,29 +702,26 @@ mod test {
 
         assert_eq!(
             res,
-            Edn::Tagged(
-                "domain/model".to_string(),
-                Box::new(Edn::Map(Map::new(map! {
+            Edn::Tagged(Tagged::new(
+                "domain/model",
+                Edn::Map(Map::new(map! {
                     "#keyword :4".to_string() => Edn::Map(
                         Map::new(map!
                             {
-                                ":cool-tagged".to_string() => Edn::Tagged(
-                                    "yay".to_string(),
-                                    Box::new(Edn::Map(
+                                ":cool-tagged".to_string() => Edn::Tagged(Tagged::new("yay", Edn::Map(
                                         Map::new(
                                             map!{
                                                 ":stuff".to_string() => Edn::Str(
-                                                    "hehe".to_string(),
+                                                    "hehe".into(),
                                                 )
                                             },
                                         ),
-                                    )),
-                                )
+                                    )))
                             },
                         ),
                     ),
                     "1".to_string() => Edn::Str(
-                        "hello".to_string(),
+                        "hello".into(),
                     ),
                     "3".to_string() => Edn::Vector(
                         Vector::new(
@@ -780,20 +765,17 @@ mod test {
                             ],
                         ),
                     ),
-                    "5".to_string() => Edn::Tagged(
-                        "wow".to_string(),
-                        Box::new(Edn::Map(
+                    "5".to_string() => Edn::Tagged(Tagged::new("wow", Edn::Map(
                             Map::new(map!
                                 {
                                     ":a".to_string() => Edn::Key(
-                                        ":b".to_string(),
+                                        ":b".into(),
                                     )
                                 },
                             ),
-                        )),
-                    )
-                },),),)
-            )
+                        )))
+                },),),
+            ))
         );
     }
 
@@ -809,7 +791,7 @@ mod test {
     fn parse_numberic_symbol_with_doube_e() {
         assert_eq!(
             Edn::from_str("5011227E71367421E12").unwrap(),
-            Edn::Symbol("5011227E71367421E12".to_string())
+            Edn::Symbol("5011227E71367421E12".into())
         );
     }
 
@@ -836,7 +818,7 @@ mod test {
             res,
             Edn::Double(0.000_000_000_005_011_227_713_674_21.into())
         );
-        assert_eq!(res.to_string(), "0.00000000000501122771367421");
+        assert_eq!(res.to_string(), "5.01122771367421e-12");
     }
 
     #[test]
@@ -895,17 +877,17 @@ mod test {
         assert_eq!(
             Edn::from_str("('(symbol))").unwrap(),
             Edn::List(List::new(vec![
-                Edn::Symbol("'".to_string()),
-                Edn::List(List::new(vec![Edn::Symbol("symbol".to_string()),]))
+                Edn::Symbol("'".into()),
+                Edn::List(List::new(vec![Edn::Symbol("symbol".into()),]))
             ]))
         );
 
         assert_eq!(
             Edn::from_str("(apply + '(1 2 3))").unwrap(),
             Edn::List(List::new(vec![
-                Edn::Symbol("apply".to_string()),
-                Edn::Symbol("+".to_string()),
-                Edn::Symbol("'".to_string()),
+                Edn::Symbol("apply".into()),
+                Edn::Symbol("+".into()),
+                Edn::Symbol("'".into()),
                 Edn::List(List::new(vec![Edn::UInt(1), Edn::UInt(2), Edn::UInt(3),]))
             ]))
         );
@@ -913,10 +895,8 @@ mod test {
         assert_eq!(
             Edn::from_str("('(''symbol'foo''bar''))").unwrap(),
             Edn::List(List::new(vec![
-                Edn::Symbol("'".to_string()),
-                Edn::List(List::new(vec![Edn::Symbol(
-                    "''symbol'foo''bar''".to_string()
-                ),]))
+                Edn::Symbol("'".into()),
+                Edn::List(List::new(vec![Edn::Symbol("''symbol'foo''bar''".into()),]))
             ]))
         );
     }
@@ -925,16 +905,16 @@ mod test {
     fn minus_char_symbol() {
         assert_eq!(
             Edn::from_str("-foobar").unwrap(),
-            Edn::Symbol("-foobar".to_string())
+            Edn::Symbol("-foobar".into())
         );
 
         assert_eq!(
             Edn::from_str("(+foobar +foo+bar+ +'- '-+)").unwrap(),
             Edn::List(List::new(vec![
-                Edn::Symbol("+foobar".to_string()),
-                Edn::Symbol("+foo+bar+".to_string()),
-                Edn::Symbol("+'-".to_string()),
-                Edn::Symbol("'-+".to_string()),
+                Edn::Symbol("+foobar".into()),
+                Edn::Symbol("+foo+bar+".into()),
+                Edn::Symbol("+'-".into()),
+                Edn::Symbol("'-+".into()),
             ]))
         );
 
@@ -994,4 +974,20 @@ mod test {
             ]))
         );
     }
+
+    #[test]
+    fn edn_fields_keep_their_value() {
+        let edn = Edn::from_str("[{:a #{1}} nil :k]").unwrap();
+        let values: Vec<Option<Edn>> = from_edn(&edn).unwrap();
+
+        assert_eq!(
+            values,
+            vec![
+                Some(Edn::from_str("{:a #{1}}").unwrap()),
+                None,
+                Some(Edn::Key(":k".into()))
+            ]
+        );
+        assert_eq!(from_edn::<Edn>(&edn), Ok(edn));
+    }
 }
@@ -1,4 +1,4 @@
-#[cfg(feature = "sets")]
+#[cfg(feature = "std")]
 #[cfg(test)]
 mod test {
     extern crate alloc;
@@ -7,14 +7,14 @@ mod test {
     use core::str::FromStr;
 
     use edn::{Error, List, Vector};
-    use edn_rs::{edn, from_edn, from_str, hset, map, set, Edn, Map, Set};
+    use edn_rs::{edn, from_edn, from_str, map, set, Edn, Map, Set, Tagged};
 
     #[test]
     fn parse_set_with_commas() {
         assert_eq!(
             Edn::from_str("#{true, \\c, 3,four, }").unwrap(),
             Edn::Set(Set::new(set![
-                Edn::Symbol("four".to_string()),
+                Edn::Symbol("four".into()),
                 Edn::Bool(true),
                 Edn::Char('c'),
                 Edn::UInt(3),
@@ -63,9 +63,9 @@ mod test {
             Ok(Edn::List(List::new(vec![
                 Edn::UInt(1),
                 Edn::Int(-10),
-                Edn::Str("2".to_string()),
+                Edn::Str("2".into()),
                 Edn::Double(3.3.into()),
-                Edn::Key(":b".to_string()),
+                Edn::Key(":b".into()),
                 Edn::Set(Set::new(set![Edn::Bool(true), Edn::Char('c')]))
             ])))
         );
@@ -76,7 +76,7 @@ mod test {
         assert_eq!(
             Edn::from_str("[:b ( 5 \\c #{true \\c 3 } ) ]").unwrap(),
             Edn::Vector(Vector::new(vec![
-                Edn::Key(":b".to_string()),
+                Edn::Key(":b".into()),
                 Edn::List(List::new(vec![
                     Edn::UInt(5),
                     Edn::Char('c'),
@@ -95,7 +95,7 @@ mod test {
         assert_eq!(
             Edn::from_str("[:b ( 5 \\c #{true \\c; char c in a set\n3 } ) ]").unwrap(),
             Edn::Vector(Vector::new(vec![
-                Edn::Key(":b".to_string()),
+                Edn::Key(":b".into()),
                 Edn::List(List::new(vec![
                     Edn::UInt(5),
                     Edn::Char('c'),
@@ -116,12 +116,12 @@ mod test {
         assert_eq!(
             Edn::from_str(edn),
             Ok(Edn::Map(Map::new(map! {
-            ":a".to_string() =>Edn::Str("2".to_string()),
+            ":a".to_string() =>Edn::Str("2".into()),
             ":b".to_string() => Edn::Vector(Vector::new(vec![Edn::Bool(true), Edn::Bool(false)])),
             ":c".to_string() => Edn::Set(Set::new(
                 set!{
-                    Edn::Map(Map::new(map!{":a".to_string() => Edn::Key(":b".to_string())})),
-                    Edn::Key(":A".to_string()),
+                    Edn::Map(Map::new(map!{":a".to_string() => Edn::Key(":b".into())})),
+                    Edn::Key(":A".into()),
                     Edn::Nil}))})))
         );
     }
@@ -134,12 +134,12 @@ mod test {
             )
             .unwrap(),
             Edn::Set(Set::new(set! {
-                Edn::Key(":a".to_string()),
-                Edn::Key(":b".to_string()),
+                Edn::Key(":a".into()),
+                Edn::Key(":b".into()),
                 Edn::Map(Map::new(map! {
-                    ":c".to_string() => Edn::Key(":d".to_string()),
-                    ":date".to_string() => Edn::Tagged("inst".to_string(), Box::new(Edn::Str("2020-07-16T21:53:14.628-00:00".to_string()))),
-                    "::c".to_string() => Edn::Key("::d".to_string())
+                    ":c".to_string() => Edn::Key(":d".into()),
+                    ":date".to_string() => Edn::Tagged(Tagged::new("inst", Edn::Str("2020-07-16T21:53:14.628-00:00".into()))),
+                    "::c".to_string() => Edn::Key("::d".into())
                 })),
                 Edn::Nil
             }))
@@ -162,14 +162,10 @@ mod test {
     fn parse_tagged_set() {
         assert_eq!(
             Edn::from_str("#domain/model #{1 2 3}").unwrap(),
-            Edn::Tagged(
-                String::from("domain/model"),
-                Box::new(Edn::Set(Set::new(set![
-                    Edn::UInt(1),
-                    Edn::UInt(2),
-                    Edn::UInt(3)
-                ])))
-            )
+            Edn::Tagged(Tagged::new(
+                "domain/model",
+                Edn::Set(Set::new(set![Edn::UInt(1), Edn::UInt(2), Edn::UInt(3)]))
+            ))
         );
     }
 
@@ -190,12 +186,12 @@ mod test {
     fn test_more_sym() {
         let edn: Edn = Edn::from_str("(a \\b \"c\" 5 #{hello world})").unwrap();
         let expected = Edn::List(List::new(vec![
-            Edn::Symbol("a".to_string()),
+            Edn::Symbol("a".into()),
             Edn::Char('b'),
-            Edn::Str("c".to_string()),
+            Edn::Str("c".into()),
             Edn::UInt(5u64),
             Edn::Set(Set::new(
-                set! { Edn::Symbol("hello".to_string()), Edn::Symbol("world".to_string()) },
+                set! { Edn::Symbol("hello".into()), Edn::Symbol("world".into()) },
             )),
         ]));
         assert_eq!(edn, expected);
@@ -218,8 +214,9 @@ mod test {
     }
 
     #[test]
-    #[cfg(feature = "std")]
+    #[cfg(feature = "ordered-float")]
     fn deser_hashset() {
+        use edn_rs::hset;
         use ordered_float::OrderedFloat;
 
         let set = Edn::Set(Set::new(set! {
@@ -29,4 +29,27 @@ mod tests {
         assert_eq!(edn_to_string_unwrap("[\\space \\@ \\` \\tab \\return \\newline \\# \\% \\' \\g \\( \\* \\j \\+ \\, \\l \\- \\. \\/ \\0 \\2 \\r \\: \\; \\< \\\\ \\] \\} \\~ \\? \\_]"),
         "[\\space \\@ \\` \\tab \\return \\newline \\# \\% \\' \\g \\( \\* \\j \\+ \\, \\l \\- \\. \\/ \\0 \\2 \\r \\: \\; \\< \\\\ \\] \\} \\~ \\? \\_]")
     }
+
+    #[test]
+    fn double_formatting() {
+        assert_eq!(
+            edn_to_string_unwrap("[1.0 -0.0 2.50 1e21 1E-7 0.1]"),
+            "[1.0 -0.0 2.5 1e21 1e-7 0.1]"
+        );
+        assert_eq!(
+            edn_to_string_unwrap("[##Inf ##-Inf ##NaN]"),
+            "[##Inf ##-Inf ##NaN]"
+        );
+        assert_eq!(Edn::Double(1.0.into()).to_string(), "1.0");
+        assert_eq!(
+            Edn::Double(f64::MAX.into()).to_string(),
+            "1.7976931348623157e308"
+        );
+        assert!(Edn::from_str("##Infinity").is_err());
+
+        for s in ["1.0", "-3.0", "1e21", "5e-324", "##Inf", "##NaN"] {
+            let edn = Edn::from_str(s).unwrap();
+            assert_eq!(Edn::from_str(&edn.to_string()).unwrap(), edn);
+        }
+    }
 }
@@ -85,8 +85,9 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "derive")]
     fn regression_str_to_uint_test() {
-        use edn_derive::Deserialize;
+        use edn_rs::Deserialize;
         use edn_rs::EdnError;
         #[derive(Deserialize, Debug, PartialEq)]
         struct A {
@@ -108,24 +109,24 @@ mod tests {
             Edn::UInt(3),
             Edn::List(List::new(vec![
                 Edn::Bool(false),
-                Edn::Key(":f".to_string()),
+                Edn::Key(":f".into()),
                 Edn::Nil,
-                Edn::Rational("3/4".to_string()),
+                Edn::Rational("3/4".into()),
                 Edn::Set(Set::new(set! {
-                    Edn::Rational("3/4".to_string())
+                    Edn::Rational("3/4".into())
                 })),
             ])),
             Edn::Map(Map::new(map![
-                    String::from("false") => Edn::Key(":f".to_string()),
-                    String::from("nil") => Edn::Rational("3/4".to_string()),
+                    String::from("false") => Edn::Key(":f".into()),
+                    String::from("nil") => Edn::Rational("3/4".into()),
                     String::from(":my-crazy-map") => Edn::Map(Map::new(map![
                         String::from("false") => Edn::Map(
                             Map::new( map![
-                                String::from(":f") => Edn::Key(String::from(":b"))
+                                String::from(":f") => Edn::Key(":b".into())
                             ])),
                         String::from("nil") => Edn::Vector(
                             Vector::new( vec![
-                                Edn::Rational("3/4".to_string()),
+                                Edn::Rational("3/4".into()),
                                 Edn::Int(1i64)
                             ]))
                 ]))
@@ -0,0 +1,69 @@
+#[cfg(test)]
+mod tests {
+    use core::str::FromStr;
+
+    use edn_rs::Edn;
+
+    // Values taken from `(hash (clojure.edn/read-string s))` on the JVM.
+    const JVM_HASHES: [(&str, i32); 29] = [
+        ("nil", 0),
+        ("true", 1231),
+        ("false", 1237),
+        ("1", 1_392_991_556),
+        ("-1", 1_651_860_712),
+        ("42", 1_871_679_806),
+        ("9223372036854775807", -2_106_506_049),
+        ("-9223372036854775808", 1_366_273_829),
+        ("18446744073709551615", -32),
+        ("1.0", 1_072_693_248),
+        ("3.14", 300_063_655),
+        ("-0.0", -2_147_483_648),
+        ("1/2", 3),
+        ("-3/4", -7),
+        ("\"\"", 0),
+        ("\"hello\"", 1_715_862_179),
+        ("\"héllo wörld\"", 588_849_372),
+        ("\\a", 97),
+        (":a", -2_123_407_586),
+        (":db/id", -1_388_397_098),
+        (":user/name", 1_848_814_598),
+        ("foo", -1_385_541_733),
+        ("clojure.core/map", -505_055_043),
+        ("[]", -2_017_569_654),
+        ("[1 2 3]", 736_442_005),
+        ("#{1 2 3}", 439_094_965),
+        ("{:a 1}", 1_772_842_048),
+        ("{:a 1 \"b\" [nil true]}", 1_738_656_577),
+        (
+            "#uuid \"7a6b6722-0221-4280-865e-ad41060d53b2\"",
+            -132_523_183,
+        ),
+    ];
+
+    #[test]
+    fn hasheq_matches_jvm() {
+        for (edn, expected) in JVM_HASHES {
+            assert_eq!(Edn::from_str(edn).unwrap().hasheq(), expected, "{edn}");
+        }
+    }
+
+    #[test]
+    fn hasheq_of_equal_collections() {
+        let list = Edn::from_str("(1 2 3)").unwrap();
+        let vector = Edn::from_str("[1 2 3]").unwrap();
+        let empty_set = Edn::from_str("#{}").unwrap();
+        let empty_map = Edn::from_str("{}").unwrap();
+
+        assert_eq!(list.hasheq(), vector.hasheq());
+        assert_eq!(empty_set.hasheq(), empty_map.hasheq());
+        assert_eq!(
+            Edn::from_str("{:a 1 :b 2}").unwrap().hasheq(),
+            Edn::from_str("{:b 2 :a 1}").unwrap().hasheq()
+        );
+    }
+
+    #[test]
+    fn hasheq_of_nan() {
+        assert_eq!(Edn::Double(f64::NAN.into()).hasheq(), 2_146_959_360);
+    }
+}
@@ -4,10 +4,13 @@ extern crate alloc;
 #[cfg(feature = "std")]
 extern crate std;
 
+pub mod derive;
 pub mod deserialize;
 pub mod deserialize_sets;
 pub mod emit;
 pub mod emit_json;
+pub mod hasheq;
 pub mod parse;
 pub mod parse_sets;
+pub mod round_trip;
 pub mod ser;
@@ -10,17 +10,17 @@ mod tests {
     fn parse_primitive_types() {
         assert_eq!(edn!(1), Edn::Int(1));
         assert_eq!(edn!(12.5), Edn::Double(12.5.into()));
-        assert_eq!(edn!(:key), Edn::Key(":key".to_string()));
+        assert_eq!(edn!(:key), Edn::Key(":key".into()));
         assert_eq!(
             edn!("this is a string"),
-            Edn::Str("this is a string".to_string())
+            Edn::Str("this is a string".into())
         );
-        assert_eq!(edn!(3 / 4), Edn::Rational("3/4".to_string()));
+        assert_eq!(edn!(3 / 4), Edn::Rational("3/4".into()));
         assert_eq!(edn!(true), Edn::Bool(true));
         assert_eq!(edn!(false), Edn::Bool(false));
         assert_eq!(edn!(nil), Edn::Nil);
-        assert_eq!(edn!(shsadc - has), Edn::Symbol(String::from("shsadc-has")));
-        assert_eq!(edn!(sym), Edn::Symbol(String::from("sym")));
+        assert_eq!(edn!(shsadc - has), Edn::Symbol("shsadc-has".into()));
+        assert_eq!(edn!(sym), Edn::Symbol("sym".into()));
     }
 
     #[test]
@@ -33,13 +33,13 @@ mod tests {
     #[test]
     fn parse_simple_vector() {
         let expected = Edn::Vector(Vector::new(vec![
-            Edn::Symbol("sym".to_string()),
+            Edn::Symbol("sym".into()),
             Edn::Double(1.2.into()),
             Edn::Int(3),
             Edn::Bool(false),
-            Edn::Key(":f".to_string()),
+            Edn::Key(":f".into()),
             Edn::Nil,
-            Edn::Rational("3/4".to_string()),
+            Edn::Rational("3/4".into()),
         ]));
 
         assert_eq!(edn!([ sym 1.2 3 false :f nil 3/4]), expected);
@@ -52,9 +52,9 @@ mod tests {
             Edn::Double(1.2.into()),
             Edn::Int(3),
             Edn::Bool(false),
-            Edn::Key(":f".to_string()),
+            Edn::Key(":f".into()),
             Edn::Nil,
-            Edn::Rational("3/4".to_string()),
+            Edn::Rational("3/4".into()),
         ]));
 
         assert_eq!(edn!((1 1.2 3 false :f nil 3/4)), expected);
@@ -64,7 +64,7 @@ mod tests {
     fn parse_simple_map() {
         let expected = Edn::Map(Map::new(map! {
             String::from("1.2") => Edn::Bool(false),
-            String::from(":b") => Edn::Rational(String::from("3/4"))
+            String::from(":b") => Edn::Rational("3/4".into())
         }));
 
         assert_eq!(edn!({1.2 false, :b 3/4}), expected);
@@ -78,9 +78,9 @@ mod tests {
             Edn::Int(3),
             Edn::Vector(Vector::new(vec![
                 Edn::Bool(false),
-                Edn::Key(":f".to_string()),
+                Edn::Key(":f".into()),
                 Edn::Nil,
-                Edn::Rational("3/4".to_string()),
+                Edn::Rational("3/4".into()),
             ])),
         ]));
 
@@ -95,9 +95,9 @@ mod tests {
             Edn::Int(3),
             Edn::List(List::new(vec![
                 Edn::Bool(false),
-                Edn::Key(":f".to_string()),
+                Edn::Key(":f".into()),
                 Edn::Nil,
-                Edn::Rational("3/4".to_string()),
+                Edn::Rational("3/4".into()),
             ])),
         ]));
 
@@ -111,8 +111,8 @@ mod tests {
             Edn::Double(1.2.into()),
             Edn::Int(3),
             Edn::Map(Map::new(map![
-                    String::from("false") => Edn::Key(":f".to_string()),
-                    String::from("nil") => Edn::Rational("3/4".to_string())
+                    String::from("false") => Edn::Key(":f".into()),
+                    String::from("nil") => Edn::Rational("3/4".into())
             ])),
         ]));
 
@@ -128,11 +128,11 @@ mod tests {
             Edn::Map(Map::new(map![
                     String::from("false") => Edn::Map(
                         Map::new( map![
-                            String::from(":f") => Edn::Key(String::from(":b"))
+                            String::from(":f") => Edn::Key(":b".into())
                         ])),
                     String::from("nil") => Edn::Vector(
                         Vector::new( vec![
-                            Edn::Rational("3/4".to_string()),
+                            Edn::Rational("3/4".into()),
                             Edn::Int(1i64)
                         ]))
             ])),
@@ -148,8 +148,8 @@ mod tests {
         assert_eq!(edn[1], edn!(1.2));
         assert_eq!(edn[1], Edn::Double(1.2f64.into()));
         assert_eq!(edn[3]["false"], edn!(:f));
-        assert_eq!(edn[3]["false"], Edn::Key(":f".to_string()));
-        assert_eq!(edn[3]["2"], Edn::Str("banana".to_string()));
-        assert_eq!(edn[3][2], Edn::Str("banana".to_string()));
+        assert_eq!(edn[3]["false"], Edn::Key(":f".into()));
+        assert_eq!(edn[3]["2"], Edn::Str("banana".into()));
+        assert_eq!(edn[3][2], Edn::Str("banana".into()));
     }
 }
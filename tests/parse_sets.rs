@@ -1,4 +1,3 @@
-#[cfg(feature = "sets")]
 #[cfg(test)]
 mod tests {
     extern crate alloc;
@@ -22,9 +21,9 @@ mod tests {
             Edn::Double(1.2.into()),
             Edn::Int(3),
             Edn::Bool(false),
-            Edn::Key(":f".to_string()),
+            Edn::Key(":f".into()),
             Edn::Nil,
-            Edn::Rational("3/4".to_string())
+            Edn::Rational("3/4".into())
         }));
 
         assert_eq!(edn!(#{1 1.2 3 false :f nil 3/4}), expected);
@@ -39,15 +38,15 @@ mod tests {
             Edn::List(
                 List::new( vec![
                     Edn::Bool(false),
-                    Edn::Key(":f".to_string()),
+                    Edn::Key(":f".into()),
                     Edn::Nil,
-                    Edn::Rational("3/4".to_string())
+                    Edn::Rational("3/4".into())
             ])),
             Edn::Vector(
                 Vector::new( vec![
                     Edn::Bool(true),
-                    Edn::Key(":b".to_string()),
-                    Edn::Rational("12/5".to_string())
+                    Edn::Key(":b".into()),
+                    Edn::Rational("12/5".into())
             ]))
         }));
 
@@ -0,0 +1,376 @@
+#[cfg(test)]
+mod tests {
+    extern crate alloc;
+
+    use alloc::borrow::Cow;
+    use alloc::collections::{BTreeMap, BinaryHeap, LinkedList, VecDeque};
+    use alloc::rc::Rc;
+    use alloc::sync::Arc;
+    use core::cmp::{Ordering, Reverse};
+    use core::fmt::Debug;
+    use core::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+    use core::num::{NonZeroI128, NonZeroI32, NonZeroU64, NonZeroU8, NonZeroUsize, Wrapping};
+    use core::time::Duration;
+
+    use edn_rs::{from_str, map, to_string, Bytes, Deserialize, Edn, EdnError, Keyword, Serialize};
+
+    type Sixteen = (
+        u8,
+        u16,
+        u32,
+        u64,
+        i8,
+        i16,
+        i32,
+        i64,
+        usize,
+        isize,
+        char,
+        bool,
+        (),
+        String,
+        f64,
+        Vec<u8>,
+    );
+
+    fn round_trip<T: Serialize + Deserialize + PartialEq + Debug>(value: &T) {
+        assert_eq!(from_str::<T>(&to_string(value)).as_ref(), Ok(value));
+    }
+
+    #[test]
+    fn tuples() {
+        round_trip(&(1u8,));
+        round_trip(&(1u8, "two".to_string()));
+        round_trip(&(1u8, 'c', -3i64, true, None::<u8>, 0.5f64, (), Some(2u16)));
+        // std only implements `PartialEq` and `Debug` for tuples up to 12 elements.
+        let sixteen = (
+            1u8,
+            2u16,
+            3u32,
+            4u64,
+            5i8,
+            6i16,
+            7i32,
+            8i64,
+            9usize,
+            10isize,
+            'k',
+            true,
+            (),
+            "n".to_string(),
+            15.5f64,
+            vec![16u8],
+        );
+        let edn = to_string(&sixteen);
+        let back: Sixteen = from_str(&edn).unwrap();
+        assert_eq!(to_string(&back), edn);
+        assert_eq!(back.15, vec![16]);
+        assert_eq!(from_str("[1 2]"), Ok((1u8, 2u8)));
+        assert!(from_str::<(u8, u8)>("(1 2 3)").is_err());
+        assert!(from_str::<(u8, u8)>("#{1 2}").is_err());
+    }
+
+    #[test]
+    fn arrays_and_slices() {
+        round_trip(&[1i32, -2, 3]);
+        round_trip(&[[1u8, 2], [3, 4]]);
+        round_trip(&[0u8; 0]);
+        round_trip(&Box::<[u8]>::from([5u8, 6]));
+        assert_eq!(to_string(&[1u8, 2][..]), "[1, 2]");
+        assert_eq!(from_str("(1 2)"), Ok([1u8, 2]));
+        assert!(from_str::<[u8; 3]>("[1 2]").is_err());
+    }
+
+    #[test]
+    fn pointers_and_cow() {
+        round_trip(&Box::new(1u8));
+        round_trip(&Rc::new("rc".to_string()));
+        round_trip(&Arc::new(vec!['a']));
+        round_trip(&Box::<str>::from("boxed"));
+        round_trip(&Cow::<str>::Owned("cow".to_string()));
+        round_trip(&Cow::<[u8]>::Owned(vec![1, 2]));
+        assert_eq!(to_string(&Cow::Borrowed("b")), "\"b\"");
+    }
+
+    #[test]
+    fn collections() {
+        round_trip(&[1u8, 2, 3].into_iter().collect::<VecDeque<_>>());
+        round_trip(
+            &["a".to_string(), "b".to_string()]
+                .into_iter()
+                .collect::<LinkedList<_>>(),
+        );
+        assert_eq!(from_str("#{1}"), Ok(vec![1u8]));
+
+        let heap = [3u8, 1, 2].into_iter().collect::<BinaryHeap<_>>();
+        let back: BinaryHeap<u8> = from_str(&to_string(&heap)).unwrap();
+        assert_eq!(back.into_sorted_vec(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn wide_integers() {
+        round_trip(&0i128);
+        round_trip(&i128::from(i64::MIN));
+        round_trip(&u128::from(u64::MAX));
+        round_trip(&i128::MIN);
+        round_trip(&i128::MAX);
+        round_trip(&u128::MAX);
+        assert_eq!(to_string(&-5i128), "-5");
+        assert_eq!(
+            to_string(&u128::MAX),
+            "\"340282366920938463463374607431768211455\""
+        );
+        assert!(from_str::<u128>("-1").is_err());
+    }
+
+    #[test]
+    fn non_zero_integers() {
+        round_trip(&NonZeroU8::new(1).unwrap());
+        round_trip(&NonZeroI32::new(-7).unwrap());
+        round_trip(&NonZeroU64::new(u64::MAX).unwrap());
+        round_trip(&NonZeroUsize::new(3).unwrap());
+        round_trip(&NonZeroI128::new(i128::MIN).unwrap());
+        assert!(matches!(
+            from_str::<NonZeroU8>("0"),
+            Err(EdnError::Deserialize(_))
+        ));
+    }
+
+    #[test]
+    fn result_and_ordering() {
+        round_trip(&Ok::<u8, String>(1));
+        round_trip(&Err::<u8, String>("no".to_string()));
+        assert_eq!(to_string(&Ok::<u8, ()>(1)), "#Ok 1");
+        assert!(from_str::<Result<u8, u8>>("#Maybe 1").is_err());
+
+        for ordering in [Ordering::Less, Ordering::Equal, Ordering::Greater] {
+            round_trip(&ordering);
+        }
+        assert_eq!(to_string(&Ordering::Less), "-1");
+        assert_eq!(from_str("42"), Ok(Ordering::Greater));
+    }
+
+    #[test]
+    fn wrappers_and_floats() {
+        round_trip(&Wrapping(250u8));
+        round_trip(&Reverse("r".to_string()));
+        round_trip(&1.5f32);
+        round_trip(&vec![-0.25f32, 8.0]);
+        round_trip(&vec![1.0f64, -0.0, 1e21, 1e-7, f64::MAX, f64::MIN_POSITIVE]);
+        round_trip(&vec![f64::INFINITY, f64::NEG_INFINITY]);
+        round_trip(&vec![f32::MAX, f32::MIN_POSITIVE, f32::NEG_INFINITY]);
+        assert!(from_str::<f64>(&to_string(&f64::NAN)).unwrap().is_nan());
+        assert!(from_str::<f32>(&to_string(&f32::NAN)).unwrap().is_nan());
+    }
+
+    #[test]
+    fn floats_never_read_back_as_integers() {
+        assert_eq!(to_string(&1.0f64), "1.0");
+        assert_eq!(to_string(&1e21f64), "1e21");
+        assert_eq!(to_string(&12.1f32), "12.1");
+        assert_eq!(to_string(&16_777_216f32), "16777216.0");
+        assert_eq!(
+            to_string(&[f64::NAN, f64::INFINITY, f64::NEG_INFINITY]),
+            "[##NaN, ##Inf, ##-Inf]"
+        );
+        assert_eq!(
+            from_str::<Edn>("1.0").unwrap().to_string(),
+            to_string(&1.0f64)
+        );
+    }
+
+    #[test]
+    fn maps_keep_their_keys() {
+        round_trip(&map! {"max-retries".to_string() => 1u8, "db/id".to_string() => 2});
+        round_trip(&map! {Edn::from("this is a key") => 1u8, Edn::from(":a") => 2});
+        round_trip(&map! {Keyword::new("max-retries") => 3u8, Keyword::new("db/id") => 4});
+        round_trip(&map! {-1i64 => 'a', 2 => 'b'});
+        round_trip(&map! {(1u8, true) => vec![()], (2, false) => vec![]});
+        round_trip(
+            &map! {Ordering::Less => "lt".to_string(), Ordering::Greater => "gt".to_string()},
+        );
+        round_trip(&map! {Ok::<u8, char>(1) => 1.5f64, Err('e') => -0.5});
+        round_trip(&map! {Some('k') => map! {vec![1u8] => None::<u8>}});
+
+        assert_eq!(to_string(&map! {"a b".to_string() => 1u8}), "{:a-b 1}");
+        assert_eq!(to_string(&map! {"a(b".to_string() => 1u8}), "{\"a(b\" 1}");
+        assert_eq!(to_string(&map! {Edn::from("a b") => 1u8}), "{\"a b\" 1}");
+        let strings: BTreeMap<String, u8> = from_str("{:a 1 \"b\" 2}").unwrap();
+        assert_eq!(strings, map! {"a".to_string() => 1, "b".to_string() => 2});
+        assert_eq!(to_string(&strings), "{:a 1, :b 2}");
+        let retries: BTreeMap<String, u8> =
+            from_str(&to_string(&map! {"max_retries".to_string() => 3u8})).unwrap();
+        assert_eq!(retries, map! {"max-retries".to_string() => 3});
+        assert_eq!(
+            from_str("{:a 1 :b/c 2}"),
+            Ok(map! {Keyword::from("a") => 1u8, Keyword::from("b/c") => 2})
+        );
+        assert_eq!(from_str("{ 1 :x}"), Ok(map! {1u8 => Keyword::new("x")}));
+        assert!(from_str::<BTreeMap<Keyword, u8>>("{\"a\" 1}").is_err());
+        assert!(from_str::<BTreeMap<u8, u8>>("{:a 1}").is_err());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn hash_maps_keep_their_keys() {
+        use std::collections::HashMap;
+
+        let mut by_tuple = HashMap::new();
+        by_tuple.insert((1u8, 'a'), "x".to_string());
+        by_tuple.insert((2, 'b'), "y".to_string());
+        round_trip(&by_tuple);
+
+        let by_keyword: HashMap<Keyword, bool> = from_str("{:on true :off false}").unwrap();
+        assert_eq!(by_keyword.get("on"), Some(&true));
+        assert_eq!(by_keyword.get("off"), Some(&false));
+    }
+
+    #[test]
+    fn durations() {
+        assert_eq!(
+            to_string(&Duration::new(90, 500_000_000)),
+            "#duration {:secs 90, :nanos 500000000}"
+        );
+        round_trip(&Duration::ZERO);
+        round_trip(&Duration::from_nanos(1));
+        round_trip(&Duration::MAX);
+        assert!(from_str::<Duration>("#duration {:secs 1 :nanos 1000000000}").is_err());
+        assert!(from_str::<Duration>("{:secs 1 :nanos 0}").is_err());
+    }
+
+    #[test]
+    fn addresses() {
+        let localhost = IpAddr::from([127, 0, 0, 1]);
+        assert_eq!(to_string(&localhost), "#ip \"127.0.0.1\"");
+        assert_eq!(
+            to_string(&SocketAddr::new(Ipv6Addr::LOCALHOST.into(), 8080)),
+            "#socket \"[::1]:8080\""
+        );
+        round_trip(&localhost);
+        round_trip(&IpAddr::from(Ipv6Addr::new(
+            0x2001, 0xdb8, 0, 0, 0, 0, 0, 1,
+        )));
+        round_trip(&Ipv4Addr::BROADCAST);
+        round_trip(&Ipv6Addr::UNSPECIFIED);
+        round_trip(&SocketAddr::new(localhost, 80));
+        round_trip(&SocketAddrV4::new(Ipv4Addr::LOCALHOST, 443));
+        round_trip(&SocketAddrV6::new(Ipv6Addr::LOCALHOST, 22, 0, 0));
+        assert!(from_str::<IpAddr>("\"127.0.0.1\"").is_err());
+        assert!(from_str::<Ipv4Addr>("#ip \"::1\"").is_err());
+        assert!(from_str::<SocketAddr>("#ip \"127.0.0.1\"").is_err());
+    }
+
+    #[test]
+    fn bytes_are_base64() {
+        assert_eq!(to_string(&Bytes(vec![])), "#base64 \"\"");
+        assert_eq!(to_string(&Bytes(b"f".to_vec())), "#base64 \"Zg==\"");
+        assert_eq!(to_string(&Bytes(b"fo".to_vec())), "#base64 \"Zm8=\"");
+        assert_eq!(
+            to_string(&Bytes(b"foobar".to_vec())),
+            "#base64 \"Zm9vYmFy\""
+        );
+        round_trip(&Bytes((0..=255).collect()));
+        round_trip(&vec![Bytes(vec![1]), Bytes(vec![2, 3])]);
+        for bad in [
+            "#base64 \"Zg=\"",
+            "#base64 \"Z===\"",
+            "#base64 \"Zg==Zg==\"",
+            "#base64 \"Zm9*\"",
+            "\"Zg==\"",
+        ] {
+            assert!(from_str::<Bytes>(bad).is_err(), "{bad}");
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn system_times_are_insts() {
+        use std::time::{SystemTime, UNIX_EPOCH};
+
+        let at = |secs, nanos| UNIX_EPOCH + Duration::new(secs, nanos);
+        assert_eq!(to_string(&UNIX_EPOCH), "#inst \"1970-01-01T00:00:00Z\"");
+        assert_eq!(
+            to_string(&at(482_196_050, 520_000_000)),
+            "#inst \"1985-04-12T23:20:50.52Z\""
+        );
+        assert_eq!(
+            to_string(&(UNIX_EPOCH - Duration::new(1, 500_000_000))),
+            "#inst \"1969-12-31T23:59:58.5Z\""
+        );
+        assert_eq!(
+            from_str("#inst \"1985-04-12T19:20:50.52-04:00\""),
+            Ok(at(482_196_050, 520_000_000))
+        );
+        assert_eq!(
+            from_str("#inst \"2000-02-29T00:00:00.000000001123+00:00\""),
+            Ok(at(951_782_400, 1))
+        );
+
+        round_trip(&SystemTime::now());
+        round_trip(&at(4_102_444_799, 999_999_999));
+        round_trip(&(UNIX_EPOCH - Duration::new(2_208_988_800, 1)));
+        for bad in [
+            "\"1970-01-01T00:00:00Z\"",
+            "#inst \"1970-01-01\"",
+            "#inst \"1970-01-01T00:00:00\"",
+            "#inst \"1970-02-30T00:00:00Z\"",
+            "#inst \"1970-01-01T24:00:00Z\"",
+            "#inst \"1970-01-01T00:00:00.Z\"",
+            "#inst \"1970-01-01T00:00:00+0100\"",
+            "#inst \"10000-01-01T00:00:00Z\"",
+            "#inst \"+999-01-01T00:00:00Z\"",
+            "#inst \"+1234567890-01-01T00:00:00Z\"",
+        ] {
+            assert!(from_str::<SystemTime>(bad).is_err(), "{bad}");
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn years_outside_four_digits_are_signed() {
+        use std::time::{SystemTime, UNIX_EPOCH};
+
+        let days = |days: u64| Duration::from_hours(days * 24);
+        let year_10000 = UNIX_EPOCH + days(2_932_897);
+        let year_0 = UNIX_EPOCH - days(719_528);
+        let nano = Duration::from_nanos(1);
+        let cases = [
+            (year_10000 - nano, "9999-12-31T23:59:59.999999999Z"),
+            (year_10000, "+10000-01-01T00:00:00Z"),
+            (year_0, "0000-01-01T00:00:00Z"),
+            (year_0 - nano, "-0001-12-31T23:59:59.999999999Z"),
+        ];
+        for (time, inst) in cases {
+            assert_eq!(to_string(&time), format!("#inst \"{inst}\""));
+            round_trip(&time);
+        }
+        assert_eq!(
+            from_str::<SystemTime>("#inst \"-0001-03-01T00:00:00+01:00\""),
+            Ok(year_0 - days(306) - Duration::from_hours(1))
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn paths_are_strings() {
+        use std::path::PathBuf;
+
+        let path = PathBuf::from("/var/log/app \"1\".log");
+        assert_eq!(to_string(&path), "\"/var/log/app \\\"1\\\".log\"");
+        assert_eq!(to_string(path.as_path()), to_string(&path));
+        round_trip(&path);
+        round_trip(&PathBuf::new());
+        assert!(from_str::<PathBuf>(":path").is_err());
+
+        #[cfg(unix)]
+        {
+            use edn_rs::ToEdn;
+            use std::ffi::OsStr;
+            use std::os::unix::ffi::OsStrExt;
+
+            let path = PathBuf::from(OsStr::from_bytes(b"/tmp/a\xff"));
+            assert_eq!(to_string(&path), "\"/tmp/a\u{fffd}\"");
+            assert_eq!(path.to_edn(), Edn::Str("/tmp/a\u{fffd}".into()));
+        }
+    }
+}
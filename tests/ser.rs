@@ -1,8 +1,10 @@
+#![cfg(feature = "derive")]
+
 #[cfg(test)]
 mod tests {
     use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 
-    use edn_derive::Serialize;
+    use edn_rs::Serialize;
     use edn_rs::{hmap, hset, map, set};
 
     #[test]
@@ -70,7 +72,7 @@ fn pub_struct() {
 }
 
 mod helper {
-    use edn_derive::Serialize;
+    use edn_rs::Serialize;
 
     #[derive(Serialize, Debug, Clone)]
     pub struct Edn {